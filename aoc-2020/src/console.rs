@@ -0,0 +1,288 @@
+use aoc_utils::{Input, Line, ParseResult};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+
+pub type Value = i64;
+pub type Program = Vec<Instruction>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Opcode {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub argument: Value,
+}
+
+impl Instruction {
+    pub fn new(opcode: Opcode, argument: Value) -> Self {
+        Instruction { opcode, argument }
+    }
+
+    /// The instruction with `jmp` and `nop` swapped, `None` for `acc`.
+    pub fn flipped(self) -> Option<Self> {
+        let opcode = match self.opcode {
+            Opcode::Jmp => Opcode::Nop,
+            Opcode::Nop => Opcode::Jmp,
+            Opcode::Acc => return None,
+        };
+
+        Some(Instruction::new(opcode, self.argument))
+    }
+
+    /// Where execution continues after this instruction at `position`.
+    fn target(self, position: usize) -> Option<usize> {
+        match self.opcode {
+            Opcode::Jmp => position.checked_add_signed(self.argument as isize),
+            Opcode::Acc | Opcode::Nop => Some(position + 1),
+        }
+    }
+}
+
+/// Prints the instruction back in source syntax.
+impl Display for Instruction {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self.opcode {
+            Opcode::Acc => "acc",
+            Opcode::Jmp => "jmp",
+            Opcode::Nop => "nop",
+        };
+
+        write!(formatter, "{} {:+}", name, self.argument)
+    }
+}
+
+/// What happened on a step, or why a run stopped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StepResult {
+    Executed,
+    Breakpoint,
+    /// Execution reached the instruction just past the end of the program.
+    Halted,
+    /// The next instruction has already run once.
+    LoopDetected,
+    /// A jump left the program without landing just past its end.
+    OutOfBounds,
+}
+
+/// The handheld game console: one accumulator and an instruction pointer.
+#[derive(Debug, Clone)]
+pub struct Console<'a> {
+    program: &'a [Instruction],
+    position: Option<usize>,
+    accumulator: Value,
+    visited: HashSet<usize>,
+    trace: Vec<usize>,
+    breakpoints: HashSet<usize>,
+}
+
+impl<'a> Console<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Console {
+            program,
+            position: Some(0),
+            accumulator: 0,
+            visited: HashSet::new(),
+            trace: Vec::new(),
+            breakpoints: HashSet::new(),
+        }
+    }
+
+    pub fn accumulator(&self) -> Value {
+        self.accumulator
+    }
+
+    /// Index of the next instruction, `None` after jumping before the start.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// Positions of the executed instructions, in order.
+    pub fn trace(&self) -> &[usize] {
+        &self.trace
+    }
+
+    /// Makes [`Console::run`] stop before executing the instruction at `position`.
+    pub fn add_breakpoint(&mut self, position: usize) {
+        self.breakpoints.insert(position);
+    }
+
+    pub fn remove_breakpoint(&mut self, position: usize) {
+        self.breakpoints.remove(&position);
+    }
+
+    /// Executes the next instruction unless the program has halted, left its bounds or would
+    /// repeat an instruction.
+    pub fn step(&mut self) -> StepResult {
+        let position = match self.position {
+            Some(position) if position == self.program.len() => return StepResult::Halted,
+            Some(position) if position < self.program.len() => position,
+            _ => return StepResult::OutOfBounds,
+        };
+
+        if !self.visited.insert(position) {
+            return StepResult::LoopDetected;
+        }
+
+        let instruction = self.program[position];
+
+        if instruction.opcode == Opcode::Acc {
+            self.accumulator += instruction.argument;
+        }
+
+        self.trace.push(position);
+        self.position = instruction.target(position);
+
+        StepResult::Executed
+    }
+
+    /// Steps until the program stops or reaches a breakpoint, always executing at least once.
+    pub fn run(&mut self) -> StepResult {
+        loop {
+            match self.step() {
+                StepResult::Executed => {
+                    if self
+                        .position
+                        .is_some_and(|position| self.breakpoints.contains(&position))
+                    {
+                        return StepResult::Breakpoint;
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Finds the single `jmp` or `nop` whose flip lets the program halt, with its replacement, or
+/// `None` if the program already halts or no flip helps. Works backwards from the end to find
+/// every instruction that already leads there, then picks the instruction on the looping path
+/// whose flipped target is one of them.
+pub fn repair(program: &[Instruction]) -> Option<(usize, Instruction)> {
+    let end = program.len();
+    let mut sources: Vec<Vec<usize>> = vec![Vec::new(); end + 1];
+
+    for (position, instruction) in program.iter().enumerate() {
+        if let Some(target) = instruction.target(position).filter(|&target| target <= end) {
+            sources[target].push(position);
+        }
+    }
+
+    let mut halting = HashSet::from([end]);
+    let mut queue = VecDeque::from([end]);
+
+    while let Some(target) = queue.pop_front() {
+        for &source in &sources[target] {
+            if halting.insert(source) {
+                queue.push_back(source);
+            }
+        }
+    }
+
+    let mut console = Console::new(program);
+
+    if console.run() == StepResult::Halted {
+        return None;
+    }
+
+    console.trace().iter().find_map(|&position| {
+        let flipped = program[position].flipped()?;
+
+        flipped
+            .target(position)
+            .filter(|target| halting.contains(target))
+            .map(|_| (position, flipped))
+    })
+}
+
+pub fn parse_program(input: &Input) -> ParseResult<Program> {
+    let lines: Vec<String> = input.lines(true).collect();
+
+    aoc_utils::numbered(&lines)
+        .map(|line| parse_instruction(&line))
+        .collect()
+}
+
+fn parse_instruction(line: &Line) -> ParseResult<Instruction> {
+    let mut parts = line.split_whitespace();
+
+    let opcode = match parts.next() {
+        Some("acc") => Opcode::Acc,
+        Some("jmp") => Opcode::Jmp,
+        Some("nop") => Opcode::Nop,
+        Some(opcode) => return Err(line.error(opcode, "acc, jmp or nop")),
+        None => return Err(line.error_end("operation")),
+    };
+
+    let argument = parts.next().ok_or_else(|| line.error_end("argument"))?;
+    let argument = line.parse(argument, "number")?;
+
+    Ok(Instruction::new(opcode, argument))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+    fn program(text: &str) -> Program {
+        parse_program(&Input::literal(text)).expect("program")
+    }
+
+    #[test]
+    fn detects_loops_and_bounds() {
+        let program = program(PROGRAM);
+        let mut console = Console::new(&program);
+
+        assert_eq!(console.run(), StepResult::LoopDetected);
+        assert_eq!(console.accumulator(), 5);
+        assert_eq!(console.trace(), [0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(program[4].to_string(), "jmp -3");
+
+        let escape = self::program("acc +2\njmp +5");
+
+        assert_eq!(Console::new(&escape).run(), StepResult::OutOfBounds);
+        assert_eq!(Console::new(&escape[..1]).run(), StepResult::Halted);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let program = program(PROGRAM);
+        let mut console = Console::new(&program);
+
+        console.add_breakpoint(3);
+
+        assert_eq!(console.run(), StepResult::Breakpoint);
+        assert_eq!(console.position(), Some(3));
+        assert_eq!(console.accumulator(), 2);
+        assert_eq!(console.step(), StepResult::Executed);
+        assert_eq!(console.accumulator(), 5);
+    }
+
+    #[test]
+    fn repairs_the_looping_program() {
+        let mut program = program(PROGRAM);
+
+        let (position, flipped) = repair(&program).expect("repair");
+
+        assert_eq!(position, 7);
+
+        program[position] = flipped;
+
+        let mut console = Console::new(&program);
+
+        assert_eq!(console.run(), StepResult::Halted);
+        assert_eq!(console.accumulator(), 8);
+    }
+
+    #[test]
+    fn leaves_halting_programs_alone() {
+        assert_eq!(repair(&program("nop +0")), None);
+        assert_eq!(repair(&program("acc +1\nnop +0\nacc +2")), None);
+    }
+}
@@ -1,23 +1,55 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 
 const SUM: i32 = 2020;
 
-pub fn run() {
-    let numbers: Vec<i32> = aoc_utils::read_lines("inputs/day01.txt", true)
-        .map(|line| line.parse().expect("number"))
-        .collect();
+pub struct Day01;
 
-    for a in &numbers {
-        for b in &numbers {
-            for c in &numbers {
-                if a + b + c == SUM {
-                    println!("A + B + C: {:?}", a * b * c);
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        aoc_utils::numbered(&lines)
+            .map(|line| line.parse(line.text, "number"))
+            .collect()
+    }
+
+    fn part1(numbers: &Self::Input) -> Self::Part1 {
+        for a in numbers {
+            for b in numbers {
+                if a + b == SUM {
+                    return a * b;
                 }
             }
+        }
+
+        0
+    }
 
-            if a + b == SUM {
-                println!("A + B: {:?}", a * b);
+    fn part2(numbers: &Self::Input) -> Self::Part2 {
+        for a in numbers {
+            for b in numbers {
+                for c in numbers {
+                    if a + b + c == SUM {
+                        return a * b * c;
+                    }
+                }
             }
         }
+
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day01;
+
+    aoc_utils::examples! {
+        Day01,
+        example: "day01.txt" => part1 "514579", part2 "241861950";
     }
 }
@@ -1,9 +1,9 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use rayon::prelude::*;
 use regex::Regex;
 
 #[derive(Debug)]
-struct Entry {
+pub struct Entry {
     pub left: usize,
     pub right: usize,
     pub letter: String,
@@ -13,18 +13,18 @@ struct Entry {
 impl Entry {
     const RE_ENTRY: &'static str = r"(?P<left>\d+)-(?P<right>\d+) (?P<letter>\w): (?P<password>.*)";
 
-    pub fn from_line(line: &str) -> Entry {
+    pub fn from_line(line: &Line) -> ParseResult<Entry> {
         let caps = Regex::new(Entry::RE_ENTRY)
             .expect("regex")
-            .captures(line)
-            .expect("captures");
+            .captures(line.text)
+            .ok_or_else(|| line.error(line.text, "policy and password"))?;
 
-        Entry {
-            left: caps["left"].to_string().parse().expect("number"),
-            right: caps["right"].to_string().parse().expect("number"),
+        Ok(Entry {
+            left: line.parse(&caps["left"], "number")?,
+            right: line.parse(&caps["right"], "number")?,
             letter: caps["letter"].to_string(),
             password: caps["password"].to_string(),
-        }
+        })
     }
 
     pub fn is_valid_sled(self: &Entry) -> bool {
@@ -48,20 +48,41 @@ impl Entry {
     }
 }
 
-pub fn run() {
-    let entries: Vec<Entry> = aoc_utils::read_lines("inputs/day02.txt", true)
-        .collect::<Vec<String>>()
-        .par_iter()
-        .map(|line| Entry::from_line(&line))
-        .collect();
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        aoc_utils::numbered(&lines)
+            .collect::<Vec<_>>()
+            .par_iter()
+            .map(Entry::from_line)
+            .collect()
+    }
+
+    fn part1(entries: &Self::Input) -> Self::Part1 {
+        entries.iter().filter(|entry| entry.is_valid_sled()).count()
+    }
 
-    let count_sled = entries.iter().filter(|entry| entry.is_valid_sled()).count();
+    fn part2(entries: &Self::Input) -> Self::Part2 {
+        entries
+            .iter()
+            .filter(|entry| entry.is_valid_toboggan())
+            .count()
+    }
+}
 
-    let count_toboggan = entries
-        .iter()
-        .filter(|entry| entry.is_valid_toboggan())
-        .count();
+#[cfg(test)]
+mod tests {
+    use super::Day02;
 
-    println!("{}", count_sled);
-    println!("{}", count_toboggan);
+    aoc_utils::examples! {
+        Day02,
+        example: "day02.txt" => part1 "2", part2 "1";
+    }
 }
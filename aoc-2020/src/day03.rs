@@ -1,32 +1,58 @@
-use aoc_utils;
+use aoc_utils::{Grid, Input, ParseResult, Solution};
+
+type Map = Grid<char>;
 
 const TREE: char = '#';
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day03.txt", true).collect();
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        Grid::parse_with(
+            aoc_utils::numbered(&lines),
+            |char| matches!(char, TREE | '.').then_some(char),
+            "'.' or '#'",
+        )
+    }
 
-    let counts: Vec<usize> = slopes
-        .into_iter()
-        .map(|slope| count_trees(&lines, slope))
-        .collect();
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        count_trees(map, (3, 1))
+    }
 
-    let count_slope = counts.get(1).expect("count");
-    let count_all: usize = counts.iter().product();
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
 
-    println!("{}", count_slope);
-    println!("{}", count_all);
+        slopes
+            .into_iter()
+            .map(|slope| count_trees(map, slope))
+            .product()
+    }
 }
 
-fn count_trees(map: &Vec<String>, (slope_right, slope_down): (usize, usize)) -> usize {
-    map.iter()
+fn count_trees(map: &Map, (slope_right, slope_down): (usize, usize)) -> usize {
+    (0..map.height())
         .step_by(slope_down)
         .enumerate()
-        .filter(|(i, row)| is_tree(row, i * slope_right))
+        .filter(|&(i, row)| is_tree(map, (i * slope_right, row)))
         .count()
 }
 
-fn is_tree(row: &str, right: usize) -> bool {
-    row.chars().cycle().nth(right).expect("position") == TREE
+fn is_tree(map: &Map, (right, row): (usize, usize)) -> bool {
+    map.get_wrapped((right as isize, row as isize)) == &TREE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day03;
+
+    aoc_utils::examples! {
+        Day03,
+        example: "day03.txt" => part1 "7", part2 "336";
+    }
 }
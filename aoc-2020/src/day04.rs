@@ -1,36 +1,54 @@
-use aoc_utils;
+use aoc_utils::{Block, Input, ParseResult, Solution};
 use rayon::prelude::*;
 use regex::Regex;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day04.txt", false).collect();
+type Passport = Vec<String>;
 
-    let passports = lines
-        .split(|line| line.is_empty())
-        .map(|lines| extract_fields(lines));
+pub struct Day04;
 
-    let required_valid: Vec<Vec<&str>> = passports
-        .filter(|fields| is_required_rule_valid(fields))
-        .collect();
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let value_valid = required_valid
-        .par_iter()
-        .filter(|fields| is_value_rule_valid(fields))
-        .count();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.parse_blocks(parse_passport)
+    }
+
+    fn part1(passports: &Self::Input) -> Self::Part1 {
+        passports
+            .iter()
+            .filter(|fields| is_required_rule_valid(fields))
+            .count()
+    }
 
-    println!("{}", required_valid.len());
-    println!("{}", value_valid);
+    fn part2(passports: &Self::Input) -> Self::Part2 {
+        passports
+            .par_iter()
+            .filter(|fields| is_required_rule_valid(fields))
+            .filter(|fields| is_value_rule_valid(fields))
+            .count()
+    }
 }
 
-fn extract_fields(lines: &[String]) -> Vec<&str> {
-    lines
-        .iter()
-        .flat_map(|line| line.split_whitespace())
-        .collect()
+fn parse_passport(block: Block) -> ParseResult<Passport> {
+    let mut passport = Passport::new();
+
+    for line in block.lines() {
+        for field in line.split_whitespace() {
+            if !field.contains(':') {
+                return Err(line.error(field, "key:value field"));
+            }
+
+            passport.push(field.to_string());
+        }
+    }
+
+    Ok(passport)
 }
 
-fn is_required_rule_valid(fields: &[&str]) -> bool {
-    let required = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+fn is_required_rule_valid(fields: &[String]) -> bool {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
 
     let found = fields
         .iter()
@@ -40,7 +58,7 @@ fn is_required_rule_valid(fields: &[&str]) -> bool {
     found >= required.len()
 }
 
-fn is_value_rule_valid(fields: &[&str]) -> bool {
+fn is_value_rule_valid(fields: &[String]) -> bool {
     let valid_values = fields.iter().filter(|field| is_field_valid(field)).count();
 
     valid_values == fields.len()
@@ -64,21 +82,21 @@ fn is_field_valid(field: &str) -> bool {
 fn is_birth_year_valid(value: &str) -> bool {
     value
         .parse::<usize>()
-        .map(|number| number >= 1920 && number <= 2002)
+        .map(|number| (1920..=2002).contains(&number))
         .unwrap_or(false)
 }
 
 fn is_issue_year_valid(value: &str) -> bool {
     value
         .parse::<usize>()
-        .map(|number| number >= 2010 && number <= 2020)
+        .map(|number| (2010..=2020).contains(&number))
         .unwrap_or(false)
 }
 
 fn is_expiration_year_valid(value: &str) -> bool {
     value
         .parse::<usize>()
-        .map(|number| number >= 2020 && number <= 2030)
+        .map(|number| (2020..=2030).contains(&number))
         .unwrap_or(false)
 }
 
@@ -91,8 +109,8 @@ fn is_height_valid(value: &str) -> bool {
             let unit = caps["unit"].to_string();
 
             match &unit[..] {
-                "cm" => number >= 150 && number <= 193,
-                "in" => number >= 59 && number <= 76,
+                "cm" => (150..=193).contains(&number),
+                "in" => (59..=76).contains(&number),
                 _ => false,
             }
         })
@@ -104,9 +122,20 @@ fn is_hair_color_valid(value: &str) -> bool {
 }
 
 fn is_eye_color_valid(value: &str) -> bool {
-    vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value)
+    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value)
 }
 
 fn is_passport_id_valid(value: &str) -> bool {
     Regex::new(r"\b\d{9}\b").expect("regex").is_match(value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day04;
+
+    aoc_utils::examples! {
+        Day04,
+        example: "day04.txt" => part1 "2";
+        example_validation: "day04-2.txt" => part2 "4";
+    }
+}
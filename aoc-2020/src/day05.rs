@@ -1,25 +1,58 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 
-pub fn run() {
-    let mut seats: Vec<usize> = aoc_utils::read_lines("inputs/day05.txt", true)
-        .map(|seat| find_row_column(&seat))
-        .map(|(row, column)| row * 8 + column)
-        .collect();
+pub struct Day05;
 
-    seats.sort_unstable();
+impl Solution for Day05 {
+    type Input = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let last = seats.last().expect("last");
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let empty = seats
-        .windows(2)
-        .find(|pair| match pair {
-            [current, next] => current + 1 != *next,
+        let mut seats = aoc_utils::numbered(&lines)
+            .map(|line| parse_seat(&line))
+            .map(|seat| seat.map(|(row, column)| row * 8 + column))
+            .collect::<ParseResult<Vec<usize>>>()?;
+
+        seats.sort_unstable();
+
+        Ok(seats)
+    }
+
+    fn part1(seats: &Self::Input) -> Self::Part1 {
+        *seats.last().expect("last")
+    }
+
+    fn part2(seats: &Self::Input) -> Self::Part2 {
+        seats
+            .windows(2)
+            .find_map(|pair| match pair {
+                [current, next] if current + 1 != *next => Some(current + 1),
+                _ => None,
+            })
+            .expect("empty")
+    }
+}
+
+fn parse_seat(line: &Line) -> ParseResult<(usize, usize)> {
+    for (offset, char) in line.char_indices() {
+        let valid = match offset {
+            0..=6 => char == 'F' || char == 'B',
+            7..=9 => char == 'L' || char == 'R',
             _ => false,
-        })
-        .expect("empty");
+        };
 
-    println!("{}", last);
-    println!("{:?}", empty);
+        if !valid {
+            return Err(line.error_at(offset, "'F'/'B' row then 'L'/'R' column"));
+        }
+    }
+
+    if line.len() < 10 {
+        return Err(line.error_end("10 characters"));
+    }
+
+    Ok(find_row_column(line))
 }
 
 fn find_row_column(seat: &str) -> (usize, usize) {
@@ -51,3 +84,13 @@ fn find_seat(seat: &str, min: usize, max: usize) -> usize {
         find_seat(remaining, min, max)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day05;
+
+    aoc_utils::examples! {
+        Day05,
+        example: "day05.txt" => part1 "820";
+    }
+}
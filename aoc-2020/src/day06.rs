@@ -1,45 +1,51 @@
-use aoc_utils;
+use aoc_utils::{Block, Input, Line, ParseResult, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-type Group<'a> = Vec<Person<'a>>;
-type Person<'a> = Vec<Answer<'a>>;
-type Answer<'a> = &'a str;
+type Group = Vec<Person>;
+type Person = Vec<Answer>;
+type Answer = char;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day06.txt", false).collect();
+pub struct Day06;
 
-    let groups: Vec<Group> = lines
-        .split(|line| line.is_empty())
-        .map(|group| parse_group(group))
-        .collect();
+impl Solution for Day06 {
+    type Input = Vec<Group>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let unique: usize = count_unique(&groups);
-    let all: usize = count_all(&groups);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.parse_blocks(parse_group)
+    }
 
-    println!("{:?}", unique);
-    println!("{:?}", all);
+    fn part1(groups: &Self::Input) -> Self::Part1 {
+        count_unique(groups)
+    }
+
+    fn part2(groups: &Self::Input) -> Self::Part2 {
+        count_all(groups)
+    }
 }
 
-fn parse_group(group: &[String]) -> Group {
-    group.iter().map(|person| parse_person(person)).collect()
+fn parse_group(group: Block) -> ParseResult<Group> {
+    group.lines().map(|person| parse_person(&person)).collect()
 }
 
-fn parse_person(person: &str) -> Person {
-    person
-        .split("")
-        .filter(|answer| !answer.is_empty())
-        .collect()
+fn parse_person(person: &Line) -> ParseResult<Person> {
+    if let Some(offset) = person.find(|char: char| !char.is_ascii_lowercase()) {
+        return Err(person.error_at(offset, "answer 'a' to 'z'"));
+    }
+
+    Ok(person.chars().collect())
 }
 
 fn count_unique(groups: &[Group]) -> usize {
     groups
         .iter()
         .map(|group| {
-            let mut answers: HashSet<&str> = HashSet::new();
+            let mut answers: HashSet<Answer> = HashSet::new();
 
             for answer in group.iter().flatten() {
-                answers.insert(answer);
+                answers.insert(*answer);
             }
 
             answers.len()
@@ -51,10 +57,10 @@ fn count_all(groups: &[Group]) -> usize {
     groups
         .iter()
         .map(|group| {
-            let mut answer_count: HashMap<&str, usize> = HashMap::new();
+            let mut answer_count: HashMap<Answer, usize> = HashMap::new();
 
             for answer in group.iter().flatten() {
-                let count = answer_count.entry(answer).or_insert(0);
+                let count = answer_count.entry(*answer).or_insert(0);
 
                 *count += 1;
             }
@@ -66,3 +72,13 @@ fn count_all(groups: &[Group]) -> usize {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day06;
+
+    aoc_utils::examples! {
+        Day06,
+        example: "day06.txt" => part1 "11", part2 "6";
+    }
+}
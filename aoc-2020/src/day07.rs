@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashMap;
@@ -9,49 +9,61 @@ type Count = usize;
 type RuleMap = HashMap<Color, RestrictionMap>;
 type RestrictionMap = HashMap<Color, Count>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day07.txt", true).collect();
+pub struct Day07;
 
-    let rules = parse_rules(lines);
+impl Solution for Day07 {
+    type Input = RuleMap;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let shiny_gold_outwards = count_outwards(&rules, "shiny gold");
-    let shiny_gold_inward = count_inward(&rules, "shiny gold") - 1;
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", shiny_gold_outwards);
-    println!("{:?}", shiny_gold_inward);
-}
+        parse_rules(&lines)
+    }
 
-fn parse_rules(lines: Vec<String>) -> RuleMap {
-    lines.par_iter().map(|line| parse_rule(line)).collect()
+    fn part1(rules: &Self::Input) -> Self::Part1 {
+        count_outwards(rules, "shiny gold")
+    }
+
+    fn part2(rules: &Self::Input) -> Self::Part2 {
+        count_inward(rules, "shiny gold") - 1
+    }
 }
 
-fn parse_rule(line: &str) -> (Color, RestrictionMap) {
-    let mut parts = line.split("bags contain");
+fn parse_rules(lines: &[String]) -> ParseResult<RuleMap> {
+    aoc_utils::numbered(lines)
+        .collect::<Vec<_>>()
+        .par_iter()
+        .map(parse_rule)
+        .collect()
+}
 
-    let rule_color = parts.next().expect("rule_color").trim().to_string();
+fn parse_rule(line: &Line) -> ParseResult<(Color, RestrictionMap)> {
+    let (rule_color, rule_restrictions) = line
+        .split_once("bags contain")
+        .ok_or_else(|| line.error_end("'bags contain'"))?;
 
-    let rule_restrictions: RestrictionMap = parts
-        .next()
-        .expect("rule_restrictions")
+    let rule_restrictions: RestrictionMap = rule_restrictions
         .trim()
-        .split(",")
+        .split(',')
         .filter(|restriction| !restriction.contains("no other"))
-        .map(|restriction| parse_restriction(restriction))
-        .collect();
+        .map(|restriction| parse_restriction(line, restriction))
+        .collect::<ParseResult<_>>()?;
 
-    (rule_color, rule_restrictions)
+    Ok((rule_color.trim().to_string(), rule_restrictions))
 }
 
-fn parse_restriction(restriction: &str) -> (Color, Count) {
+fn parse_restriction(line: &Line, restriction: &str) -> ParseResult<(Color, Count)> {
     let caps = Regex::new(r"(?P<count>\d+)\s(?P<color>.+)\sbag")
         .expect("regex")
         .captures(restriction)
-        .expect("captures");
+        .ok_or_else(|| line.error(restriction, "count and color of bags"))?;
 
     let color = caps["color"].to_string();
-    let count: Count = caps["count"].to_string().parse().expect("count");
+    let count: Count = line.parse(&caps["count"], "count")?;
 
-    (color, count)
+    Ok((color, count))
 }
 
 fn count_outwards(rules: &RuleMap, bag_color: &str) -> usize {
@@ -89,3 +101,14 @@ fn count_inward(rules: &RuleMap, bag_color: &str) -> usize {
 
     1 + count
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day07;
+
+    aoc_utils::examples! {
+        Day07,
+        example: "day07.txt" => part1 "4", part2 "32";
+        example_nested: "day07-2.txt" => part2 "126";
+    }
+}
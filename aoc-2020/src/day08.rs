@@ -1,93 +1,46 @@
-use aoc_utils;
-use std::collections::HashSet;
+use crate::console::{self, Console, Program, Value};
+use aoc_utils::{Input, ParseResult, Solution};
 
-type ID = usize;
-type Operation = String;
-type Argument = i32;
-type Instruction = (ID, Operation, Argument);
+pub struct Day08;
 
-type Accumulator = i32;
-type Exit = i32;
-type Status = (Exit, Accumulator);
+impl Solution for Day08 {
+    type Input = Program;
+    type Part1 = Value;
+    type Part2 = Value;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day08.txt", true).collect();
-
-    let instructions = parse_instructions(lines);
-
-    let (_, accumulator_loop) = execute(&instructions);
-    let (_, accumulator_fixed) = brute_force(&instructions);
-
-    println!("{}", accumulator_loop);
-    println!("{}", accumulator_fixed);
-}
-
-fn parse_instructions(lines: Vec<String>) -> Vec<Instruction> {
-    lines
-        .iter()
-        .enumerate()
-        .map(|(id, line)| {
-            let mut parts = line.split_whitespace();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        console::parse_program(input)
+    }
 
-            let operation = parts.next().expect("operation").to_string();
-            let argument = parts.next().expect("argument").parse().expect("number");
+    fn part1(program: &Self::Input) -> Self::Part1 {
+        let mut console = Console::new(program);
 
-            (id, operation, argument)
-        })
-        .collect()
-}
+        console.run();
 
-fn execute(instructions: &Vec<Instruction>) -> Status {
-    let mut processed = HashSet::new();
+        console.accumulator()
+    }
 
-    let mut position = 0;
-    let mut accumulator = 0;
-    let mut exit = 0;
+    fn part2(program: &Self::Input) -> Self::Part2 {
+        let mut program = program.clone();
 
-    while let Some((id, operation, argument)) = instructions.get(position as usize) {
-        if processed.contains(id) {
-            exit = -1;
-            break;
+        if let Some((position, flipped)) = console::repair(&program) {
+            program[position] = flipped;
         }
 
-        match &operation[..] {
-            "jmp" => position += argument,
-            "acc" => {
-                accumulator += argument;
-                position += 1;
-            }
-            _ => position += 1,
-        };
+        let mut console = Console::new(&program);
 
-        processed.insert(id);
-    }
+        console.run();
 
-    (exit, accumulator)
+        console.accumulator()
+    }
 }
 
-fn brute_force(instructions: &Vec<Instruction>) -> Status {
-    for (fix_id, fix_operation, _) in instructions {
-        let fix_operation = match fix_operation.as_str() {
-            "jmp" => String::from("nop"),
-            "nop" => String::from("jmp"),
-            _ => continue,
-        };
-
-        let instructions: Vec<Instruction> = instructions
-            .iter()
-            .map(|(id, operation, argument)| match id == fix_id {
-                true => (*id, fix_operation.to_string(), *argument),
-                false => (*id, operation.to_string(), *argument),
-            })
-            .collect();
+#[cfg(test)]
+mod tests {
+    use super::Day08;
 
-        let status = execute(&instructions);
-        let (exit, _) = status;
-
-        if exit == 0 {
-            return status;
-        }
+    aoc_utils::examples! {
+        Day08,
+        example: "day08.txt" => part1 "5", part2 "8";
     }
-
-    execute(instructions)
 }
@@ -1,16 +1,32 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 
-pub fn run() {
-    let numbers: Vec<u128> = aoc_utils::read_lines("inputs/day09.txt", true)
-        .map(|line| line.parse().expect("number"))
-        .collect();
+const PREAMBLE: usize = 25;
 
-    let invalid = find_invalid_number(&numbers, 25);
-    let range = find_contiguous_range(&numbers, invalid);
-    let sum = range.iter().min().expect("min") + range.iter().max().expect("max");
+pub struct Day09;
 
-    println!("{}", invalid);
-    println!("{:?}", sum);
+impl Solution for Day09 {
+    type Input = Vec<u128>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        aoc_utils::numbered(&lines)
+            .map(|line| line.parse(line.text, "number"))
+            .collect()
+    }
+
+    fn part1(numbers: &Self::Input) -> Self::Part1 {
+        find_invalid_number(numbers, PREAMBLE)
+    }
+
+    fn part2(numbers: &Self::Input) -> Self::Part2 {
+        let invalid = find_invalid_number(numbers, PREAMBLE);
+        let range = find_contiguous_range(numbers, invalid);
+
+        range.iter().min().expect("min") + range.iter().max().expect("max")
+    }
 }
 
 fn find_invalid_number(numbers: &[u128], preamble: usize) -> u128 {
@@ -54,3 +70,13 @@ fn find_contiguous_range(numbers: &[u128], invalid: u128) -> &[u128] {
 
     &[]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day09;
+
+    aoc_utils::examples! {
+        Day09,
+        example: "day09.txt" => part1 "100", part2 "25";
+    }
+}
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -6,22 +6,35 @@ const MAX: u128 = 3;
 const OUTLET: u128 = 0;
 const DEVICE: u128 = 3;
 
-pub fn run() {
-    let adapters: Vec<u128> = aoc_utils::read_lines("inputs/day10.txt", true)
-        .map(|line| line.parse().expect("number"))
-        .collect();
+pub struct Day10;
 
-    let differences = find_differences(&adapters);
+impl Solution for Day10 {
+    type Input = Vec<u128>;
+    type Part1 = u128;
+    type Part2 = u128;
 
-    let count1 = differences.get(&1).unwrap_or(&0);
-    let count3 = differences.get(&3).unwrap_or(&0);
-    let count_differences = count1 * count3;
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let mut cache: HashMap<u128, u128> = HashMap::new();
-    let arrangements = find_arrangements(&adapters, 0, &mut cache);
+        aoc_utils::numbered(&lines)
+            .map(|line| line.parse(line.text, "number"))
+            .collect()
+    }
+
+    fn part1(adapters: &Self::Input) -> Self::Part1 {
+        let differences = find_differences(adapters);
+
+        let count1 = differences.get(&1).unwrap_or(&0);
+        let count3 = differences.get(&3).unwrap_or(&0);
+
+        count1 * count3
+    }
+
+    fn part2(adapters: &Self::Input) -> Self::Part2 {
+        let mut cache: HashMap<u128, u128> = HashMap::new();
 
-    println!("{:?}", count_differences);
-    println!("{:?}", arrangements);
+        find_arrangements(adapters, OUTLET, &mut cache)
+    }
 }
 
 fn find_differences(adapters: &[u128]) -> HashMap<u128, u128> {
@@ -33,7 +46,7 @@ fn find_differences(adapters: &[u128]) -> HashMap<u128, u128> {
         let next_adapter = find_next_adapters(adapters, jolts)
             .par_iter()
             .min()
-            .map(|adapter| *adapter);
+            .copied();
 
         if let Some(adapter) = next_adapter {
             let difference = adapter - jolts;
@@ -64,16 +77,13 @@ fn find_arrangements(adapters: &[u128], jolts: u128, cache: &mut HashMap<u128, u
         next_adapters
             .into_iter()
             .map(|next_adapter| {
-                cache
-                    .get(&next_adapter)
-                    .map(|count| *count)
-                    .unwrap_or_else(|| {
-                        let count = find_arrangements(adapters, next_adapter, cache);
+                cache.get(&next_adapter).copied().unwrap_or_else(|| {
+                    let count = find_arrangements(adapters, next_adapter, cache);
 
-                        cache.insert(next_adapter, count);
+                    cache.insert(next_adapter, count);
 
-                        count
-                    })
+                    count
+                })
             })
             .sum()
     }
@@ -83,6 +93,17 @@ fn find_next_adapters(adapters: &[u128], jolts: u128) -> Vec<u128> {
     adapters
         .par_iter()
         .filter(|adapter| **adapter > jolts && **adapter <= jolts + MAX)
-        .map(|adapter| *adapter)
+        .copied()
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day10;
+
+    aoc_utils::examples! {
+        Day10,
+        example: "day10.txt" => part1 "35", part2 "8";
+        example_larger: "day10-2.txt" => part1 "220", part2 "19208";
+    }
+}
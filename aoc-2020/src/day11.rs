@@ -1,167 +1,128 @@
-use aoc_utils;
-use rayon::prelude::*;
+use aoc_utils::{
+    Dense, Grid, Input, ParseResult, Position, Solution, Square8, Topology, Vec2, NEIGHBORS_8,
+};
 
-type Map = Vec<Vec<State>>;
+type Map = Grid<State>;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum State {
+pub enum State {
     Floor,
     Empty,
     Occupied,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day11.txt", true).collect();
+impl TryFrom<char> for State {
+    type Error = char;
 
-    let map = parse_map(&lines);
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            'L' => Ok(State::Empty),
+            '#' => Ok(State::Occupied),
+            '.' => Ok(State::Floor),
+            _ => Err(char),
+        }
+    }
+}
 
-    let stable_immediately = stabilize_map(map.clone(), 4, true);
-    let count_immediately = count_map(&stable_immediately, State::Occupied);
+pub struct Day11;
 
-    let stable_directions = stabilize_map(map, 5, false);
-    let count_directions = count_map(&stable_directions, State::Occupied);
+impl Solution for Day11 {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!("{:?}", count_immediately);
-    println!("{:?}", count_directions);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_map(&lines)
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        let mut seats = Dense::new(Square8, map.clone());
+
+        seats.settle(&|state, neighbors: &[State]| next_position_state(state, neighbors, 4));
+
+        count_map(seats.grid(), State::Occupied)
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        let mut seats = Dense::new(Sightlines::new(map), map.clone());
+
+        seats.settle(&|state, neighbors: &[State]| next_position_state(state, neighbors, 5));
+
+        count_map(seats.grid(), State::Occupied)
+    }
 }
 
-fn parse_map(lines: &Vec<String>) -> Map {
-    lines
-        .par_iter()
-        .map(|line| {
-            line.chars()
-                .map(|position| match position {
-                    'L' => State::Empty,
-                    '#' => State::Occupied,
-                    _ => State::Floor,
-                })
-                .collect()
-        })
-        .collect()
+fn parse_map(lines: &[String]) -> ParseResult<Map> {
+    Grid::parse(aoc_utils::numbered(lines), "'L', '#' or '.'")
 }
 
 fn count_map(map: &Map, count_state: State) -> usize {
-    map.par_iter()
-        .map(|columns| {
-            columns
-                .par_iter()
-                .filter(|state| state == &&count_state)
-                .count()
-        })
-        .sum()
+    map.values().filter(|state| state == &&count_state).count()
 }
 
-fn state_map(map: &Map, row: i32, column: i32) -> Option<&State> {
-    map.get(row as usize)
-        .map(|columns| columns.get(column as usize))
-        .flatten()
-}
+fn next_position_state(state: State, neighbors: &[State], adjacent_count: usize) -> State {
+    let occupied_count = neighbors
+        .iter()
+        .filter(|&&neighbor| neighbor == State::Occupied)
+        .count();
 
-fn stabilize_map(map: Map, adjacent_count: usize, adjacent_immediately: bool) -> Map {
-    let mut current_state = map;
+    match state {
+        State::Empty if occupied_count == 0 => State::Occupied,
+        State::Occupied if occupied_count >= adjacent_count => State::Empty,
+        _ => state,
+    }
+}
 
-    loop {
-        let next_state = next_map_state(&current_state, adjacent_count, adjacent_immediately);
+/// Each seat neighbours the first seat it sees in each of the eight directions.
+struct Sightlines(Grid<Vec<Position>>);
 
-        if next_state == current_state {
-            return current_state;
-        } else {
-            current_state = next_state;
-        }
+impl Sightlines {
+    fn new(map: &Map) -> Self {
+        Sightlines(map.map(|position, _| visible_seats(map, position)))
     }
 }
 
-fn next_map_state(map: &Map, adjacent_count: usize, adjacent_immediately: bool) -> Map {
-    map.par_iter()
-        .enumerate()
-        .map(|(row, columns)| {
-            columns
-                .par_iter()
-                .enumerate()
-                .map(|(column, state)| {
-                    next_position_state(
-                        map,
-                        row as i32,
-                        column as i32,
-                        state,
-                        adjacent_count,
-                        adjacent_immediately,
-                    )
-                })
-                .collect()
-        })
-        .collect()
-}
+impl Topology for Sightlines {
+    type Cell = Vec2<i64>;
 
-fn next_position_state(
-    map: &Map,
-    row: i32,
-    column: i32,
-    state: &State,
-    adjacent_count: usize,
-    adjacent_immediately: bool,
-) -> State {
-    if state == &State::Floor {
-        State::Floor
-    } else {
-        let count_directions: Vec<(i32, i32)> = (-1..2)
-            .flat_map(|y| (-1..2).map(move |x| (y, x)))
-            .filter(|(y, x)| y != &0 || x != &0)
-            .collect();
-
-        let occupied_count = if adjacent_immediately {
-            count_adjacent_immediately(map, row, column, count_directions)
-        } else {
-            count_adjacent_directions(map, row, column, count_directions)
-        };
-
-        if state == &State::Empty && occupied_count == 0 {
-            State::Occupied
-        } else if state == &State::Occupied && occupied_count >= adjacent_count {
-            State::Empty
-        } else {
-            *state
-        }
+    fn degree(&self) -> usize {
+        8
     }
-}
 
-fn count_adjacent_immediately(
-    map: &Map,
-    row: i32,
-    column: i32,
-    directions: Vec<(i32, i32)>,
-) -> usize {
-    directions
-        .par_iter()
-        .map(|(y, x)| state_map(map, row + y, column + x))
-        .filter_map(|state| state)
-        .filter(|state| **state == State::Occupied)
-        .count()
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        self.0[(cell.x as usize, cell.y as usize)]
+            .iter()
+            .map(|&(x, y)| Vec2::new(x as i64, y as i64))
+            .collect()
+    }
 }
 
-fn count_adjacent_directions(
-    map: &Map,
-    row: i32,
-    column: i32,
-    directions: Vec<(i32, i32)>,
-) -> usize {
-    directions
-        .par_iter()
-        .map(|(y, x)| {
-            let mut state = None;
-
-            for i in 1.. {
-                state = state_map(map, row + y * i, column + x * i);
-
-                match state {
-                    Some(State::Floor) => (),
-                    _ => break,
+/// The first seat seen in each of the eight directions, looking past floor.
+fn visible_seats(map: &Map, position: Position) -> Vec<Position> {
+    NEIGHBORS_8
+        .iter()
+        .filter_map(|&offset| {
+            let mut current = position;
+
+            loop {
+                current = map.offset(current, offset)?;
+
+                if map[current] != State::Floor {
+                    return Some(current);
                 }
             }
-
-            state
         })
-        .filter_map(|state| state)
-        .filter(|state| **state == State::Occupied)
-        .count()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day11;
+
+    aoc_utils::examples! {
+        Day11,
+        example: "day11.txt" => part1 "37", part2 "26";
+    }
 }
@@ -1,8 +1,7 @@
-use aoc_utils;
-use rayon::prelude::*;
+use aoc_utils::{Input, ParseResult, Solution};
 
 #[derive(Debug)]
-struct Instruction {
+pub struct Instruction {
     action: String,
     value: usize,
 }
@@ -15,7 +14,7 @@ struct Vector {
 
 impl Vector {
     fn new(x: i32, y: i32) -> Vector {
-        Vector { x: x, y: y }
+        Vector { x, y }
     }
 
     fn manhattan_distance(&self) -> i32 {
@@ -27,7 +26,7 @@ impl Vector {
             90 | -270 => Vector::new(-self.y, self.x),
             180 | -180 => Vector::new(-self.x, -self.y),
             270 | -90 => Vector::new(self.y, -self.x),
-            _ => self.clone(),
+            _ => *self,
         }
     }
 }
@@ -38,29 +37,49 @@ struct State {
     waypoint_translation: Vector,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day12.txt", true).collect();
+pub struct Day12;
 
-    let instructions = parse_instructions(&lines);
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = i32;
 
-    let state = follow_instructions(&instructions, false);
-    let state_waypoint = follow_instructions(&instructions, true);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", state.ship_translation.manhattan_distance());
-    println!("{}", state_waypoint.ship_translation.manhattan_distance());
+        parse_instructions(&lines)
+    }
+
+    fn part1(instructions: &Self::Input) -> Self::Part1 {
+        let state = follow_instructions(instructions, false);
+
+        state.ship_translation.manhattan_distance()
+    }
+
+    fn part2(instructions: &Self::Input) -> Self::Part2 {
+        let state_waypoint = follow_instructions(instructions, true);
+
+        state_waypoint.ship_translation.manhattan_distance()
+    }
 }
 
-fn parse_instructions(lines: &Vec<String>) -> Vec<Instruction> {
-    lines
-        .par_iter()
-        .map(|line| Instruction {
-            action: (&line[0..1]).to_string(),
-            value: (&line[1..]).to_string().parse().expect("number"),
+fn parse_instructions(lines: &[String]) -> ParseResult<Vec<Instruction>> {
+    aoc_utils::numbered(lines)
+        .map(|line| {
+            let action = match line.get(0..1) {
+                Some(action @ ("N" | "S" | "E" | "W" | "L" | "R" | "F")) => action,
+                _ => return Err(line.error_at(0, "action N, S, E, W, L, R or F")),
+            };
+
+            Ok(Instruction {
+                action: action.to_string(),
+                value: line.parse(&line[1..], "number")?,
+            })
         })
         .collect()
 }
 
-fn follow_instructions(instructions: &Vec<Instruction>, waypoint: bool) -> State {
+fn follow_instructions(instructions: &[Instruction], waypoint: bool) -> State {
     let mut state = if waypoint {
         State {
             ship_translation: Vector::new(0, 0),
@@ -81,7 +100,7 @@ fn follow_instructions(instructions: &Vec<Instruction>, waypoint: bool) -> State
 }
 
 fn follow_instruction(instruction: &Instruction, current: &State, waypoint: bool) -> State {
-    let mut next = current.clone();
+    let mut next = *current;
 
     if waypoint {
         match &instruction.action[..] {
@@ -130,7 +149,7 @@ fn follow_instruction(instruction: &Instruction, current: &State, waypoint: bool
         "R" => {
             next.waypoint_translation = current
                 .waypoint_translation
-                .rotate(instruction.value as i32 * -1);
+                .rotate(-(instruction.value as i32));
         }
         "F" => {
             next.ship_translation.x = current.ship_translation.x
@@ -144,3 +163,13 @@ fn follow_instruction(instruction: &Instruction, current: &State, waypoint: bool
 
     next
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day12;
+
+    aoc_utils::examples! {
+        Day12,
+        example: "day12.txt" => part1 "25", part2 "286";
+    }
+}
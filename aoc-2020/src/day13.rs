@@ -1,43 +1,68 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 
 type ID = u128;
 type Timestamp = u128;
 
 #[derive(Debug)]
-struct Notes {
+pub struct Notes {
     depart: u128,
     buses: Vec<(ID, Timestamp)>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day13.txt", true).collect();
+pub struct Day13;
 
-    let notes = parse_notes(&lines);
+impl Solution for Day13 {
+    type Input = Notes;
+    type Part1 = u128;
+    type Part2 = String;
 
-    let (bus, wait) = find_bus_wait(&notes);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let depart = find_match_depart(&notes);
+        parse_notes(&lines)
+    }
+
+    fn part1(notes: &Self::Input) -> Self::Part1 {
+        let (bus, wait) = find_bus_wait(notes);
 
-    println!("{}", bus * wait);
-    println!("{:?}", depart);
+        bus * wait
+    }
+
+    fn part2(notes: &Self::Input) -> Self::Part2 {
+        match find_match_depart(notes) {
+            Some(timestamp) => timestamp.to_string(),
+            None => "no solution".to_string(),
+        }
+    }
 }
 
-fn parse_notes(lines: &Vec<String>) -> Notes {
-    let depart = lines.get(0).expect("depart").parse().expect("number");
+fn parse_notes(lines: &[String]) -> ParseResult<Notes> {
+    let mut lines = aoc_utils::numbered(lines);
+
+    let line = lines.next().unwrap_or(Line::new(1, ""));
+    let depart = line.parse(line.text, "depart timestamp")?;
+
+    let line = lines.next().unwrap_or(Line::new(2, ""));
+
+    if line.is_empty() {
+        return Err(line.error_end("bus ids"));
+    }
 
-    let buses: Vec<(ID, Timestamp)> = lines
-        .get(1)
-        .expect("buses")
-        .split(",")
+    let buses: Vec<(ID, Timestamp)> = line
+        .split(',')
         .enumerate()
         .filter(|(_, id)| id != &"x")
-        .map(|(timestamp, id)| (id.parse().expect("number"), timestamp as u128))
-        .collect();
+        .map(|(timestamp, id)| match line.parse(id, "bus id or x")? {
+            0 => Err(line.error(id, "bus id above 0")),
+            id => Ok((id, timestamp as u128)),
+        })
+        .collect::<ParseResult<_>>()?;
 
-    Notes {
-        depart: depart,
-        buses: buses,
+    if buses.is_empty() {
+        return Err(line.error_at(0, "at least one bus id"));
     }
+
+    Ok(Notes { depart, buses })
 }
 
 fn find_bus_wait(notes: &Notes) -> (u128, u128) {
@@ -57,24 +82,24 @@ fn find_bus_wait(notes: &Notes) -> (u128, u128) {
     (id, wait)
 }
 
-fn find_match_depart(notes: &Notes) -> u128 {
-    let mut current_timestamp = 0;
-    let mut current_step = 1;
-
-    for (bus_id, bus_offset) in &notes.buses {
-        loop {
-            if (current_timestamp + bus_offset) % bus_id == 0 {
-                current_step = lcm_prime(current_step, *bus_id);
-                break;
-            } else {
-                current_timestamp += current_step;
-            }
-        }
-    }
+/// The earliest timestamp at which every bus leaves at its offset, `None` if there is none.
+fn find_match_depart(notes: &Notes) -> Option<u128> {
+    let congruences = notes
+        .buses
+        .iter()
+        .map(|&(bus_id, bus_offset)| (-(bus_offset as i128), bus_id as i128));
+
+    let (timestamp, _) = aoc_utils::crt(congruences)?;
 
-    current_timestamp
+    Some(timestamp as u128)
 }
 
-fn lcm_prime(a: u128, b: u128) -> u128 {
-    a * b
+#[cfg(test)]
+mod tests {
+    use super::Day13;
+
+    aoc_utils::examples! {
+        Day13,
+        example: "day13.txt" => part1 "295", part2 "1068781";
+    }
 }
@@ -1,5 +1,4 @@
-use aoc_utils;
-use rayon::prelude::*;
+use aoc_utils::{Input, ParseResult, Solution};
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -10,7 +9,7 @@ type Value = u64;
 type Program = Vec<Command>;
 
 #[derive(Debug, Clone)]
-struct Command {
+pub struct Command {
     action: Action,
     bitmask: String,
     address: Address,
@@ -23,49 +22,59 @@ enum Action {
     Value,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day14.txt", true).collect();
+pub struct Day14;
 
-    let program = parse_program(&lines);
+impl Solution for Day14 {
+    type Input = Program;
+    type Part1 = Value;
+    type Part2 = Value;
 
-    let memory_v1 = run_program_v1(&program);
-    let memory_v2 = run_program_v2(&program);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let memory_v1_sum: u64 = memory_v1.values().sum();
-    let memory_v2_sum: u64 = memory_v2.values().sum();
+        parse_program(&lines)
+    }
+
+    fn part1(program: &Self::Input) -> Self::Part1 {
+        run_program_v1(program).values().sum()
+    }
 
-    println!("{:?}", memory_v1_sum);
-    println!("{:?}", memory_v2_sum);
+    fn part2(program: &Self::Input) -> Self::Part2 {
+        run_program_v2(program).values().sum()
+    }
 }
 
-fn parse_program(lines: &Vec<String>) -> Program {
-    let mask_re = Regex::new(r"mask = (?P<bitmask>.+)").expect("mark regex");
-    let mem_re = Regex::new(r"mem\[(?P<address>\d+)\] = (?P<value>\d+)").expect("mem regex");
+fn parse_program(lines: &[String]) -> ParseResult<Program> {
+    let mask_re = Regex::new(r"^mask = (?P<bitmask>[01X]{36})$").expect("mark regex");
+    let mem_re = Regex::new(r"^mem\[(?P<address>\d+)\] = (?P<value>\d+)$").expect("mem regex");
 
-    lines
-        .par_iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
             if line.starts_with("mask") {
-                let caps = mask_re.captures(line).expect("captures");
+                let caps = mask_re
+                    .captures(line.text)
+                    .ok_or_else(|| line.error(line.text, "mask = <36 bits of 0, 1 or X>"))?;
                 let bitmask = caps["bitmask"].to_string();
 
-                Command {
+                Ok(Command {
                     action: Action::Bitmask,
-                    bitmask: bitmask,
+                    bitmask,
                     address: 0,
                     value: 0,
-                }
+                })
             } else {
-                let caps = mem_re.captures(line).expect("captures");
-                let address = caps["address"].to_string().parse().expect("number");
-                let value = caps["value"].to_string().parse().expect("number");
+                let caps = mem_re
+                    .captures(line.text)
+                    .ok_or_else(|| line.error(line.text, "mem[<address>] = <value>"))?;
+                let address = line.parse(&caps["address"], "address")?;
+                let value = line.parse(&caps["value"], "value")?;
 
-                Command {
+                Ok(Command {
                     action: Action::Value,
                     bitmask: String::from(""),
-                    address: address,
-                    value: value,
-                }
+                    address,
+                    value,
+                })
             }
         })
         .collect()
@@ -173,3 +182,14 @@ fn build_binary_combinations(length: usize) -> Vec<String> {
 
     build(length, String::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day14;
+
+    aoc_utils::examples! {
+        Day14,
+        example: "day14.txt" => part1 "165";
+        example_floating: "day14-2.txt" => part2 "208";
+    }
+}
@@ -1,17 +1,27 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use std::collections::HashMap;
 
-pub fn run() {
-    let numbers: Vec<u128> = aoc_utils::read_numbers("inputs/day15.txt", ",").collect();
+pub struct Day15;
 
-    let number_2020 = find_nth_number(&numbers, 2020);
-    let number_30000000 = find_nth_number(&numbers, 30000000);
+impl Solution for Day15 {
+    type Input = Vec<u128>;
+    type Part1 = u128;
+    type Part2 = u128;
 
-    println!("{}", number_2020);
-    println!("{}", number_30000000);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.numbers(",")
+    }
+
+    fn part1(numbers: &Self::Input) -> Self::Part1 {
+        find_nth_number(numbers, 2020)
+    }
+
+    fn part2(numbers: &Self::Input) -> Self::Part2 {
+        find_nth_number(numbers, 30000000)
+    }
 }
 
-fn find_nth_number(numbers: &Vec<u128>, nth: u128) -> u128 {
+fn find_nth_number(numbers: &[u128], nth: u128) -> u128 {
     let mut last_number = 0;
     let mut last_turn: HashMap<u128, u128> = HashMap::new();
     let mut before_last_turn: HashMap<u128, u128> = HashMap::new();
@@ -21,7 +31,7 @@ fn find_nth_number(numbers: &Vec<u128>, nth: u128) -> u128 {
         last_turn.insert(last_number, index as u128 + 1);
     }
 
-    for turn in (numbers.len() + 1) as u128..nth as u128 + 1 {
+    for turn in (numbers.len() + 1) as u128..nth + 1 {
         if let (Some(_), None) = (
             last_turn.get(&last_number),
             before_last_turn.get(&last_number),
@@ -60,3 +70,13 @@ fn update_turn_history(
         .and_modify(|value| *value = turn)
         .or_insert(turn);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day15;
+
+    aoc_utils::examples! {
+        Day15,
+        example: "day15.txt" => part1 "436";
+    }
+}
@@ -1,98 +1,112 @@
-use aoc_utils;
+use aoc_utils::{Block, Input, IntervalSet, Line, ParseError, ParseResult, Solution};
 use std::iter;
 
 type Ticket = Vec<usize>;
 
 #[derive(Debug)]
-struct Document {
+pub struct Document {
     rules: Vec<Rule>,
     your_ticket: Ticket,
     nearby_tickets: Vec<Ticket>,
 }
 
 #[derive(Debug, PartialEq)]
-struct Rule {
+pub struct Rule {
     id: String,
-    ranges: Vec<(usize, usize)>,
+    values: IntervalSet<usize>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day16.txt", true).collect();
+pub struct Day16;
 
-    let document = parse_document(&lines);
+impl Solution for Day16 {
+    type Input = Document;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let error_rate = ticket_scanning_error_rate(&document);
-
-    let field_order = build_field_order(&document);
-    let departure = departure_total(&document, &field_order);
-
-    println!("{:?}", error_rate);
-    println!("{:?}", departure);
-}
-
-fn parse_document(lines: &Vec<String>) -> Document {
-    let mut rules = vec![];
-    let mut your_ticket = vec![];
-    let mut nearby_tickets = vec![];
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        parse_document(input)
+    }
 
-    let mut section = "rules";
+    fn part1(document: &Self::Input) -> Self::Part1 {
+        ticket_scanning_error_rate(document)
+    }
 
-    for line in lines {
-        match (&line[..], &section[..]) {
-            ("your ticket:", _) => section = "your_ticket",
-            ("nearby tickets:", _) => section = "nearby_tickets",
+    fn part2(document: &Self::Input) -> Self::Part2 {
+        let field_order = build_field_order(document);
 
-            (_, "rules") => {
-                let mut parts = line.split(":");
+        departure_total(document, &field_order)
+    }
+}
 
-                let id = parts.next().expect("id").to_string();
+fn parse_document(input: &Input) -> ParseResult<Document> {
+    let mut blocks = input.blocks();
+
+    let rules = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "rules"))?;
+    let your_ticket = blocks
+        .next()
+        .ok_or_else(|| rules.error_end("your ticket"))?;
+    let nearby_tickets = blocks
+        .next()
+        .ok_or_else(|| your_ticket.error_end("nearby tickets"))?;
+
+    let rules = rules
+        .lines()
+        .map(|line| parse_rule(&line))
+        .collect::<ParseResult<_>>()?;
+
+    let your_ticket = parse_section(&your_ticket, "your ticket:")?
+        .first()
+        .ok_or_else(|| your_ticket.error_end("ticket"))
+        .and_then(parse_ticket)?;
+
+    let nearby_tickets = parse_section(&nearby_tickets, "nearby tickets:")?
+        .iter()
+        .map(parse_ticket)
+        .collect::<ParseResult<_>>()?;
+
+    Ok(Document {
+        rules,
+        your_ticket,
+        nearby_tickets,
+    })
+}
 
-                let ranges = parts
-                    .next()
-                    .expect("ranges")
-                    .split("or")
-                    .map(|range| {
-                        let mut values = range
-                            .trim()
-                            .split("-")
-                            .map(|value| value.parse::<usize>().expect("value"));
+fn parse_section<'a, 'b>(block: &'a Block<'b>, header: &str) -> ParseResult<&'a [Line<'b>]> {
+    match block.split_first() {
+        Some((line, lines)) if line.text == header => Ok(lines),
+        _ => Err(block[0].error_at(0, &format!("'{}'", header))),
+    }
+}
 
-                        let min = values.next().expect("min");
-                        let max = values.next().expect("max");
+fn parse_rule(line: &Line) -> ParseResult<Rule> {
+    let (id, ranges) = line
+        .split_once(':')
+        .ok_or_else(|| line.error_end("':' after field name"))?;
+
+    let values = ranges
+        .split("or")
+        .map(|range| {
+            let (min, max) = range
+                .trim()
+                .split_once('-')
+                .ok_or_else(|| line.error(range.trim(), "range min-max"))?;
+            let (min, max): (usize, usize) = (line.parse(min, "min")?, line.parse(max, "max")?);
+
+            Ok(min..max + 1)
+        })
+        .collect::<ParseResult<_>>()?;
 
-                        (min, max)
-                    })
-                    .collect();
+    let id = id.to_string();
 
-                let rule = Rule {
-                    id: id,
-                    ranges: ranges,
-                };
-
-                rules.push(rule);
-            }
-            (_, "your_ticket") => {
-                your_ticket = line
-                    .split(",")
-                    .map(|value| value.parse().expect("value"))
-                    .collect();
-            }
-            (_, "nearby_tickets") => {
-                nearby_tickets.push(
-                    line.split(",")
-                        .map(|value| value.parse().expect("value"))
-                        .collect(),
-                );
-            }
-            _ => (),
-        }
-    }
+    Ok(Rule { id, values })
+}
 
-    Document {
-        rules: rules,
-        your_ticket: your_ticket,
-        nearby_tickets: nearby_tickets,
-    }
+fn parse_ticket(line: &Line) -> ParseResult<Ticket> {
+    line.split(',')
+        .map(|value| line.parse(value, "ticket value"))
+        .collect()
 }
 
 fn ticket_scanning_error_rate(document: &Document) -> usize {
@@ -103,7 +117,7 @@ fn ticket_scanning_error_rate(document: &Document) -> usize {
             ticket
                 .iter()
                 .filter(|value| !is_value_valid(&document.rules, value))
-                .map(|value| *value)
+                .copied()
         })
         .sum()
 }
@@ -129,7 +143,7 @@ fn build_field_order(document: &Document) -> Vec<(String, usize)> {
                             .iter()
                             .all(|ticket| is_rule_value_valid(rule, &ticket[**position]))
                     })
-                    .map(|position| *position)
+                    .copied()
                     .collect();
 
                 (rule, positions)
@@ -149,12 +163,12 @@ fn build_field_order(document: &Document) -> Vec<(String, usize)> {
     field_order
 }
 
-fn departure_total(document: &Document, field_order: &Vec<(String, usize)>) -> usize {
+fn departure_total(document: &Document, field_order: &[(String, usize)]) -> usize {
     field_order
         .iter()
         .filter(|(id, _)| id.contains("departure"))
         .map(|(_, position)| document.your_ticket[*position])
-        .fold(1, |acc, x| acc * x)
+        .product()
 }
 
 fn is_ticket_valid(document: &Document, ticket: &Ticket) -> bool {
@@ -163,12 +177,20 @@ fn is_ticket_valid(document: &Document, ticket: &Ticket) -> bool {
         .all(|value| is_value_valid(&document.rules, value))
 }
 
-fn is_value_valid(rules: &Vec<Rule>, value: &usize) -> bool {
+fn is_value_valid(rules: &[Rule], value: &usize) -> bool {
     rules.iter().any(|rule| is_rule_value_valid(rule, value))
 }
 
 fn is_rule_value_valid(rule: &Rule, value: &usize) -> bool {
-    rule.ranges
-        .iter()
-        .any(|(min, max)| value >= min && value <= max)
+    rule.values.contains(*value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day16;
+
+    aoc_utils::examples! {
+        Day16,
+        example: "day16.txt" => part1 "71";
+    }
 }
@@ -1,161 +1,66 @@
-use aoc_utils;
-use itertools::Itertools;
-use std::collections::HashSet;
-use std::iter::Iterator;
-use std::ops::Range;
+use aoc_utils::{Cubic, Input, LifeRule, ParseResult, Solution, Sparse};
 
-type Point = Vec<i128>;
+pub struct Day17;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day17.txt", true).collect();
+impl Solution for Day17 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let points_three = parse_input(&lines, 3);
-    let points_four = parse_input(&lines, 4);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let count_three = simulate(points_three, 3, 6);
-    let count_four = simulate(points_four, 4, 6);
-
-    println!("{}", count_three);
-    println!("{}", count_four);
-}
-
-fn parse_input(rows: &Vec<String>, dimensions: usize) -> Vec<Point> {
-    rows.iter()
-        .enumerate()
-        .flat_map(|(row, columns)| parse_input_line(row, columns, dimensions))
-        .collect()
-}
-
-fn parse_input_line(row: usize, columns: &str, dimensions: usize) -> Vec<Point> {
-    columns
-        .split("")
-        .filter(|value| value == &"#" || value == &".")
-        .enumerate()
-        .filter(|(_, value)| value == &"#")
-        .map(move |(column, _)| {
-            let mut point = vec![0i128; dimensions];
-            point[0] = column as i128;
-            point[1] = row as i128;
-
-            point
-        })
-        .collect()
-}
-
-fn simulate(points: Vec<Point>, dimensions: usize, cycles: usize) -> usize {
-    let neighbors = get_neighbors(dimensions);
-
-    let mut cubes = HashSet::new();
-    let mut min = vec![-1; dimensions];
-    let mut max = vec![1; dimensions];
-
-    for point in points {
-        update_bounds(&point, &mut min, &mut max);
-        set_active(&mut cubes, point, true);
-    }
-
-    for _ in 0..cycles {
-        let mut next_cubes = cubes.clone();
-
-        let ranges = (0..dimensions)
-            .map(|index| min[index]..(max[index] + 1))
-            .collect();
-
-        for point in cartesian_product(ranges) {
-            let count = get_neighbors_count(&cubes, &neighbors, &point);
-            let active = get_active(&cubes, &point);
-
-            if active && !(count == 2 || count == 3) {
-                set_active(&mut next_cubes, point, false);
-            } else if !active && count == 3 {
-                update_bounds(&point, &mut min, &mut max);
-                set_active(&mut next_cubes, point, true);
+        for line in aoc_utils::numbered(&lines) {
+            if let Some(offset) = line.find(|char| char != '#' && char != '.') {
+                return Err(line.error_at(offset, "'#' or '.'"));
             }
         }
 
-        cubes = next_cubes;
+        Ok(lines)
     }
 
-    cubes.len()
-}
-
-fn get_neighbors(dimensions: usize) -> Vec<Point> {
-    let ranges = (0..dimensions).map(|_| (-1..2)).collect();
+    fn part1(lines: &Self::Input) -> Self::Part1 {
+        simulate::<3>(lines, 6)
+    }
 
-    cartesian_product(ranges)
-        .into_iter()
-        .filter(|values| !values.iter().all(|value| value == &0))
-        .collect()
+    fn part2(lines: &Self::Input) -> Self::Part2 {
+        simulate::<4>(lines, 6)
+    }
 }
 
-fn get_neighbors_count(cubes: &HashSet<Point>, neighbors: &Vec<Point>, point: &Point) -> usize {
-    neighbors
-        .iter()
-        .filter(|neighbor| {
-            let neighbor_point = point
-                .iter()
+fn parse_input<const D: usize>(rows: &[String]) -> Vec<[i64; D]> {
+    rows.iter()
+        .enumerate()
+        .flat_map(|(row, columns)| {
+            columns
+                .chars()
                 .enumerate()
-                .map(|(index, value)| value + neighbor[index])
-                .collect();
-
-            get_active(cubes, &neighbor_point)
+                .filter(|&(_, value)| value == '#')
+                .map(move |(column, _)| {
+                    let mut point = [0; D];
+                    point[0] = column as i64;
+                    point[1] = row as i64;
+
+                    point
+                })
         })
-        .count()
-}
-
-fn get_active(cubes: &HashSet<Point>, point: &Point) -> bool {
-    cubes.contains(point)
+        .collect()
 }
 
-fn set_active(cubes: &mut HashSet<Point>, point: Point, active: bool) {
-    if active {
-        cubes.insert(point);
-    } else {
-        cubes.remove(&point);
-    }
-}
+fn simulate<const D: usize>(rows: &[String], cycles: usize) -> usize {
+    let mut cubes = Sparse::new(Cubic::<D>, parse_input(rows));
 
-fn update_bounds(point: &Point, min: &mut Point, max: &mut Point) {
-    *min = min
-        .iter()
-        .enumerate()
-        .map(|(index, value)| *value.min(&(point[index] - 1)))
-        .collect();
+    cubes.run(&LifeRule::conway(), cycles);
 
-    *max = max
-        .iter()
-        .enumerate()
-        .map(|(index, value)| *value.max(&(point[index] + 1)))
-        .collect();
+    cubes.population().expect("finite population")
 }
 
-fn cartesian_product(mut ranges: Vec<Range<i128>>) -> Vec<Vec<i128>> {
-    fn nested(mut ranges: Vec<Range<i128>>, items: Vec<Vec<i128>>) -> Vec<Vec<i128>> {
-        let items = ranges
-            .remove(0)
-            .cartesian_product(items.into_iter())
-            .map(|(item, vec)| {
-                let mut vec = vec.clone();
-                vec.push(item);
-                vec
-            })
-            .collect();
-        if ranges.is_empty() {
-            items
-        } else {
-            nested(ranges, items)
-        }
-    }
-
-    let items = ranges
-        .remove(0)
-        .cartesian_product(ranges.remove(0))
-        .map(|(left, right)| vec![left, right])
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::Day17;
 
-    if ranges.is_empty() {
-        items
-    } else {
-        nested(ranges, items)
+    aoc_utils::examples! {
+        Day17,
+        example: "day17.txt" => part1 "112", part2 "848";
     }
 }
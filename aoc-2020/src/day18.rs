@@ -1,152 +1,107 @@
-use aoc_utils;
-use rayon::prelude::*;
+use aoc_utils::{
+    Associativity, BinaryOperator, Expr, Input, Line, OperatorTable, ParseResult, Solution,
+};
 
-type Expression = Vec<Token>;
 type Value = i128;
 
-#[derive(Debug, Eq, PartialEq)]
-enum Token {
-    Number(Value),
-    Symbol(Operator),
-    Bracket(Expression),
+/// One line of homework read under both rule sets.
+#[derive(Debug, Clone)]
+pub struct Homework {
+    basic: Expr,
+    advanced: Expr,
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-enum Operator {
-    Add,
-    Mul,
-}
+pub struct Day18;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day18.txt", true).collect();
+impl Solution for Day18 {
+    type Input = Vec<Homework>;
+    type Part1 = String;
+    type Part2 = String;
 
-    let expressions_simple = parse_expressions(&lines);
-    let expressions_advanced = parse_expressions(&lines);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let sum_simple = evaluate_sum(expressions_simple, false);
-    let sum_advanced = evaluate_sum(expressions_advanced, true);
+        aoc_utils::numbered(&lines)
+            .map(|line| parse_homework(&line))
+            .collect()
+    }
 
-    println!("{}", sum_simple);
-    println!("{}", sum_advanced);
-}
+    fn part1(homework: &Self::Input) -> Self::Part1 {
+        match sum_answers(homework.iter().map(|line| &line.basic)) {
+            Some(sum) => sum.to_string(),
+            None => "overflow".to_string(),
+        }
+    }
 
-fn parse_expressions(lines: &Vec<String>) -> Vec<Expression> {
-    lines
-        .par_iter()
-        .map(|line| parse_expression(line))
-        .collect()
+    fn part2(homework: &Self::Input) -> Self::Part2 {
+        match sum_answers(homework.iter().map(|line| &line.advanced)) {
+            Some(sum) => sum.to_string(),
+            None => "overflow".to_string(),
+        }
+    }
 }
 
-fn parse_expression(string: &str) -> Expression {
-    let mut expression = vec![];
-
-    let mut iter = string.chars().enumerate();
-
-    while let Some((index, character)) = iter.next() {
-        match character {
-            ' ' => (),
-            '+' => expression.push(Token::Symbol(Operator::Add)),
-            '*' => expression.push(Token::Symbol(Operator::Mul)),
-            '(' => {
-                let close_index = find_close_bracket(string, index);
-
-                let bracket_string = &string[index + 1..close_index];
-                let bracket_token = Token::Bracket(parse_expression(bracket_string));
+/// `+` and `*` bind equally, evaluated left to right.
+fn basic_rules() -> OperatorTable {
+    OperatorTable::new(
+        &[
+            (BinaryOperator::Add, 1, Associativity::Left),
+            (BinaryOperator::Mul, 1, Associativity::Left),
+        ],
+        None,
+    )
+}
 
-                expression.push(bracket_token);
+/// `+` binds tighter than `*`.
+fn advanced_rules() -> OperatorTable {
+    OperatorTable::new(
+        &[
+            (BinaryOperator::Add, 2, Associativity::Left),
+            (BinaryOperator::Mul, 1, Associativity::Left),
+        ],
+        None,
+    )
+}
 
-                iter.nth(bracket_string.len());
-            }
-            _ => {
-                let value = character.to_string().parse().expect("number");
+fn parse_homework(line: &Line) -> ParseResult<Homework> {
+    let parse = |table: &OperatorTable| {
+        Expr::parse(line, table).map_err(|error| line.error_at(error.offset, error.expected))
+    };
 
-                expression.push(Token::Number(value));
-            }
-        }
-    }
-
-    expression
+    Ok(Homework {
+        basic: parse(&basic_rules())?,
+        advanced: parse(&advanced_rules())?,
+    })
 }
 
-fn find_close_bracket(string: &str, open_index: usize) -> usize {
-    let characters = string
-        .chars()
-        .enumerate()
-        .filter(|(index, _)| index > &open_index)
-        .filter(|(_, character)| character == &'(' || character == &')');
-
-    let mut control = 1;
+/// None when an answer or the running sum overflows.
+fn sum_answers<'a>(mut expressions: impl Iterator<Item = &'a Expr>) -> Option<Value> {
+    expressions.try_fold(0, |sum: Value, expression| {
+        sum.checked_add(expression.evaluate()?)
+    })
+}
 
-    for (index, character) in characters {
-        match character {
-            '(' => control += 1,
-            ')' => control -= 1,
-            _ => (),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if control == 0 {
-            return index;
-        }
+    aoc_utils::examples! {
+        Day18,
+        example: "day18.txt" => part1 "26457", part2 "694173";
     }
 
-    open_index
-}
+    #[test]
+    fn overflow() {
+        let line = vec!["9"; 41].join(" * ");
+        let homework = Day18::parse(&Input::literal(&line)).expect("homework");
 
-fn evaluate_sum(expressions: Vec<Expression>, advanced: bool) -> Value {
-    expressions
-        .into_par_iter()
-        .map(|expression| evaluate(&expression, advanced))
-        .sum()
-}
+        assert_eq!(Day18::part1(&homework), "overflow");
+        assert_eq!(Day18::part2(&homework), "overflow");
 
-fn evaluate(expression: &[Token], advanced: bool) -> Value {
-    let mut expression: Expression = expression
-        .iter()
-        .map(|token| match token {
-            Token::Bracket(expression) => Token::Number(evaluate(expression, advanced)),
-            Token::Symbol(operator) => Token::Symbol(*operator),
-            Token::Number(value) => Token::Number(*value),
-        })
-        .collect();
-
-    if advanced {
-        loop {
-            let symbol_index = expression
-                .iter()
-                .position(|token| token == &Token::Symbol(Operator::Add));
-
-            if let Some(symbol_index) = symbol_index {
-                let left = &expression[symbol_index - 1];
-                let right = &expression[symbol_index + 1];
-
-                let value = match (left, right) {
-                    (Token::Number(left), Token::Number(right)) => Token::Number(left + right),
-                    _ => Token::Number(0),
-                };
-
-                expression.remove(symbol_index - 1);
-                expression.remove(symbol_index - 1);
-                expression.remove(symbol_index - 1);
-                expression.insert(symbol_index - 1, value);
-            } else {
-                break;
-            }
-        }
-    }
+        let line = (Value::MAX / 2 + 1).to_string();
+        let homework =
+            Day18::parse(&Input::literal(&format!("{line}\n{line}\n"))).expect("homework");
 
-    let mut current_value = 0;
-    let mut current_operator = Operator::Add;
-
-    for token in expression {
-        match token {
-            Token::Bracket(_) => (),
-            Token::Symbol(operator) => current_operator = operator,
-            Token::Number(value) => match current_operator {
-                Operator::Add => current_value += value,
-                Operator::Mul => current_value *= value,
-            },
-        }
+        assert_eq!(Day18::part1(&homework), "overflow");
     }
-
-    current_value
 }
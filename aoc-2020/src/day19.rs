@@ -1,120 +1,141 @@
-use aoc_utils;
-use itertools::join;
-use regex::Regex;
-use std::collections::HashMap;
+use aoc_utils::{Block, Grammar, Input, Line, ParseError, ParseResult, Solution, Symbol};
 
-type Rules = HashMap<String, Vec<Vec<String>>>;
+type Alternatives = Vec<Vec<Symbol>>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day19.txt", false).collect();
+const START: &str = "0";
 
-    let (lines_rules, lines_messages) = parse_input(&lines);
-    let mut rules = parse_rules(lines_rules);
+/// The replacements part 2 makes, which loop back into themselves.
+const LOOPING_RULES: [&str; 2] = ["8: 42 | 42 8", "11: 42 31 | 42 11 31"];
 
-    let regex_one = build_regex(&rules, "0");
-    let count_one = count_messages(lines_messages, &regex_one);
+#[derive(Debug)]
+pub struct Satellite {
+    grammar: Grammar,
+    messages: Vec<String>,
+}
 
-    update_rules_loop(&mut rules);
+pub struct Day19;
 
-    let regex_two = build_regex(&rules, "0");
-    let count_two = count_messages(lines_messages, &regex_two);
+impl Solution for Day19 {
+    type Input = Satellite;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!("{}", count_one);
-    println!("{}", count_two);
-}
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let mut blocks = input.blocks();
 
-fn parse_input(lines: &Vec<String>) -> (&[String], &[String]) {
-    let empty_index = lines
-        .iter()
-        .position(|line| line.is_empty())
-        .expect("empty index");
+        let rules = blocks
+            .next()
+            .ok_or_else(|| ParseError::new(1, 1, "rules"))?;
+        let messages = blocks
+            .next()
+            .ok_or_else(|| rules.error_end("blank line before messages"))?;
 
-    let lines_rules = &lines[..empty_index];
-    let lines_messages = &lines[empty_index + 1..lines.len()];
+        Ok(Satellite {
+            grammar: parse_grammar(&rules)?,
+            messages: messages.lines().map(|line| line.to_string()).collect(),
+        })
+    }
 
-    (lines_rules, lines_messages)
-}
+    fn part1(satellite: &Self::Input) -> Self::Part1 {
+        count_messages(&satellite.grammar, &satellite.messages)
+    }
 
-fn parse_rules(lines: &[String]) -> Rules {
-    lines
-        .iter()
-        .map(|line| {
-            let mut iter = line.split(": ");
+    fn part2(satellite: &Self::Input) -> Self::Part2 {
+        let mut grammar = satellite.grammar.clone();
 
-            let id = iter.next().expect("id").to_string();
+        for rule in LOOPING_RULES {
+            let (name, alternatives) = parse_rule(&Line::new(1, rule)).expect("rule");
 
-            let sub_rules = iter
-                .next()
-                .expect("sub_rules")
-                .replace("\"", "")
-                .split(" | ")
-                .map(|sub_rule| sub_rule.split(" ").map(|item| item.to_string()).collect())
-                .collect();
+            grammar.insert(name, alternatives);
+        }
 
-            (id, sub_rules)
-        })
-        .collect()
+        count_messages(&grammar, &satellite.messages)
+    }
 }
 
-fn build_regex(rules: &Rules, id: &str) -> String {
-    fn build(rules: &Rules, id: &str, loop_max: usize, loop_index: usize) -> String {
-        let sub_rules = &rules[id];
-
-        let character_item = sub_rules
-            .first()
-            .map(|items| items.first())
-            .flatten()
-            .filter(|item| item == &"a" || item == &"b");
-
-        if let Some(character) = character_item {
-            format!("{}", character)
-        } else {
-            let iter = sub_rules.iter().map(|items| {
-                let iter = items.iter().map(|item| {
-                    let is_loop = item == id;
-
-                    if is_loop {
-                        if loop_index < loop_max {
-                            build(rules, item, loop_max, loop_index + 1)
-                        } else {
-                            String::from("")
-                        }
-                    } else {
-                        build(rules, item, loop_max, 0)
-                    }
-                });
-
-                join(iter, "")
-            });
-
-            format!("(?:{})", join(iter, "|"))
-        }
+fn parse_grammar(block: &Block) -> ParseResult<Grammar> {
+    let mut grammar = Grammar::new();
+
+    for line in block.lines() {
+        let (name, alternatives) = parse_rule(&line)?;
+
+        grammar.insert(name, alternatives);
+    }
+
+    if let Some(name) = grammar.undefined().first() {
+        return Err(block.error_end(&format!("a definition of rule {}", name)));
     }
 
-    format!("\\b{}\\b", build(rules, id, 9, 0))
+    Ok(grammar)
 }
 
-fn count_messages(messages: &[String], regex: &str) -> usize {
-    let re = Regex::new(&regex).expect("regex");
+/// Reads `<name>: <symbols> | <symbols> ...`, where quoted symbols are literal text and the
+/// rest name other rules.
+fn parse_rule<'a>(line: &Line<'a>) -> ParseResult<(&'a str, Alternatives)> {
+    let text = line.text;
+
+    let (name, rest) = text
+        .split_once(": ")
+        .ok_or_else(|| line.error_end("'<id>: <rule>'"))?;
+
+    let start = name.len() + 2;
+    let mut alternatives = vec![vec![]];
+    let mut chars = rest.char_indices().peekable();
+
+    while let Some((offset, char)) = chars.next() {
+        let symbol = match char {
+            ' ' => continue,
+            '|' => {
+                alternatives.push(vec![]);
+                continue;
+            }
+            '"' => {
+                let close = rest[offset + 1..]
+                    .find('"')
+                    .ok_or_else(|| line.error_at(start + offset, "closing '\"'"))?;
+
+                let terminal = &rest[offset + 1..offset + 1 + close];
+
+                while chars
+                    .next_if(|&(next, _)| next <= offset + 1 + close)
+                    .is_some()
+                {}
+
+                Symbol::Terminal(terminal.to_string())
+            }
+            _ => {
+                let mut end = offset + char.len_utf8();
+
+                while let Some((next, char)) =
+                    chars.next_if(|&(_, char)| !matches!(char, ' ' | '|' | '"'))
+                {
+                    end = next + char.len_utf8();
+                }
+
+                Symbol::Rule(rest[offset..end].to_string())
+            }
+        };
+
+        alternatives.last_mut().expect("alternative").push(symbol);
+    }
+
+    Ok((name, alternatives))
+}
 
+fn count_messages(grammar: &Grammar, messages: &[String]) -> usize {
     messages
         .iter()
-        .filter(|message| re.is_match(message))
+        .filter(|message| grammar.matches(START, message))
         .count()
 }
 
-fn update_rules_loop(rules: &mut Rules) {
-    rules.entry(String::from("8")).and_modify(|sub_rules| {
-        *sub_rules = vec![
-            vec![String::from("42")],
-            vec![String::from("42"), String::from("8")],
-        ]
-    });
-
-    rules.entry(String::from("11")).and_modify(|sub_rules| {
-        *sub_rules = vec![
-            vec![String::from("42"), String::from("31")],
-            vec![String::from("42"), String::from("11"), String::from("31")],
-        ]
-    });
+#[cfg(test)]
+mod tests {
+    use super::Day19;
+
+    aoc_utils::examples! {
+        Day19,
+        example: "day19.txt" => part1 "2";
+        example_loops: "day19-2.txt" => part1 "3", part2 "12";
+    }
 }
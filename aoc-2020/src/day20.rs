@@ -1,119 +1,63 @@
-use aoc_utils;
+use aoc_utils::{Block, Direction, Grid, Input, ParseResult, Solution};
 use colored::*;
 use rayon::prelude::*;
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Top,
-    Left,
-    Bottom,
-    Right,
-}
-
 #[derive(Debug, Clone)]
-struct Tile {
+pub struct Tile {
     id: u128,
-    data: Vec<Vec<String>>,
+    data: Grid<char>,
 }
 
 impl Tile {
     fn rows(&self) -> usize {
-        self.data.len()
+        self.data.height()
     }
 
     fn columns(&self) -> usize {
-        self.data.get(0).map(|row| row.len()).unwrap_or(0)
+        self.data.width()
     }
 
     fn rotate_clockwise(&self) -> Self {
-        let rows = self.rows();
-        let columns = self.columns();
-
-        let new_data = (0..columns)
-            .map(|j| {
-                (0..rows)
-                    .map(|i| self.data[rows - 1 - i][j].to_string())
-                    .collect()
-            })
-            .collect();
-
         Self {
             id: self.id,
-            data: new_data,
+            data: self.data.rotate_clockwise(),
         }
     }
 
     fn flip_horizontal(&self) -> Self {
-        let rows = self.rows();
-        let columns = self.columns();
-
-        let new_data = (0..rows)
-            .map(|i| {
-                (0..columns)
-                    .map(|j| self.data[i][columns - 1 - j].to_string())
-                    .collect()
-            })
-            .collect();
-
         Self {
             id: self.id,
-            data: new_data,
+            data: self.data.flip_horizontal(),
         }
     }
 
     fn flip_vertical(&self) -> Self {
-        let rows = self.rows();
-        let columns = self.columns();
-
-        let new_data = (0..rows)
-            .map(|i| {
-                (0..columns)
-                    .map(|j| self.data[rows - 1 - i][j].to_string())
-                    .collect()
-            })
-            .collect();
-
         Self {
             id: self.id,
-            data: new_data,
+            data: self.data.flip_vertical(),
         }
     }
 
     fn top(&self) -> String {
-        self.data
-            .first()
-            .map(|row| row.par_iter().map(|value| value.to_string()).collect())
-            .unwrap_or(String::from(""))
+        self.data.rows().next().unwrap_or_default().iter().collect()
     }
 
     fn left(&self) -> String {
-        self.data
-            .par_iter()
-            .map(|row| {
-                row.first()
-                    .map(|value| value.to_string())
-                    .unwrap_or(String::from(""))
-            })
-            .collect()
+        self.data.columns().next().into_iter().flatten().collect()
     }
 
     fn bottom(&self) -> String {
         self.data
-            .last()
-            .map(|row| row.par_iter().map(|value| value.to_string()).collect())
-            .unwrap_or(String::from(""))
+            .rows()
+            .next_back()
+            .unwrap_or_default()
+            .iter()
+            .collect()
     }
 
     fn right(&self) -> String {
-        self.data
-            .par_iter()
-            .map(|row| {
-                row.last()
-                    .map(|value| value.to_string())
-                    .unwrap_or(String::from(""))
-            })
-            .collect()
+        self.data.columns().last().into_iter().flatten().collect()
     }
 
     fn combinations(&self) -> Vec<Self> {
@@ -143,9 +87,9 @@ impl Tile {
 
     fn corner_match(&self, other: &Self, direction: Direction) -> bool {
         match direction {
-            Direction::Top => self.top() == other.bottom(),
+            Direction::Up => self.top() == other.bottom(),
             Direction::Left => self.left() == other.right(),
-            Direction::Bottom => self.bottom() == other.top(),
+            Direction::Down => self.bottom() == other.top(),
             Direction::Right => self.right() == other.left(),
         }
     }
@@ -153,21 +97,21 @@ impl Tile {
 
 impl Display for Tile {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "Tile {}:\n", self.id)?;
+        writeln!(f, "Tile {}:", self.id)?;
 
-        for row in &self.data {
+        for row in self.data.rows() {
             for value in row {
-                let value = match &value[..] {
-                    "O" => "██".red(),
-                    "#" => "██".blue(),
-                    "." => "██".cyan(),
+                let value = match value {
+                    'O' => "██".red(),
+                    '#' => "██".blue(),
+                    '.' => "██".cyan(),
                     _ => "".white(),
                 };
 
                 write!(f, "{}", value)?;
             }
 
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -175,7 +119,7 @@ impl Display for Tile {
 }
 
 struct Monster {
-    data: Vec<Vec<String>>,
+    data: Grid<char>,
     rows: usize,
     columns: usize,
     blocks: usize,
@@ -183,24 +127,18 @@ struct Monster {
 
 impl Monster {
     fn new() -> Monster {
-        let image = vec![
+        let image = [
             "                  # ",
             "#    ##    ##    ###",
             " #  #  #  #  #  #   ",
         ];
 
-        let data: Vec<Vec<String>> = image
-            .iter()
-            .map(|row| row.chars().map(|value| value.to_string()).collect())
-            .collect();
+        let data = Grid::from_rows(image.iter().map(|row| row.chars().collect()).collect());
 
-        let rows = data.len();
-        let columns = data[0].len();
+        let rows = data.height();
+        let columns = data.width();
 
-        let blocks: usize = data
-            .iter()
-            .map(|row| row.iter().filter(|value| value == &"#").count())
-            .sum();
+        let blocks = data.values().filter(|value| value == &&'#').count();
 
         Monster {
             data,
@@ -215,14 +153,9 @@ impl Monster {
 
         for i in row..row + self.rows {
             for j in column..column + self.columns {
-                let value_match = tile
-                    .data
-                    .get(i)
-                    .map(|row| row.get(j))
-                    .flatten()
-                    .filter(|value| value == &"#");
-
-                if self.data[i - row][j - column] == "#" && value_match.is_some() {
+                let value_match = tile.data.get((j, i)) == Some(&'#');
+
+                if self.data[(j - column, i - row)] == '#' && value_match {
                     match_blocks += 1;
                 }
             }
@@ -234,83 +167,65 @@ impl Monster {
     fn replace(&self, tile: &mut Tile, row: usize, column: usize) {
         for i in row..row + self.rows {
             for j in column..column + self.columns {
-                let value_match = tile
-                    .data
-                    .get(i)
-                    .map(|row| row.get(j))
-                    .flatten()
-                    .filter(|value| value == &"#");
-
-                if self.data[i - row][j - column] == "#" && value_match.is_some() {
-                    tile.data[i][j] = String::from("O");
+                let value_match = tile.data.get((j, i)) == Some(&'#');
+
+                if self.data[(j - column, i - row)] == '#' && value_match {
+                    tile.data[(j, i)] = 'O';
                 }
             }
         }
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day20.txt", false).collect();
-
-    let tiles = parse_tiles(&lines);
+pub struct Day20;
 
-    let combinations = build_combinations(&tiles);
+impl Solution for Day20 {
+    type Input = Vec<Tile>;
+    type Part1 = u128;
+    type Part2 = usize;
 
-    let jigsaw = build_jigsaw(&combinations);
-
-    let image = build_image(&jigsaw);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.parse_blocks(parse_tile)
+    }
 
-    let oriented = find_oriented_monsters(&image);
+    fn part1(tiles: &Self::Input) -> Self::Part1 {
+        let combinations = build_combinations(tiles);
 
-    let blocks = count_image_blocks(&oriented);
+        let jigsaw = build_jigsaw(&combinations);
 
-    println!("{}", oriented);
-    println!("{}", blocks);
-}
-
-fn parse_tiles(lines: &[String]) -> Vec<Tile> {
-    let mut tiles = vec![];
-    let mut iter = lines.iter().enumerate();
-    let mut previous_length = 0;
+        build_image_id(&jigsaw)
+    }
 
-    while let Some((index, line)) = iter.next() {
-        if line.len() >= 4 && &line[..4] == "Tile" {
-            let length = lines[index..]
-                .iter()
-                .position(|line| line.is_empty())
-                .unwrap_or(previous_length);
+    fn part2(tiles: &Self::Input) -> Self::Part2 {
+        let combinations = build_combinations(tiles);
 
-            previous_length = length;
+        let jigsaw = build_jigsaw(&combinations);
 
-            let tile = parse_tile(&lines[index..index + length]);
+        let image = build_image(&jigsaw);
 
-            tiles.push(tile);
+        let oriented = find_oriented_monsters(&image);
 
-            iter.nth(length - 1);
-        }
+        count_image_blocks(&oriented)
     }
-
-    tiles
 }
 
-fn parse_tile(lines: &[String]) -> Tile {
-    let id = lines[0]
-        .replace("Tile ", "")
-        .replace(":", "")
-        .parse()
-        .expect("id");
+fn parse_tile(block: Block) -> ParseResult<Tile> {
+    let line = block[0];
 
-    let data = lines[1..]
-        .par_iter()
-        .map(|line| {
-            line.split("")
-                .filter(|value| value == &"." || value == &"#")
-                .map(|value| value.to_string())
-                .collect()
-        })
-        .collect();
+    let id = line
+        .strip_prefix("Tile ")
+        .and_then(|id| id.strip_suffix(':'))
+        .ok_or_else(|| line.error_at(0, "'Tile <id>:'"))?;
+
+    let id = line.parse(id, "tile id")?;
+
+    let data = Grid::parse_with(
+        block[1..].iter().copied(),
+        |char| matches!(char, '.' | '#').then_some(char),
+        "'.' or '#'",
+    )?;
 
-    Tile { id, data }
+    Ok(Tile { id, data })
 }
 
 fn build_combinations(tiles: &[Tile]) -> Vec<Tile> {
@@ -324,10 +239,10 @@ fn find_top_left(combinations: &[Tile]) -> Tile {
     combinations
         .par_iter()
         .find_any(|combination| {
-            let top = find_next(&combinations, &combination, Direction::Top);
-            let left = find_next(&combinations, &combination, Direction::Left);
-            let bottom = find_next(&combinations, &combination, Direction::Bottom);
-            let right = find_next(&combinations, &combination, Direction::Right);
+            let top = find_next(combinations, combination, Direction::Up);
+            let left = find_next(combinations, combination, Direction::Left);
+            let bottom = find_next(combinations, combination, Direction::Down);
+            let right = find_next(combinations, combination, Direction::Right);
 
             top.is_none() && left.is_none() && bottom.is_some() && right.is_some()
         })
@@ -340,18 +255,18 @@ fn find_next(combinations: &[Tile], tile: &Tile, direction: Direction) -> Option
         .par_iter()
         .filter(|other| other.id != tile.id)
         .find_any(|other| tile.corner_match(other, direction))
-        .map(|tile| tile.clone())
+        .cloned()
 }
 
 fn build_jigsaw(combinations: &[Tile]) -> Vec<Vec<Tile>> {
-    let top_left = find_top_left(&combinations);
+    let top_left = find_top_left(combinations);
 
     let mut jigsaw = vec![];
     let mut current_row = vec![top_left];
 
     loop {
         let current = current_row.last().expect("last");
-        let right = find_next(&combinations, &current, Direction::Right);
+        let right = find_next(combinations, current, Direction::Right);
 
         if let Some(right) = right {
             current_row.push(right);
@@ -359,7 +274,7 @@ fn build_jigsaw(combinations: &[Tile]) -> Vec<Vec<Tile>> {
             jigsaw.push(current_row);
 
             let current = jigsaw.last().expect("last").first().expect("first");
-            let bottom = find_next(&combinations, &current, Direction::Bottom);
+            let bottom = find_next(combinations, current, Direction::Down);
 
             if let Some(bottom) = bottom {
                 current_row = vec![bottom];
@@ -372,10 +287,10 @@ fn build_jigsaw(combinations: &[Tile]) -> Vec<Vec<Tile>> {
     jigsaw
 }
 
-fn build_image(jigsaw: &Vec<Vec<Tile>>) -> Tile {
+fn build_image(jigsaw: &[Vec<Tile>]) -> Tile {
     let id = build_image_id(jigsaw);
 
-    let data = jigsaw
+    let rows = jigsaw
         .iter()
         .flat_map(|row_tiles| {
             let internal_rows = row_tiles[0].rows();
@@ -386,20 +301,23 @@ fn build_image(jigsaw: &Vec<Vec<Tile>>) -> Tile {
                     row_tiles
                         .iter()
                         .flat_map(|row_tile| {
-                            row_tile.data[internal_row][1..internal_columns - 1]
+                            row_tile.data.row(internal_row)[1..internal_columns - 1]
                                 .iter()
-                                .map(|internal_column| internal_column.to_string())
+                                .copied()
                         })
                         .collect()
                 })
-                .collect::<Vec<Vec<String>>>()
+                .collect::<Vec<Vec<char>>>()
         })
         .collect();
 
-    Tile { id, data }
+    Tile {
+        id,
+        data: Grid::from_rows(rows),
+    }
 }
 
-fn build_image_id(jigsaw: &Vec<Vec<Tile>>) -> u128 {
+fn build_image_id(jigsaw: &[Vec<Tile>]) -> u128 {
     let top_left = jigsaw.first().expect("top").first().expect("left");
     let top_right = jigsaw.first().expect("top").last().expect("right");
     let bottom_left = jigsaw.last().expect("bottom").first().expect("left");
@@ -416,12 +334,7 @@ fn find_oriented_monsters(image: &Tile) -> Tile {
         .into_iter()
         .find(|combination| {
             (0..combination.rows())
-                .find(|i| {
-                    (0..combination.columns())
-                        .find(|j| monster.find(combination, *i, *j))
-                        .is_some()
-                })
-                .is_some()
+                .any(|i| (0..combination.columns()).any(|j| monster.find(combination, i, j)))
         })
         .expect("oriented");
 
@@ -437,9 +350,15 @@ fn find_oriented_monsters(image: &Tile) -> Tile {
 }
 
 fn count_image_blocks(image: &Tile) -> usize {
-    image
-        .data
-        .iter()
-        .map(|row| row.iter().filter(|value| value == &"#").count())
-        .sum()
+    image.data.values().filter(|value| value == &&'#').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day20;
+
+    aoc_utils::examples! {
+        Day20,
+        example: "day20.txt" => part1 "20899048083289", part2 "273";
+    }
 }
@@ -1,10 +1,10 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use itertools::join;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
-struct Food {
+pub struct Food {
     ingredients: Vec<String>,
     allergens: Vec<String>,
 }
@@ -15,28 +15,38 @@ impl AsRef<Self> for Food {
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day21.txt", true).collect();
+pub struct Day21;
 
-    let foods = parse_foods(&lines);
+impl Solution for Day21 {
+    type Input = Vec<Food>;
+    type Part1 = usize;
+    type Part2 = String;
 
-    let allergens = find_allergen_ingredients(&foods);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let safe_count = count_safe_ingredients(&foods, &allergens);
+        parse_foods(&lines)
+    }
+
+    fn part1(foods: &Self::Input) -> Self::Part1 {
+        let allergens = find_allergen_ingredients(foods);
 
-    let dangerous_list = dangerous_ingredients(&allergens);
+        count_safe_ingredients(foods, &allergens)
+    }
 
-    println!("{:?}", safe_count);
-    println!("{:?}", dangerous_list);
+    fn part2(foods: &Self::Input) -> Self::Part2 {
+        let allergens = find_allergen_ingredients(foods);
+
+        dangerous_ingredients(&allergens)
+    }
 }
 
-fn parse_foods(lines: &[String]) -> Vec<Food> {
+fn parse_foods(lines: &[String]) -> ParseResult<Vec<Food>> {
     let re = Regex::new(r"(?P<ingredients>.+) [(]contains (?P<allergens>.+)[)]").expect("regex");
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
-            re.captures(line)
+            re.captures(line.text)
                 .map(|caps| {
                     let ingredients = caps["ingredients"]
                         .split_whitespace()
@@ -53,7 +63,7 @@ fn parse_foods(lines: &[String]) -> Vec<Food> {
                         allergens,
                     }
                 })
-                .expect("captures")
+                .ok_or_else(|| line.error(line.text, "ingredients (contains allergens)"))
         })
         .collect()
 }
@@ -167,3 +177,13 @@ fn dangerous_ingredients<T: AsRef<str>>(allergens: &[(T, T)]) -> String {
 
     join(iter, ",")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day21;
+
+    aoc_utils::examples! {
+        Day21,
+        example: "day21.txt" => part1 "5", part2 "mxmxvkd,sqjhc,fvjkl";
+    }
+}
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Block, Input, ParseError, ParseResult, Solution};
 
 type Deck = Vec<Card>;
 type DeckSlice<'a> = &'a [Card];
@@ -11,55 +11,59 @@ enum Player {
     Two,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day22.txt", true).collect();
+pub struct Day22;
 
-    let (one, two) = parse_decks(&lines);
+impl Solution for Day22 {
+    type Input = (Deck, Deck);
+    type Part1 = Score;
+    type Part2 = Score;
 
-    let (winning_combat, _) = play_game(&one, &two, false);
-    let (winning_recursive_combat, _) = play_game(&one, &two, true);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        parse_decks(input)
+    }
+
+    fn part1((one, two): &Self::Input) -> Self::Part1 {
+        let (winning_combat, _) = play_game(one, two, false);
+
+        calculate_score(&winning_combat)
+    }
 
-    let score_combat = calculate_score(&winning_combat);
-    let score_recursive_combat = calculate_score(&winning_recursive_combat);
+    fn part2((one, two): &Self::Input) -> Self::Part2 {
+        let (winning_recursive_combat, _) = play_game(one, two, true);
 
-    println!("{:?}", score_combat);
-    println!("{:?}", score_recursive_combat);
+        calculate_score(&winning_recursive_combat)
+    }
 }
 
-fn parse_decks<T>(lines: &[T]) -> (Deck, Deck)
-where
-    T: AsRef<str>,
-{
-    let mut one = vec![];
-    let mut two = vec![];
-
-    let mut control = "";
-
-    for line in lines {
-        let line = line.as_ref();
-
-        match &line[..] {
-            "Player 1:" => control = "one",
-            "Player 2:" => control = "two",
-            _ => {
-                let value = line.parse().expect("number");
-
-                match control {
-                    "one" => one.push(value),
-                    "two" => two.push(value),
-                    _ => (),
-                }
-            }
-        }
+fn parse_decks(input: &Input) -> ParseResult<(Deck, Deck)> {
+    let mut blocks = input.blocks();
+
+    let one = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "'Player 1:'"))?;
+    let two = blocks.next().ok_or_else(|| one.error_end("'Player 2:'"))?;
+
+    Ok((
+        parse_deck(&one, "Player 1:")?,
+        parse_deck(&two, "Player 2:")?,
+    ))
+}
+
+fn parse_deck(block: &Block, player: &str) -> ParseResult<Deck> {
+    if block[0].text != player {
+        return Err(block[0].error_at(0, &format!("'{}'", player)));
     }
 
-    (one, two)
+    block[1..]
+        .iter()
+        .map(|line| line.parse(line.text, "card number"))
+        .collect()
 }
 
 fn play_game(start_one: DeckSlice, start_two: DeckSlice, recursive: bool) -> (Deck, Player) {
     let mut history: Vec<(Deck, Deck)> = vec![];
 
-    let (mut one, mut two) = next_game_state(&start_one, &start_two, &history, recursive);
+    let (mut one, mut two) = next_game_state(start_one, start_two, &history, recursive);
 
     while !one.is_empty() && !two.is_empty() {
         let (next_one, next_two) = next_game_state(&one, &two, &history, recursive);
@@ -91,10 +95,10 @@ fn next_game_state(
             .iter()
             .any(|(history_one, history_two)| one == &history_one[..] && two == &history_two[..]);
 
-        let recursive_state = one.len() - 1 >= top_one && two.len() - 1 >= top_two;
+        let recursive_state = one.len() > top_one && two.len() > top_two;
 
         if previous_state {
-            let next_one = one.iter().map(|value| *value).collect();
+            let next_one = one.to_vec();
             let next_two = vec![];
 
             return (next_one, next_two);
@@ -125,11 +129,11 @@ fn next_deck_state(winner: DeckSlice, loser: DeckSlice, keep_order: bool) -> (De
 
     let next_winner = winner[1..]
         .iter()
-        .map(|card| *card)
+        .copied()
         .chain(vec![top_winner, top_loser])
         .collect();
 
-    let next_loser = loser[1..].iter().map(|card| *card).collect();
+    let next_loser = loser[1..].to_vec();
 
     if keep_order {
         (next_winner, next_loser)
@@ -145,3 +149,13 @@ fn calculate_score(deck: DeckSlice) -> Score {
         .map(|(index, card)| *card as Score * (index + 1) as Score)
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day22;
+
+    aoc_utils::examples! {
+        Day22,
+        example: "day22.txt" => part1 "306", part2 "291";
+    }
+}
@@ -1,36 +1,51 @@
-use aoc_utils;
-use itertools;
+use aoc_utils::{Input, ParseResult, Solution};
 use std::collections::HashMap;
 use std::iter;
 
 type Label = usize;
 type NextLabel = usize;
 
-pub fn run() {
-    let cups: Vec<Label> = aoc_utils::read_numbers("inputs/day23.txt", "").collect();
+pub struct Day23;
 
-    let cups_one = extend_cups(&cups, 0, 0);
-    let cups_two = extend_cups(&cups, 10, 1000000 + 1);
+impl Solution for Day23 {
+    type Input = Vec<Label>;
+    type Part1 = String;
+    type Part2 = u128;
 
-    let data_one = play_game(&cups_one, 100);
-    let data_two = play_game(&cups_two, 10000000);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.numbers("")
+    }
+
+    fn part1(cups: &Self::Input) -> Self::Part1 {
+        let cups_one = extend_cups(cups, 0, 0);
+
+        let data_one = play_game(&cups_one, 100);
+
+        let (labels, _) = build_labels_stars(&data_one);
+
+        labels
+    }
+
+    fn part2(cups: &Self::Input) -> Self::Part2 {
+        let cups_two = extend_cups(cups, 10, 1000000 + 1);
 
-    let (labels, _) = build_labels_stars(&data_one);
-    let (_, stars) = build_labels_stars(&data_two);
+        let data_two = play_game(&cups_two, 10000000);
 
-    println!("{:?}", labels);
-    println!("{:?}", stars);
+        let (_, stars) = build_labels_stars(&data_two);
+
+        stars
+    }
 }
 
 fn extend_cups(cups: &[Label], start: Label, end: Label) -> Vec<Label> {
-    cups.iter().map(|label| *label).chain(start..end).collect()
+    cups.iter().copied().chain(start..end).collect()
 }
 
 fn play_game(cups: &[Label], moves: usize) -> HashMap<Label, NextLabel> {
     let min_label = *cups.iter().min().expect("min label");
     let max_label = *cups.iter().max().expect("max label");
 
-    let mut current_data = build_data(&cups);
+    let mut current_data = build_data(cups);
     let mut current_label = cups[0];
 
     for _ in 0..moves {
@@ -53,7 +68,7 @@ fn next_move(
     min_label: Label,
     max_label: Label,
 ) {
-    let remove_one = current_data[&current_label];
+    let remove_one = current_data[current_label];
     let remove_two = current_data[&remove_one];
     let remove_three = current_data[&remove_two];
     let aux_next_label = current_data[&remove_three];
@@ -66,7 +81,7 @@ fn next_move(
         .entry(*current_label)
         .and_modify(|label| *label = aux_next_label);
 
-    let destination_label = find_destination(&current_data, &current_label, min_label, max_label);
+    let destination_label = find_destination(current_data, current_label, min_label, max_label);
     let aux_next_label = current_data[&destination_label];
 
     current_data
@@ -127,3 +142,13 @@ fn build_labels_stars(data: &HashMap<Label, NextLabel>) -> (String, u128) {
 
     (labels, stars)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day23;
+
+    aoc_utils::examples! {
+        Day23,
+        example: "day23.txt" => part1 "67384529";
+    }
+}
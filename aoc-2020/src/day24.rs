@@ -1,188 +1,108 @@
-use aoc_utils;
+use aoc_utils::{Hex, Input, LifeRule, ParseResult, Solution, Sparse, Vec2};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::HashSet;
 
-type Floor = HashMap<AxialCoordinate, bool>;
-type Value = i32;
+type AxialCoordinate = Vec2<i64>;
+type Floor = HashSet<AxialCoordinate>;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct AxialCoordinate {
-    q: Value,
-    r: Value,
-}
+/// The step names, in the order of [`Hex::OFFSETS`].
+const STEPS: [&str; 6] = ["e", "se", "sw", "w", "nw", "ne"];
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    East,
-    Southeast,
-    Southwest,
-    West,
-    Northwest,
-    Northeast,
-}
+pub struct Day24;
 
-impl AsRef<Self> for Direction {
-    fn as_ref(&self) -> &Self {
-        self
-    }
-}
+impl Solution for Day24 {
+    type Input = Vec<Vec<AxialCoordinate>>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day24.txt", true).collect();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let steps = parse_steps(&lines);
+        parse_steps(&lines)
+    }
+
+    fn part1(steps: &Self::Input) -> Self::Part1 {
+        let initial_floor = build_initial(steps);
 
-    let initial_floor = build_initial(&steps);
-    let daily_floor = build_daily(&initial_floor, 100);
+        count_tiles(&initial_floor)
+    }
 
-    let count_initial = count_tiles(&initial_floor);
-    let count_daily = count_tiles(&daily_floor);
+    fn part2(steps: &Self::Input) -> Self::Part2 {
+        let initial_floor = build_initial(steps);
+        let daily_floor = build_daily(&initial_floor, 100);
 
-    println!("{:?}", count_initial);
-    println!("{:?}", count_daily);
+        count_tiles(&daily_floor)
+    }
 }
 
-fn parse_steps(lines: &[String]) -> Vec<Vec<Direction>> {
+/// Each step as its offset from [`Hex::OFFSETS`].
+fn parse_steps(lines: &[String]) -> ParseResult<Vec<Vec<AxialCoordinate>>> {
     let re = Regex::new(r"se|sw|nw|ne|e|w").expect("regex");
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
-            re.find_iter(line)
-                .map(|step| match step.as_str() {
-                    "e" => Direction::East,
-                    "se" => Direction::Southeast,
-                    "sw" => Direction::Southwest,
-                    "w" => Direction::West,
-                    "nw" => Direction::Northwest,
-                    "ne" => Direction::Northeast,
-                    _ => Direction::East,
+            let mut offset = 0;
+
+            re.find_iter(line.text)
+                .map(|step| {
+                    if step.start() != offset {
+                        return Err(line.error_at(offset, "e, se, sw, w, nw or ne"));
+                    }
+
+                    offset = step.end();
+
+                    let direction = STEPS
+                        .iter()
+                        .position(|&name| name == step.as_str())
+                        .ok_or_else(|| line.error(step.as_str(), "e, se, sw, w, nw or ne"))?;
+
+                    Ok(Hex::OFFSETS[direction])
+                })
+                .collect::<ParseResult<Vec<_>>>()
+                .and_then(|steps| match offset == line.len() {
+                    true => Ok(steps),
+                    false => Err(line.error_at(offset, "e, se, sw, w, nw or ne")),
                 })
-                .collect()
         })
         .collect()
 }
 
-fn build_initial<T, U>(steps: &[U]) -> Floor
-where
-    T: AsRef<Direction>,
-    U: AsRef<[T]>,
-{
-    let mut floor: Floor = HashMap::new();
+fn build_initial(steps: &[Vec<AxialCoordinate>]) -> Floor {
+    let mut floor = Floor::new();
 
-    for directions in steps {
-        let mut position = AxialCoordinate { q: 0, r: 0 };
+    for offsets in steps {
+        let position = offsets
+            .iter()
+            .fold(AxialCoordinate::default(), |position, &offset| {
+                position + offset
+            });
 
-        for direction in directions.as_ref() {
-            position = next_position(&position, direction.as_ref());
+        if !floor.remove(&position) {
+            floor.insert(position);
         }
-
-        floor
-            .entry(position)
-            .and_modify(|black| *black = !*black)
-            .or_insert(true);
     }
 
     floor
 }
 
 fn build_daily(initial_floor: &Floor, days: usize) -> Floor {
-    let mut floor = initial_floor.clone();
-
-    let mut min_q = floor.keys().map(|tile| tile.q).min().expect("min q") - 1;
-    let mut max_q = floor.keys().map(|tile| tile.q).max().expect("max q") + 1;
-    let mut min_r = floor.keys().map(|tile| tile.r).min().expect("min r") - 1;
-    let mut max_r = floor.keys().map(|tile| tile.r).max().expect("max r") + 1;
+    let mut floor = Sparse::new(Hex, initial_floor.iter().copied());
 
-    for _ in 0..days {
-        floor = next_floor_state(&floor, (min_q, max_q, min_r, max_r));
-
-        min_q -= 1;
-        max_q += 1;
-        min_r -= 1;
-        max_r += 1;
-    }
+    floor.run(&LifeRule::new(&[2], &[1, 2]), days);
 
-    floor
-}
-
-fn next_floor_state(
-    floor: &Floor,
-    (min_q, max_q, min_r, max_r): (Value, Value, Value, Value),
-) -> Floor {
-    let mut next_floor = floor.clone();
-
-    for q in min_q..(max_q + 1) {
-        for r in min_r..(max_r + 1) {
-            let position = AxialCoordinate { q, r };
-
-            let black = floor.get(&position).map(|black| *black).unwrap_or(false);
-            let neighbors = count_neighbors(&position, &floor);
-
-            if black && (neighbors == 0 || neighbors > 2) {
-                next_floor
-                    .entry(position)
-                    .and_modify(|black| *black = false);
-            } else if !black && neighbors == 2 {
-                next_floor
-                    .entry(position)
-                    .and_modify(|black| *black = true)
-                    .or_insert(true);
-            }
-        }
-    }
-
-    next_floor
-}
-
-fn count_neighbors(position: &AxialCoordinate, floor: &Floor) -> usize {
-    vec![
-        Direction::East,
-        Direction::Southeast,
-        Direction::Southwest,
-        Direction::West,
-        Direction::Northwest,
-        Direction::Northeast,
-    ]
-    .iter()
-    .map(|direction| {
-        let position = next_position(position, direction);
-
-        floor.get(&position).map(|black| *black).unwrap_or(false)
-    })
-    .filter(|black| *black)
-    .count()
+    floor.exceptions().copied().collect()
 }
 
 fn count_tiles(floor: &Floor) -> usize {
-    floor.iter().filter(|(_, black)| **black).count()
+    floor.len()
 }
 
-fn next_position(position: &AxialCoordinate, direction: &Direction) -> AxialCoordinate {
-    match direction {
-        Direction::East => AxialCoordinate {
-            q: position.q + 1,
-            r: position.r + 0,
-        },
-        Direction::Southeast => AxialCoordinate {
-            q: position.q + 0,
-            r: position.r + 1,
-        },
-        Direction::Southwest => AxialCoordinate {
-            q: position.q - 1,
-            r: position.r + 1,
-        },
-        Direction::West => AxialCoordinate {
-            q: position.q - 1,
-            r: position.r + 0,
-        },
-        Direction::Northwest => AxialCoordinate {
-            q: position.q + 0,
-            r: position.r - 1,
-        },
-        Direction::Northeast => AxialCoordinate {
-            q: position.q + 1,
-            r: position.r - 1,
-        },
+#[cfg(test)]
+mod tests {
+    use super::Day24;
+
+    aoc_utils::examples! {
+        Day24,
+        example: "day24.txt" => part1 "10", part2 "2208";
     }
 }
@@ -1,43 +1,64 @@
-use aoc_utils;
+use aoc_utils::{discrete_log, modpow, Input, ParseError, ParseResult, Solution};
 
 type Value = u128;
 
-pub fn run() {
-    let keys: Vec<Value> = aoc_utils::read_numbers("inputs/day25.txt", ",").collect();
+const SUBJECT_NUMBER: Value = 7;
+const MODULUS: Value = 20201227;
 
-    let subject_number = 7;
-    let public_key_card = keys[0];
-    let public_key_door = keys[1];
+pub struct Day25;
 
-    let loop_size_card = calculate_loop_size(subject_number, public_key_card);
+impl Solution for Day25 {
+    /// The card's loop size and the door's public key.
+    type Input = (Value, Value);
+    type Part1 = Value;
+    type Part2 = &'static str;
 
-    let encryption_key = transform(public_key_door, loop_size_card);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+        let mut lines = aoc_utils::numbered(&lines);
 
-    println!("{}", encryption_key);
-}
+        let card = lines
+            .next()
+            .ok_or_else(|| ParseError::new(1, 1, "first public key"))?;
+        let public_key_card = card.parse(card.text, "number")?;
+        let loop_size_card = calculate_loop_size(SUBJECT_NUMBER, public_key_card)
+            .ok_or_else(|| card.error(card.text, "a key some loop size produces"))?;
 
-fn transform(subject_number: Value, loop_size: Value) -> Value {
-    let mut current_key = 1;
+        let door = lines
+            .next()
+            .ok_or_else(|| card.error_end("second public key"))?;
+        let public_key_door = door.parse(door.text, "number")?;
+
+        if let Some(line) = lines.next() {
+            return Err(line.error_at(0, "only two public keys"));
+        }
+
+        Ok((loop_size_card, public_key_door))
+    }
 
-    for _ in 0..loop_size {
-        current_key *= subject_number;
-        current_key %= 20201227;
+    fn part1(&(loop_size_card, public_key_door): &Self::Input) -> Self::Part1 {
+        transform(public_key_door, loop_size_card)
     }
 
-    current_key
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        "-"
+    }
+}
+
+fn transform(subject_number: Value, loop_size: Value) -> Value {
+    modpow(subject_number, loop_size, MODULUS)
 }
 
-fn calculate_loop_size(subject_number: Value, key: Value) -> Value {
-    let mut loop_size = 0;
-    let mut current_key = 1;
+fn calculate_loop_size(subject_number: Value, key: Value) -> Option<Value> {
+    discrete_log(subject_number, key, MODULUS)
+}
 
-    loop {
-        loop_size += 1;
-        current_key *= subject_number;
-        current_key %= 20201227;
+#[cfg(test)]
+mod tests {
+    use super::Day25;
 
-        if current_key == key {
-            return loop_size;
-        }
+    aoc_utils::examples! {
+        Day25,
+        example: "day25.txt" => part1 "14897079";
     }
 }
@@ -0,0 +1,707 @@
+use aoc_utils::{Input, Line, ParseError, ParseResult};
+use std::fmt::{Display, Formatter};
+
+pub type Value = i64;
+pub type Program = Vec<Instruction>;
+
+const DIGITS: [Value; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+/// Entry values of z the extracted constants are checked against.
+const SAMPLE_Z: [Value; 8] = [0, 1, 7, 25, 26, 27, 26 * 26 + 5, 12345];
+
+const BASE: Value = 26;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    pub const ALL: [Register; 4] = [Register::W, Register::X, Register::Y, Register::Z];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operand {
+    Constant(Value),
+    Register(Register),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+/// Why a program could not be run or analysed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AluError {
+    MissingInput(usize),
+    DivisionByZero(usize),
+    InvalidModulo(usize),
+    Block(usize),
+    Unsatisfiable(usize),
+    Unbalanced,
+}
+
+impl Display for AluError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AluError::MissingInput(index) => {
+                write!(formatter, "instruction {}: no input left", index)
+            }
+            AluError::DivisionByZero(index) => {
+                write!(formatter, "instruction {}: division by zero", index)
+            }
+            AluError::InvalidModulo(index) => {
+                write!(formatter, "instruction {}: invalid modulo operands", index)
+            }
+            AluError::Block(index) => {
+                write!(formatter, "block {} is not a digit check", index)
+            }
+            AluError::Unsatisfiable(index) => {
+                write!(formatter, "block {} rejects every digit", index)
+            }
+            AluError::Unbalanced => write!(formatter, "blocks do not pair up"),
+        }
+    }
+}
+
+impl std::error::Error for AluError {}
+
+impl Display for Register {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Register::W => "w",
+            Register::X => "x",
+            Register::Y => "y",
+            Register::Z => "z",
+        };
+
+        write!(formatter, "{}", name)
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Constant(value) => write!(formatter, "{}", value),
+            Operand::Register(register) => write!(formatter, "{}", register),
+        }
+    }
+}
+
+/// Prints the instruction back in source syntax.
+impl Display for Instruction {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let (name, a, b) = match *self {
+            Instruction::Inp(a) => return write!(formatter, "inp {}", a),
+            Instruction::Add(a, b) => ("add", a, b),
+            Instruction::Mul(a, b) => ("mul", a, b),
+            Instruction::Div(a, b) => ("div", a, b),
+            Instruction::Mod(a, b) => ("mod", a, b),
+            Instruction::Eql(a, b) => ("eql", a, b),
+        };
+
+        write!(formatter, "{} {} {}", name, a, b)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct Registers {
+    pub w: Value,
+    pub x: Value,
+    pub y: Value,
+    pub z: Value,
+}
+
+impl Registers {
+    pub fn read(&self, register: Register) -> Value {
+        match register {
+            Register::W => self.w,
+            Register::X => self.x,
+            Register::Y => self.y,
+            Register::Z => self.z,
+        }
+    }
+
+    pub fn write(&mut self, register: Register, value: Value) {
+        match register {
+            Register::W => self.w = value,
+            Register::X => self.x = value,
+            Register::Y => self.y = value,
+            Register::Z => self.z = value,
+        }
+    }
+
+    fn operand(&self, operand: Operand) -> Value {
+        match operand {
+            Operand::Constant(value) => value,
+            Operand::Register(register) => self.read(register),
+        }
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "w={} x={} y={} z={}",
+            self.w, self.x, self.y, self.z
+        )
+    }
+}
+
+/// Runs a program one instruction at a time.
+#[derive(Debug, Clone)]
+pub struct Alu<'a> {
+    program: &'a [Instruction],
+    next: usize,
+    registers: Registers,
+}
+
+impl<'a> Alu<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Alu::with_registers(program, Registers::default())
+    }
+
+    pub fn with_registers(program: &'a [Instruction], registers: Registers) -> Self {
+        Alu {
+            program,
+            next: 0,
+            registers,
+        }
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    /// Index of the instruction the next step executes.
+    pub fn next(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.next >= self.program.len()
+    }
+
+    /// Executes one instruction, taking an input from `inputs` if it reads one.
+    pub fn step(&mut self, inputs: &mut impl Iterator<Item = Value>) -> Result<(), AluError> {
+        let index = self.next;
+        let registers = &mut self.registers;
+
+        let (a, value) = match self.program[index] {
+            Instruction::Inp(a) => (a, inputs.next().ok_or(AluError::MissingInput(index))?),
+            Instruction::Add(a, b) => (a, registers.read(a) + registers.operand(b)),
+            Instruction::Mul(a, b) => (a, registers.read(a) * registers.operand(b)),
+            Instruction::Div(a, b) => {
+                let divisor = registers.operand(b);
+
+                if divisor == 0 {
+                    return Err(AluError::DivisionByZero(index));
+                }
+
+                (a, registers.read(a) / divisor)
+            }
+            Instruction::Mod(a, b) => {
+                let (value, modulus) = (registers.read(a), registers.operand(b));
+
+                if value < 0 || modulus <= 0 {
+                    return Err(AluError::InvalidModulo(index));
+                }
+
+                (a, value % modulus)
+            }
+            Instruction::Eql(a, b) => (a, (registers.read(a) == registers.operand(b)) as Value),
+        };
+
+        registers.write(a, value);
+        self.next += 1;
+
+        Ok(())
+    }
+
+    pub fn run(&mut self, inputs: impl IntoIterator<Item = Value>) -> Result<Registers, AluError> {
+        let mut inputs = inputs.into_iter();
+
+        while !self.is_halted() {
+            self.step(&mut inputs)?;
+        }
+
+        Ok(self.registers)
+    }
+}
+
+pub fn parse_program(input: &Input) -> ParseResult<Program> {
+    let lines: Vec<String> = input.lines(true).collect();
+
+    aoc_utils::numbered(&lines)
+        .map(|line| parse_instruction(&line))
+        .collect()
+}
+
+fn parse_instruction(line: &Line) -> ParseResult<Instruction> {
+    let mut parts = line.split_ascii_whitespace();
+
+    let instruction = parts.next().ok_or_else(|| line.error_end("instruction"))?;
+    let a = parts.next();
+    let b = parts.next();
+
+    let a = || match a {
+        Some(a) => parse_register(line, a),
+        None => Err(line.error_end("register")),
+    };
+
+    let b = || match b {
+        Some(b) => parse_operand(line, b),
+        None => Err(line.error_end("register or number")),
+    };
+
+    let instruction = match instruction {
+        "inp" => Instruction::Inp(a()?),
+        "add" => Instruction::Add(a()?, b()?),
+        "mul" => Instruction::Mul(a()?, b()?),
+        "div" => Instruction::Div(a()?, b()?),
+        "mod" => Instruction::Mod(a()?, b()?),
+        "eql" => Instruction::Eql(a()?, b()?),
+        _ => return Err(line.error(instruction, "inp, add, mul, div, mod or eql")),
+    };
+
+    Ok(instruction)
+}
+
+fn parse_register(line: &Line, value: &str) -> ParseResult<Register> {
+    match value {
+        "w" => Ok(Register::W),
+        "x" => Ok(Register::X),
+        "y" => Ok(Register::Y),
+        "z" => Ok(Register::Z),
+        _ => Err(line.error(value, "register w, x, y or z")),
+    }
+}
+
+fn parse_operand(line: &Line, value: &str) -> ParseResult<Operand> {
+    match value {
+        "w" | "x" | "y" | "z" => Ok(Operand::Register(parse_register(line, value)?)),
+        _ => Ok(Operand::Constant(line.parse(value, "register or number")?)),
+    }
+}
+
+/// Splits the program before every `inp`, so each block reads exactly one digit.
+pub fn blocks(program: &[Instruction]) -> Vec<&[Instruction]> {
+    let mut starts: Vec<usize> = (0..program.len())
+        .filter(|&index| matches!(program[index], Instruction::Inp(_)))
+        .collect();
+
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+
+    let ends = starts.iter().skip(1).copied().chain([program.len()]);
+
+    starts
+        .iter()
+        .zip(ends)
+        .filter(|&(&start, end)| start < end)
+        .map(|(&start, end)| &program[start..end])
+        .collect()
+}
+
+/// A register's value in terms of the block's digit and the registers on entry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expr {
+    Constant(Value),
+    Digit,
+    Entry(Register),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+    Eql(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Combines two expressions, folding constants and dropping identities.
+    fn apply(instruction: Instruction, a: Expr, b: Expr) -> Expr {
+        use Expr::Constant;
+
+        match (instruction, a, b) {
+            (Instruction::Add(..), Constant(a), Constant(b)) => Constant(a + b),
+            (Instruction::Add(..), Constant(0), e) | (Instruction::Add(..), e, Constant(0)) => e,
+            (Instruction::Add(..), a, b) => Expr::Add(Box::new(a), Box::new(b)),
+            (Instruction::Mul(..), Constant(a), Constant(b)) => Constant(a * b),
+            (Instruction::Mul(..), Constant(0), _) | (Instruction::Mul(..), _, Constant(0)) => {
+                Constant(0)
+            }
+            (Instruction::Mul(..), Constant(1), e) | (Instruction::Mul(..), e, Constant(1)) => e,
+            (Instruction::Mul(..), a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+            (Instruction::Div(..), Constant(a), Constant(b)) if b != 0 => Constant(a / b),
+            (Instruction::Div(..), e, Constant(1)) => e,
+            (Instruction::Div(..), a, b) => Expr::Div(Box::new(a), Box::new(b)),
+            (Instruction::Mod(..), Constant(a), Constant(b)) if b > 0 => Constant(a % b),
+            (Instruction::Mod(..), a, b) => Expr::Mod(Box::new(a), Box::new(b)),
+            (Instruction::Eql(..), Constant(a), Constant(b)) => Constant((a == b) as Value),
+            (Instruction::Eql(..), a, b) => Expr::Eql(Box::new(a), Box::new(b)),
+            (Instruction::Inp(_), _, _) => unreachable!("inp has no operands"),
+        }
+    }
+
+    /// This expression and everything below it, parents first.
+    fn subexpressions(&self) -> Vec<&Expr> {
+        let mut found = vec![self];
+
+        match self {
+            Expr::Add(a, b)
+            | Expr::Mul(a, b)
+            | Expr::Div(a, b)
+            | Expr::Mod(a, b)
+            | Expr::Eql(a, b) => {
+                found.extend(a.subexpressions());
+                found.extend(b.subexpressions());
+            }
+            Expr::Constant(_) | Expr::Digit | Expr::Entry(_) => {}
+        }
+
+        found
+    }
+}
+
+/// The value of z after a block, with its digit and the registers on entry left unknown.
+pub fn symbolic_z(block: &[Instruction]) -> Expr {
+    let mut registers = Register::ALL.map(Expr::Entry);
+
+    for &instruction in block {
+        match instruction {
+            Instruction::Inp(a) => registers[a.index()] = Expr::Digit,
+            Instruction::Add(a, b)
+            | Instruction::Mul(a, b)
+            | Instruction::Div(a, b)
+            | Instruction::Mod(a, b)
+            | Instruction::Eql(a, b) => {
+                let b = match b {
+                    Operand::Constant(value) => Expr::Constant(value),
+                    Operand::Register(register) => registers[register.index()].clone(),
+                };
+
+                registers[a.index()] = Expr::apply(instruction, registers[a.index()].clone(), b);
+            }
+        }
+    }
+
+    registers[Register::Z.index()].clone()
+}
+
+/// What a MONAD digit check does: `x` says whether the digit missed `z % 26 + add_x`, and
+/// `z` becomes `z / div`, followed by `digit + add_y` in base 26 when it missed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BlockConstants {
+    pub div: Value,
+    pub add_x: Value,
+    pub add_y: Value,
+}
+
+impl BlockConstants {
+    /// Reads the constants off the block's symbolic z, then checks them by running the block.
+    pub fn extract(block: &[Instruction]) -> Option<Self> {
+        let z = symbolic_z(block);
+        let parts = z.subexpressions();
+        let entry = Expr::Entry(Register::Z);
+
+        let constant = |expr: &Expr| match *expr {
+            Expr::Constant(value) => Some(value),
+            _ => None,
+        };
+
+        let div = parts
+            .iter()
+            .find_map(|expr| match expr {
+                Expr::Div(a, b) if **a == entry => constant(b),
+                _ => None,
+            })
+            .unwrap_or(1);
+
+        let add_x = parts.iter().find_map(|expr| match expr {
+            Expr::Eql(a, b) if **b == Expr::Digit => match &**a {
+                Expr::Add(a, b) if matches!(**a, Expr::Mod(..)) => constant(b),
+                Expr::Mod(..) => Some(0),
+                _ => None,
+            },
+            _ => None,
+        })?;
+
+        let add_y = parts.iter().find_map(|expr| match expr {
+            Expr::Mul(a, _) => match &**a {
+                Expr::Add(a, b) if **a == Expr::Digit => constant(b),
+                Expr::Digit => Some(0),
+                _ => None,
+            },
+            _ => None,
+        })?;
+
+        let constants = BlockConstants { div, add_x, add_y };
+
+        let matches = SAMPLE_Z.iter().all(|&z| {
+            DIGITS.iter().all(|&digit| {
+                let registers = Registers {
+                    z,
+                    ..Registers::default()
+                };
+
+                let result = Alu::with_registers(block, registers).run([digit]);
+
+                result.map(|registers| registers.z) == Ok(constants.apply(z, digit))
+            })
+        });
+
+        matches.then_some(constants)
+    }
+
+    pub fn apply(&self, z: Value, digit: Value) -> Value {
+        let x = (z % BASE + self.add_x != digit) as Value;
+
+        z / self.div * ((BASE - 1) * x + 1) + (digit + self.add_y) * x
+    }
+}
+
+/// The constants of every block, in program order.
+pub fn analyse(program: &[Instruction]) -> Result<Vec<BlockConstants>, AluError> {
+    blocks(program)
+        .into_iter()
+        .enumerate()
+        .map(|(index, block)| BlockConstants::extract(block).ok_or(AluError::Block(index)))
+        .collect()
+}
+
+/// `digits[right] == digits[left] + offset` holds for every accepted model number.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Constraint {
+    pub left: usize,
+    pub right: usize,
+    pub offset: Value,
+}
+
+/// Pairs every block that pushes a digit onto z with the block that pops it. Accepted numbers
+/// are exactly those where every pop matches, since any miss leaves z non-zero.
+pub fn constraints(blocks: &[BlockConstants]) -> Result<Vec<Constraint>, AluError> {
+    if blocks.is_empty() {
+        return Err(AluError::Unbalanced);
+    }
+
+    let mut stack = Vec::new();
+    let mut constraints = Vec::new();
+
+    for (index, block) in blocks.iter().enumerate() {
+        if block.add_y < 0 || block.add_y + 9 >= BASE {
+            return Err(AluError::Block(index));
+        }
+
+        match block.div {
+            1 if block.add_x > 9 => stack.push((index, block.add_y)),
+            BASE => {
+                let (left, add_y) = stack.pop().ok_or(AluError::Unbalanced)?;
+                let offset = add_y + block.add_x;
+
+                if offset.abs() >= DIGITS.len() as Value {
+                    return Err(AluError::Unsatisfiable(index));
+                }
+
+                constraints.push(Constraint {
+                    left,
+                    right: index,
+                    offset,
+                });
+            }
+            _ => return Err(AluError::Block(index)),
+        }
+    }
+
+    match stack.is_empty() {
+        true => Ok(constraints),
+        false => Err(AluError::Unbalanced),
+    }
+}
+
+/// The largest or smallest number meeting every constraint from [`constraints`], which only
+/// returns pairs that some digits satisfy.
+pub fn model_number(constraints: &[Constraint], largest: bool) -> String {
+    let mut digits = vec![0; constraints.len() * 2];
+
+    for constraint in constraints {
+        let left = match largest {
+            true => 9.min(9 - constraint.offset),
+            false => 1.max(1 - constraint.offset),
+        };
+        let right = left + constraint.offset;
+
+        debug_assert!(DIGITS.contains(&left) && DIGITS.contains(&right));
+
+        digits[constraint.left] = left;
+        digits[constraint.right] = right;
+    }
+
+    digits.iter().map(Value::to_string).collect()
+}
+
+/// Parses a MONAD program and derives its digit constraints, pointing at the first line of the
+/// block that breaks the pattern when it is not one.
+pub fn parse_constraints(input: &Input) -> ParseResult<Vec<Constraint>> {
+    let program = parse_program(input)?;
+
+    analyse(&program)
+        .and_then(|blocks| constraints(&blocks))
+        .map_err(|error| {
+            let blocks = blocks(&program);
+
+            let (block, expected) = match error {
+                AluError::Block(index) => (index, "a MONAD digit check"),
+                AluError::Unsatisfiable(index) => (index, "a digit check some digit passes"),
+                _ => (blocks.len(), "blocks that pair up into digit checks"),
+            };
+
+            let line = blocks[..block]
+                .iter()
+                .map(|block| block.len())
+                .sum::<usize>()
+                + 1;
+
+            ParseError::new(line.min(program.len().max(1)), 1, expected)
+        })
+}
+
+/// A numbered listing of a program with a header above every block.
+#[derive(Debug, Copy, Clone)]
+pub struct Disassembly<'a> {
+    program: &'a [Instruction],
+}
+
+impl<'a> Disassembly<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Disassembly { program }
+    }
+}
+
+impl Display for Disassembly<'_> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut index = 0;
+
+        for (number, block) in blocks(self.program).into_iter().enumerate() {
+            match BlockConstants::extract(block) {
+                Some(constants) => writeln!(
+                    formatter,
+                    "; block {}: div {}, add-x {}, add-y {}",
+                    number, constants.div, constants.add_x, constants.add_y
+                )?,
+                None => writeln!(formatter, "; block {}", number)?,
+            }
+
+            for instruction in block {
+                writeln!(formatter, "{:>4}  {}", index, instruction)?;
+
+                index += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: &str = "inp w
+mul x 0
+add x z
+mod x 26
+div z 26
+add x -6
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 7
+mul y x
+add z y";
+
+    fn program(text: &str) -> Program {
+        parse_program(&Input::literal(text)).expect("program")
+    }
+
+    #[test]
+    fn runs_and_prints_instructions() {
+        let program = program("inp x\nmul x -1\ninp z\neql z x");
+        let registers = Alu::new(&program).run([3, -3]).expect("run");
+
+        assert_eq!(registers.x, -3);
+        assert_eq!(registers.z, 1);
+        assert_eq!(program[1].to_string(), "mul x -1");
+        assert_eq!(Alu::new(&program).run([3]), Err(AluError::MissingInput(2)));
+    }
+
+    #[test]
+    fn extracts_block_constants() {
+        let program = program(BLOCK);
+
+        assert_eq!(
+            BlockConstants::extract(&program),
+            Some(BlockConstants {
+                div: 26,
+                add_x: -6,
+                add_y: 7
+            })
+        );
+        assert_eq!(BlockConstants::extract(&program[..5]), None);
+    }
+
+    #[test]
+    fn pairs_pushes_with_pops() {
+        let push = BlockConstants {
+            div: 1,
+            add_x: 12,
+            add_y: 4,
+        };
+        let pop = BlockConstants {
+            div: 26,
+            add_x: -8,
+            add_y: 1,
+        };
+
+        let pairs = constraints(&[push, pop]).expect("constraints");
+
+        assert_eq!(
+            pairs,
+            [Constraint {
+                left: 0,
+                right: 1,
+                offset: -4
+            }]
+        );
+        assert_eq!(model_number(&pairs, true), "95");
+        assert_eq!(model_number(&pairs, false), "51");
+        assert_eq!(constraints(&[push]), Err(AluError::Unbalanced));
+        assert_eq!(constraints(&[]), Err(AluError::Unbalanced));
+
+        let strict = BlockConstants { add_x: 6, ..pop };
+
+        assert_eq!(
+            constraints(&[push, strict]),
+            Err(AluError::Unsatisfiable(1))
+        );
+    }
+}
@@ -0,0 +1,487 @@
+use std::fmt::{Display, Formatter};
+
+pub type Number = u64;
+pub type Version = Number;
+
+const VERSION_BITS: usize = 3;
+const TYPE_BITS: usize = 3;
+const LITERAL_TYPE: Number = 4;
+const GROUP_BITS: usize = 4;
+const LENGTH_BITS: usize = 15;
+const COUNT_BITS: usize = 11;
+
+/// Why a transmission could not be decoded or encoded, with the bit it happened at.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BitsError {
+    InvalidHex(usize),
+    Truncated(usize),
+    LiteralOverflow(usize),
+    Operands(usize),
+    Unencodable(usize),
+}
+
+impl BitsError {
+    pub fn bit(&self) -> usize {
+        match *self {
+            BitsError::InvalidHex(bit)
+            | BitsError::Truncated(bit)
+            | BitsError::LiteralOverflow(bit)
+            | BitsError::Operands(bit)
+            | BitsError::Unencodable(bit) => bit,
+        }
+    }
+
+    pub fn expected(&self) -> &'static str {
+        match self {
+            BitsError::InvalidHex(_) => "hexadecimal digit",
+            BitsError::Truncated(_) => "more packet bits",
+            BitsError::LiteralOverflow(_) => "literal of at most 64 bits",
+            BitsError::Operands(_) => "one or more sub-packets, exactly two for comparisons",
+            BitsError::Unencodable(_) => "version below 8 and at most 2047 sub-packets",
+        }
+    }
+}
+
+impl Display for BitsError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "bit {}: expected {}",
+            self.bit(),
+            self.expected()
+        )
+    }
+}
+
+impl std::error::Error for BitsError {}
+
+/// Reads big-endian bit fields from a byte buffer.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader::with_length(bytes, bytes.len() * 8)
+    }
+
+    /// A reader over the first `length` bits only.
+    pub fn with_length(bytes: &'a [u8], length: usize) -> Self {
+        BitReader {
+            bytes,
+            position: 0,
+            end: length.min(bytes.len() * 8),
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.end - self.position
+    }
+
+    /// Reads `count` bits, at most 64, as an unsigned number.
+    pub fn read_bits(&mut self, count: usize) -> Result<Number, BitsError> {
+        assert!(count <= Number::BITS as usize, "at most 64 bits at a time");
+
+        if count > self.remaining() {
+            return Err(BitsError::Truncated(self.end));
+        }
+
+        let mut value = 0;
+
+        for bit in self.position..self.position + count {
+            let byte = self.bytes[bit / 8];
+
+            value = value << 1 | Number::from(byte >> (7 - bit % 8) & 1);
+        }
+
+        self.position += count;
+
+        Ok(value)
+    }
+}
+
+/// Appends big-endian bit fields to a byte buffer.
+#[derive(Debug, Clone, Default)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    length: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Writes the low `count` bits of `value`.
+    pub fn write_bits(&mut self, value: Number, count: usize) {
+        for shift in (0..count).rev() {
+            if self.length.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+
+            let bit = (value >> shift & 1) as u8;
+
+            *self.bytes.last_mut().expect("byte") |= bit << (7 - self.length % 8);
+            self.length += 1;
+        }
+    }
+
+    /// The bits written so far, zero-padded to whole hexadecimal digits.
+    pub fn to_hex(&self) -> String {
+        self.bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<String>()
+            .chars()
+            .take(self.length.div_ceil(4))
+            .collect()
+    }
+}
+
+/// Packs hexadecimal digits into bytes, returning how many bits they hold.
+pub fn decode_hex(text: &str) -> Result<(Vec<u8>, usize), BitsError> {
+    let nibbles: Vec<u8> = text
+        .chars()
+        .enumerate()
+        .map(|(index, char)| {
+            char.to_digit(16)
+                .map(|digit| digit as u8)
+                .ok_or(BitsError::InvalidHex(index * 4))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let bytes = nibbles
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect();
+
+    Ok((bytes, nibbles.len() * 4))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_type(packet_type: Number) -> Option<Self> {
+        match packet_type {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Minimum),
+            3 => Some(Operator::Maximum),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::EqualTo),
+            _ => None,
+        }
+    }
+
+    fn packet_type(self) -> Number {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => count == 2,
+            Operator::Sum | Operator::Product | Operator::Minimum | Operator::Maximum => count > 0,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "min",
+            Operator::Maximum => "max",
+            Operator::GreaterThan => "gt",
+            Operator::LessThan => "lt",
+            Operator::EqualTo => "eq",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Packet {
+    Literal(Version, Number),
+    Operator(Version, Operator, Vec<Packet>),
+}
+
+impl Packet {
+    /// Decodes the outermost packet of a hexadecimal transmission, ignoring trailing padding.
+    pub fn from_hex(text: &str) -> Result<Self, BitsError> {
+        let (bytes, length) = decode_hex(text)?;
+
+        Packet::decode(&mut BitReader::with_length(&bytes, length))
+    }
+
+    pub fn decode(reader: &mut BitReader) -> Result<Self, BitsError> {
+        let start = reader.position();
+        let version = reader.read_bits(VERSION_BITS)?;
+        let packet_type = reader.read_bits(TYPE_BITS)?;
+
+        let Some(operator) = Operator::from_type(packet_type) else {
+            return Ok(Packet::Literal(version, decode_literal(reader)?));
+        };
+
+        let mut packets = Vec::new();
+
+        match reader.read_bits(1)? {
+            0 => {
+                let length = reader.read_bits(LENGTH_BITS)? as usize;
+
+                if length > reader.remaining() {
+                    return Err(BitsError::Truncated(reader.end));
+                }
+
+                let end = reader.end;
+
+                reader.end = reader.position + length;
+
+                while reader.remaining() > 0 {
+                    packets.push(Packet::decode(reader)?);
+                }
+
+                reader.end = end;
+            }
+            _ => {
+                let count = reader.read_bits(COUNT_BITS)?;
+
+                for _ in 0..count {
+                    packets.push(Packet::decode(reader)?);
+                }
+            }
+        }
+
+        if !operator.accepts(packets.len()) {
+            return Err(BitsError::Operands(start));
+        }
+
+        Ok(Packet::Operator(version, operator, packets))
+    }
+
+    pub fn to_hex(&self) -> Result<String, BitsError> {
+        let mut writer = BitWriter::new();
+
+        self.encode(&mut writer)?;
+
+        Ok(writer.to_hex())
+    }
+
+    /// Writes operators with a sub-packet count, so decoding gives back the same tree.
+    pub fn encode(&self, writer: &mut BitWriter) -> Result<(), BitsError> {
+        let version = match *self {
+            Packet::Literal(version, _) | Packet::Operator(version, _, _) => version,
+        };
+
+        if version >> VERSION_BITS != 0 {
+            return Err(BitsError::Unencodable(writer.len()));
+        }
+
+        match self {
+            Packet::Literal(_, value) => {
+                writer.write_bits(version, VERSION_BITS);
+                writer.write_bits(LITERAL_TYPE, TYPE_BITS);
+
+                let groups = (Number::BITS - value.leading_zeros()).max(1) as usize;
+                let groups = groups.div_ceil(GROUP_BITS);
+
+                for group in (0..groups).rev() {
+                    writer.write_bits((group > 0) as Number, 1);
+                    writer.write_bits(value >> (group * GROUP_BITS), GROUP_BITS);
+                }
+            }
+            Packet::Operator(_, operator, packets) => {
+                if packets.len() >> COUNT_BITS != 0 {
+                    return Err(BitsError::Unencodable(writer.len()));
+                }
+
+                writer.write_bits(version, VERSION_BITS);
+                writer.write_bits(operator.packet_type(), TYPE_BITS);
+                writer.write_bits(1, 1);
+                writer.write_bits(packets.len() as Number, COUNT_BITS);
+
+                for packet in packets {
+                    packet.encode(writer)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The versions of this packet and every packet inside it, added up.
+    pub fn version_sum(&self) -> Version {
+        match self {
+            &Packet::Literal(version, _) => version,
+            Packet::Operator(version, _, packets) => {
+                version + packets.iter().map(Packet::version_sum).sum::<Version>()
+            }
+        }
+    }
+
+    pub fn value(&self) -> Number {
+        let (operator, packets) = match self {
+            &Packet::Literal(_, value) => return value,
+            Packet::Operator(_, operator, packets) => (operator, packets),
+        };
+
+        let mut values = packets.iter().map(Packet::value);
+
+        match operator {
+            Operator::Sum => values.sum(),
+            Operator::Product => values.product(),
+            Operator::Minimum => values.min().expect("min"),
+            Operator::Maximum => values.max().expect("max"),
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => {
+                let (a, b) = (values.next().expect("a"), values.next().expect("b"));
+
+                let holds = match operator {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+
+                holds as Number
+            }
+        }
+    }
+}
+
+/// Prints the expression the packet computes, e.g. `sum(3, max(7, 8))`.
+impl Display for Packet {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Literal(_, value) => write!(formatter, "{}", value),
+            Packet::Operator(_, operator, packets) => {
+                write!(formatter, "{}(", operator.name())?;
+
+                for (index, packet) in packets.iter().enumerate() {
+                    if index > 0 {
+                        write!(formatter, ", ")?;
+                    }
+
+                    write!(formatter, "{}", packet)?;
+                }
+
+                write!(formatter, ")")
+            }
+        }
+    }
+}
+
+fn decode_literal(reader: &mut BitReader) -> Result<Number, BitsError> {
+    let start = reader.position();
+    let mut value: Number = 0;
+
+    loop {
+        let more = reader.read_bits(1)?;
+        let group = reader.read_bits(GROUP_BITS)?;
+
+        if value.leading_zeros() < GROUP_BITS as u32 {
+            return Err(BitsError::LiteralOverflow(start));
+        }
+
+        value = value << GROUP_BITS | group;
+
+        if more == 0 {
+            return Ok(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_and_writes_bit_fields() {
+        let mut reader = BitReader::new(&[0b1011_0010, 0b1100_0000]);
+
+        assert_eq!(reader.read_bits(3), Ok(0b101));
+        assert_eq!(reader.read_bits(7), Ok(0b1001011));
+        assert_eq!(reader.read_bits(7), Err(BitsError::Truncated(16)));
+
+        let mut writer = BitWriter::new();
+
+        writer.write_bits(0b101, 3);
+        writer.write_bits(0b1001011, 7);
+
+        assert_eq!(writer.len(), 10);
+        assert_eq!(writer.to_hex(), "B2C");
+    }
+
+    #[test]
+    fn decodes_and_prints_expressions() {
+        let packet = Packet::from_hex("9C0141080250320F1802104A08").expect("packet");
+
+        assert_eq!(packet.to_string(), "eq(sum(1, 3), product(2, 2))");
+        assert_eq!(packet.value(), 1);
+        assert_eq!(Packet::from_hex("D2FE28"), Ok(Packet::Literal(6, 2021)));
+    }
+
+    #[test]
+    fn encoded_trees_round_trip() {
+        for hex in [
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            let packet = Packet::from_hex(hex).expect("packet");
+            let encoded = packet.to_hex().expect("hex");
+
+            assert_eq!(Packet::from_hex(&encoded), Ok(packet));
+        }
+
+        let too_new = Packet::Literal(8, 1);
+
+        assert_eq!(too_new.to_hex(), Err(BitsError::Unencodable(0)));
+    }
+
+    #[test]
+    fn reports_malformed_transmissions() {
+        assert_eq!(Packet::from_hex("D2FE2"), Err(BitsError::Truncated(20)));
+        assert_eq!(Packet::from_hex("D2XE28"), Err(BitsError::InvalidHex(8)));
+        assert_eq!(
+            Packet::from_hex("38006F45291200"),
+            Ok(Packet::Operator(
+                1,
+                Operator::LessThan,
+                vec![Packet::Literal(6, 10), Packet::Literal(2, 20)]
+            ))
+        );
+
+        let lonely = Packet::Operator(0, Operator::GreaterThan, vec![Packet::Literal(0, 1)]);
+        let hex = lonely.to_hex().expect("hex");
+
+        assert_eq!(Packet::from_hex(&hex), Err(BitsError::Operands(0)));
+    }
+}
@@ -1,23 +1,42 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use itertools::Itertools;
 
-pub fn run() {
-    let numbers: Vec<i32> = aoc_utils::read_numbers("inputs/day01.txt", ",").collect();
+pub struct Day01;
 
-    let count_two = numbers
-        .iter()
-        .tuple_windows()
-        .filter(|(one, two)| two > one)
-        .count();
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let count_three = numbers
-        .iter()
-        .tuple_windows()
-        .map(|(one, two, three)| one + two + three)
-        .tuple_windows()
-        .filter(|(one, two)| two > one)
-        .count();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.numbers(",")
+    }
 
-    println!("{}", count_two);
-    println!("{}", count_three);
+    fn part1(numbers: &Self::Input) -> Self::Part1 {
+        numbers
+            .iter()
+            .tuple_windows()
+            .filter(|(one, two)| two > one)
+            .count()
+    }
+
+    fn part2(numbers: &Self::Input) -> Self::Part2 {
+        numbers
+            .iter()
+            .tuple_windows()
+            .map(|(one, two, three)| one + two + three)
+            .tuple_windows()
+            .filter(|(one, two)| two > one)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day01;
+
+    aoc_utils::examples! {
+        Day01,
+        example: "day01.txt" => part1 "7", part2 "5";
+    }
 }
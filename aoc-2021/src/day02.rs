@@ -1,5 +1,4 @@
-use aoc_utils;
-use rayon::prelude::*;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 
 type Direction = String;
 type Value = i64;
@@ -8,29 +7,51 @@ type Horizontal = Value;
 type Depth = Value;
 type Aim = Value;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day02.txt", true).collect();
+pub struct Day02;
 
-    let commands = parse_commands(lines);
+impl Solution for Day02 {
+    type Input = Vec<Command>;
+    type Part1 = Value;
+    type Part2 = Value;
 
-    let (one_horizontal, one_depth) = move_submarine(&commands);
-    let (two_horizontal, two_depth, _) = move_aim_submarine(&commands);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", one_horizontal * one_depth);
-    println!("{}", two_horizontal * two_depth);
+        parse_commands(&lines)
+    }
+
+    fn part1(commands: &Self::Input) -> Self::Part1 {
+        let (horizontal, depth) = move_submarine(commands);
+
+        horizontal * depth
+    }
+
+    fn part2(commands: &Self::Input) -> Self::Part2 {
+        let (horizontal, depth, _) = move_aim_submarine(commands);
+
+        horizontal * depth
+    }
 }
 
-fn parse_commands(lines: Vec<String>) -> Vec<Command> {
-    lines.par_iter().map(|line| parse_command(line)).collect()
+fn parse_commands(lines: &[String]) -> ParseResult<Vec<Command>> {
+    aoc_utils::numbered(lines)
+        .map(|line| parse_command(&line))
+        .collect()
 }
 
-fn parse_command(line: &str) -> Command {
+fn parse_command(line: &Line) -> ParseResult<Command> {
     let mut parts = line.split_ascii_whitespace();
 
-    let direction = parts.next().expect("direction").trim().to_string();
-    let value = parts.next().expect("value").trim().parse().expect("number");
+    let direction = match parts.next() {
+        Some(direction @ ("forward" | "down" | "up")) => direction.to_string(),
+        Some(direction) => return Err(line.error(direction, "forward, down or up")),
+        None => return Err(line.error_end("direction")),
+    };
 
-    (direction, value)
+    let value = parts.next().ok_or_else(|| line.error_end("value"))?;
+    let value = line.parse(value, "number")?;
+
+    Ok((direction, value))
 }
 
 fn move_submarine(commands: &[Command]) -> (Horizontal, Depth) {
@@ -56,3 +77,13 @@ fn move_aim_submarine(commands: &[Command]) -> (Horizontal, Depth, Aim) {
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day02;
+
+    aoc_utils::examples! {
+        Day02,
+        example: "day02.txt" => part1 "150", part2 "900";
+    }
+}
@@ -1,29 +1,44 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use itertools::Itertools;
-use rayon::prelude::*;
 
 type Bit = bool;
 type BitVec = Vec<Bit>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day03.txt", true).collect();
+pub struct Day03;
 
-    let items = parse_items(lines);
+impl Solution for Day03 {
+    type Input = Vec<BitVec>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let (gamma, epsilon) = get_gamma_epsilon(&items);
-    let (oxygen_generator, co2_scrubber) = get_oxygen_generator_co2_scrubber(&items);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", gamma * epsilon);
-    println!("{}", oxygen_generator * co2_scrubber);
+        parse_items(&lines)
+    }
+
+    fn part1(items: &Self::Input) -> Self::Part1 {
+        let (gamma, epsilon) = get_gamma_epsilon(items);
+
+        gamma * epsilon
+    }
+
+    fn part2(items: &Self::Input) -> Self::Part2 {
+        let (oxygen_generator, co2_scrubber) = get_oxygen_generator_co2_scrubber(items);
+
+        oxygen_generator * co2_scrubber
+    }
 }
 
-fn parse_items(lines: Vec<String>) -> Vec<BitVec> {
-    lines
-        .par_iter()
+fn parse_items(lines: &[String]) -> ParseResult<Vec<BitVec>> {
+    aoc_utils::numbered(lines)
         .map(|line| {
-            line.split("")
-                .filter(|bit| !bit.is_empty())
-                .map(|bit| bit.parse::<usize>().expect("bit") != 0)
+            line.char_indices()
+                .map(|(offset, bit)| match bit {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(line.error_at(offset, "bit 0 or 1")),
+                })
                 .collect()
         })
         .collect()
@@ -78,21 +93,28 @@ fn get_filter_out_bit_vec(items: &[BitVec], most_common: bool) -> BitVec {
     for index in 0..dimensions {
         let frequency_bit_vec = get_frequency_bit_vec(&remaining_items, most_common);
 
-        remaining_items = remaining_items
-            .into_iter()
-            .filter(|bit_vec| bit_vec[index] == frequency_bit_vec[index])
-            .collect();
+        remaining_items.retain(|bit_vec| bit_vec[index] == frequency_bit_vec[index]);
 
         if remaining_items.len() <= 1 {
             break;
         }
     }
 
-    remaining_items.first().expect("first").clone().to_owned()
+    remaining_items.first().expect("first").to_vec()
 }
 
-fn bit_vec2usize(bit_vec: &BitVec) -> usize {
+fn bit_vec2usize(bit_vec: &[Bit]) -> usize {
     let binary = bit_vec.iter().map(|&bit| bit as usize).join("");
 
     usize::from_str_radix(&binary[..], 2).expect("binary")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day03;
+
+    aoc_utils::examples! {
+        Day03,
+        example: "day03.txt" => part1 "198", part2 "230";
+    }
+}
@@ -1,7 +1,7 @@
-use aoc_utils;
+use aoc_utils::{Block, Input, Line, ParseError, ParseResult, Solution};
 
 #[derive(Debug, Clone)]
-struct Bingo {
+pub struct Bingo {
     numbers: Vec<usize>,
     boards: Vec<Board>,
 }
@@ -32,7 +32,7 @@ impl Board {
         let sum_unmarked: usize = self
             .data
             .iter()
-            .flat_map(|row| row)
+            .flatten()
             .filter(|number| !numbers.contains(number))
             .sum();
 
@@ -42,59 +42,62 @@ impl Board {
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day04.txt", false).collect();
+pub struct Day04;
 
-    let bingo = parse_bingo(lines);
+impl Solution for Day04 {
+    type Input = Bingo;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let (first_numbers, first_board) = find_first_winner(&bingo);
-    let (last_numbers, last_board) = find_last_winner(&bingo);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        parse_bingo(input)
+    }
+
+    fn part1(bingo: &Self::Input) -> Self::Part1 {
+        let (first_numbers, first_board) = find_first_winner(bingo);
+
+        first_board.get_score(first_numbers)
+    }
+
+    fn part2(bingo: &Self::Input) -> Self::Part2 {
+        let (last_numbers, last_board) = find_last_winner(bingo);
 
-    println!("{:?}", first_board.get_score(first_numbers));
-    println!("{:?}", last_board.get_score(last_numbers));
+        last_board.get_score(last_numbers)
+    }
 }
 
-fn parse_bingo(lines: Vec<String>) -> Bingo {
-    let line_numbers = &lines[0];
-    let line_boards = &lines[2..];
+fn parse_bingo(input: &Input) -> ParseResult<Bingo> {
+    let mut blocks = input.blocks();
+
+    let numbers = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "drawn numbers"))?;
 
-    let numbers = parse_line_numbers(line_numbers);
-    let boards = parse_line_boards(line_boards);
+    let numbers = parse_line_numbers(&numbers[0])?;
+    let boards = blocks.map(parse_board).collect::<ParseResult<_>>()?;
 
-    Bingo { numbers, boards }
+    Ok(Bingo { numbers, boards })
 }
 
-fn parse_line_numbers(line: &str) -> Vec<usize> {
+fn parse_line_numbers(line: &Line) -> ParseResult<Vec<usize>> {
     line.split(&[' ', ','][..])
-        .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(|item| item.parse().expect("number"))
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| line.parse(item, "number"))
         .collect()
 }
 
-fn parse_line_boards(lines: &[String]) -> Vec<Board> {
-    let mut boards = vec![];
-    let mut data = vec![];
-
-    for line in lines {
-        if !line.trim().is_empty() {
-            data.push(parse_line_numbers(line));
-        } else if !data.is_empty() {
-            boards.push(Board { data });
-            data = vec![];
-        }
-    }
+fn parse_board(block: Block) -> ParseResult<Board> {
+    let data = block
+        .lines()
+        .map(|line| parse_line_numbers(&line))
+        .collect::<ParseResult<_>>()?;
 
-    if !data.is_empty() {
-        boards.push(Board { data });
-    }
-
-    boards
+    Ok(Board { data })
 }
 
 fn find_first_winner(bingo: &Bingo) -> (&[usize], &Board) {
     (0..bingo.numbers.len())
-        .filter_map(|round| {
+        .find_map(|round| {
             let numbers = &bingo.numbers[0..round + 1];
 
             bingo
@@ -103,14 +106,13 @@ fn find_first_winner(bingo: &Bingo) -> (&[usize], &Board) {
                 .find(|board| board.is_winner(numbers))
                 .map(|board| (numbers, board))
         })
-        .next()
         .expect("winner")
 }
 
 fn find_last_winner(bingo: &Bingo) -> (&[usize], &Board) {
     (0..bingo.numbers.len())
         .rev()
-        .filter_map(|round| {
+        .find_map(|round| {
             let numbers = &bingo.numbers[0..round + 1];
 
             bingo
@@ -123,6 +125,15 @@ fn find_last_winner(bingo: &Bingo) -> (&[usize], &Board) {
                     (next_numbers, board)
                 })
         })
-        .next()
         .expect("winner")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day04;
+
+    aoc_utils::examples! {
+        Day04,
+        example: "day04.txt" => part1 "4512", part2 "1924";
+    }
+}
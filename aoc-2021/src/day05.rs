@@ -1,16 +1,12 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution, Vec2};
 use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashMap;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct Point {
-    x: isize,
-    y: isize,
-}
+type Point = Vec2<isize>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct LineSegment {
+pub struct LineSegment {
     a: Point,
     b: Point,
 }
@@ -29,51 +25,58 @@ impl LineSegment {
     }
 
     fn trace(&self) -> Vec<Point> {
-        let direction_x = self.b.x.cmp(&self.a.x) as isize;
-        let direction_y = self.b.y.cmp(&self.a.y) as isize;
-
-        let steps = (self.b.x - self.a.x).abs().max((self.b.y - self.a.y).abs());
+        let direction = (self.b - self.a).signum();
+        let steps = self.a.chebyshev(self.b);
 
         (0..steps + 1)
-            .map(|index| Point {
-                x: self.a.x + (direction_x * index),
-                y: self.a.y + (direction_y * index),
-            })
+            .map(|index| self.a + direction * index)
             .collect()
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day05.txt", true).collect();
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<LineSegment>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let line_segments = parse_line_segments(lines);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let points = trace_line_segments(&line_segments, false);
-    let points_diagonal = trace_line_segments(&line_segments, true);
+        parse_line_segments(&lines)
+    }
+
+    fn part1(line_segments: &Self::Input) -> Self::Part1 {
+        let points = trace_line_segments(line_segments, false);
+
+        count_overlap_points(&points)
+    }
 
-    let overlap_points = count_overlap_points(&points);
-    let overlap_points_diagonal = count_overlap_points(&points_diagonal);
+    fn part2(line_segments: &Self::Input) -> Self::Part2 {
+        let points_diagonal = trace_line_segments(line_segments, true);
 
-    println!("{}", overlap_points);
-    println!("{}", overlap_points_diagonal);
+        count_overlap_points(&points_diagonal)
+    }
 }
 
-fn parse_line_segments(lines: Vec<String>) -> Vec<LineSegment> {
+fn parse_line_segments(lines: &[String]) -> ParseResult<Vec<LineSegment>> {
     let re = Regex::new(r"(?P<x1>\d+),(?P<y1>\d+) -> (?P<x2>\d+),(?P<y2>\d+)").expect("regex");
 
-    lines
-        .par_iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
-            let caps = re.captures(line).expect("captures");
-            let x1 = caps["x1"].to_string().parse().expect("number");
-            let y1 = caps["y1"].to_string().parse().expect("number");
-            let x2 = caps["x2"].to_string().parse().expect("number");
-            let y2 = caps["y2"].to_string().parse().expect("number");
-
-            LineSegment {
-                a: Point { x: x1, y: y1 },
-                b: Point { x: x2, y: y2 },
-            }
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "x1,y1 -> x2,y2"))?;
+            let x1 = line.parse(&caps["x1"], "number")?;
+            let y1 = line.parse(&caps["y1"], "number")?;
+            let x2 = line.parse(&caps["x2"], "number")?;
+            let y2 = line.parse(&caps["y2"], "number")?;
+
+            Ok(LineSegment {
+                a: Point::new(x1, y1),
+                b: Point::new(x2, y2),
+            })
         })
         .collect()
 }
@@ -105,3 +108,13 @@ fn count_overlap_points(points: &[Point]) -> usize {
         .filter(|(_, &count)| count > 1)
         .count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day05;
+
+    aoc_utils::examples! {
+        Day05,
+        example: "day05.txt" => part1 "5", part2 "12";
+    }
+}
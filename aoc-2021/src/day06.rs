@@ -1,20 +1,30 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 
 type Count = u64;
 type TimerCount = Vec<Count>;
 
 const MAX_TIMER: usize = 8;
 
-pub fn run() {
-    let numbers: Vec<usize> = aoc_utils::read_numbers("inputs/day06.txt", ",").collect();
+pub struct Day06;
 
-    let timer_count = parse_timer_count(numbers);
+impl Solution for Day06 {
+    type Input = TimerCount;
+    type Part1 = Count;
+    type Part2 = Count;
 
-    let simulation_80 = simulate(&timer_count, 80);
-    let simulation_256 = simulate(&timer_count, 256);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let numbers: Vec<usize> = input.numbers(",")?;
 
-    println!("{}", simulation_80.iter().sum::<Count>());
-    println!("{}", simulation_256.iter().sum::<Count>());
+        Ok(parse_timer_count(numbers))
+    }
+
+    fn part1(timer_count: &Self::Input) -> Self::Part1 {
+        simulate(timer_count, 80).iter().sum()
+    }
+
+    fn part2(timer_count: &Self::Input) -> Self::Part2 {
+        simulate(timer_count, 256).iter().sum()
+    }
 }
 
 fn parse_timer_count(numbers: Vec<usize>) -> TimerCount {
@@ -27,13 +37,13 @@ fn parse_timer_count(numbers: Vec<usize>) -> TimerCount {
     timer_count
 }
 
-fn simulate(initial_timer_count: &TimerCount, days: usize) -> TimerCount {
-    (0..days).fold(initial_timer_count.clone(), |timer_count, _| {
+fn simulate(initial_timer_count: &[Count], days: usize) -> TimerCount {
+    (0..days).fold(initial_timer_count.to_vec(), |timer_count, _| {
         next_day(&timer_count)
     })
 }
 
-fn next_day(current_timer_count: &TimerCount) -> TimerCount {
+fn next_day(current_timer_count: &[Count]) -> TimerCount {
     let mut next_timer_count = vec![0; MAX_TIMER + 1];
 
     for (timer, &count) in current_timer_count.iter().enumerate() {
@@ -47,3 +57,13 @@ fn next_day(current_timer_count: &TimerCount) -> TimerCount {
 
     next_timer_count
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day06;
+
+    aoc_utils::examples! {
+        Day06,
+        example: "day06.txt" => part1 "5934", part2 "26984457539";
+    }
+}
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -8,16 +8,26 @@ type Count = usize;
 type Cluster = (Position, Count);
 type Clusters = Vec<Cluster>;
 
-pub fn run() {
-    let numbers: Vec<usize> = aoc_utils::read_numbers("inputs/day07.txt", ",").collect();
+pub struct Day07;
 
-    let clusters = parse_clusters(numbers);
+impl Solution for Day07 {
+    type Input = Clusters;
+    type Part1 = Fuel;
+    type Part2 = Fuel;
 
-    let fuel_cost_constant = least_fuel_cost(&clusters, true);
-    let fuel_cost_increasing = least_fuel_cost(&clusters, false);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let numbers: Vec<usize> = input.numbers(",")?;
 
-    println!("{}", fuel_cost_constant);
-    println!("{}", fuel_cost_increasing);
+        Ok(parse_clusters(numbers))
+    }
+
+    fn part1(clusters: &Self::Input) -> Self::Part1 {
+        least_fuel_cost(clusters, true)
+    }
+
+    fn part2(clusters: &Self::Input) -> Self::Part2 {
+        least_fuel_cost(clusters, false)
+    }
 }
 
 fn parse_clusters(numbers: Vec<usize>) -> Clusters {
@@ -32,7 +42,7 @@ fn parse_clusters(numbers: Vec<usize>) -> Clusters {
     map.into_iter().collect()
 }
 
-fn least_fuel_cost(clusters: &Clusters, constant_rate: bool) -> Fuel {
+fn least_fuel_cost(clusters: &[Cluster], constant_rate: bool) -> Fuel {
     let min = *clusters.iter().map(|(x, _)| x).min().expect("min");
     let max = *clusters.iter().map(|(x, _)| x).max().expect("max");
 
@@ -43,7 +53,7 @@ fn least_fuel_cost(clusters: &Clusters, constant_rate: bool) -> Fuel {
         .expect("min")
 }
 
-fn fuel_cost_clusters(clusters: &Clusters, next: Position, constant: bool) -> Fuel {
+fn fuel_cost_clusters(clusters: &[Cluster], next: Position, constant: bool) -> Fuel {
     clusters
         .par_iter()
         .map(|&(current, count)| fuel_cost(current, next, constant) * count)
@@ -51,7 +61,7 @@ fn fuel_cost_clusters(clusters: &Clusters, next: Position, constant: bool) -> Fu
 }
 
 fn fuel_cost(current: Position, next: Position, constant: bool) -> Fuel {
-    let distance = (next as isize - current as isize).abs() as usize;
+    let distance = (next as isize - current as isize).unsigned_abs();
 
     if constant {
         distance
@@ -59,3 +69,13 @@ fn fuel_cost(current: Position, next: Position, constant: bool) -> Fuel {
         ((distance + 1) * distance) / 2
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day07;
+
+    aoc_utils::examples! {
+        Day07,
+        example: "day07.txt" => part1 "37", part2 "168";
+    }
+}
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::collections::HashMap;
@@ -9,34 +9,51 @@ type Output = SevenSegmentDisplay;
 type FourDigitDisplay = (Vec<Pattern>, Vec<Output>);
 type DecodedFourDigitDisplay = Vec<usize>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day08.txt", true).collect();
+pub struct Day08;
 
-    let parsed_displays = parse_four_digit_displays(lines);
+impl Solution for Day08 {
+    type Input = Vec<FourDigitDisplay>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let decoded_displays = decode_four_digit_displays(&parsed_displays);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let count = count_digits(&decoded_displays);
-    let sum = sum_displays(&decoded_displays);
+        parse_four_digit_displays(&lines)
+    }
+
+    fn part1(parsed_displays: &Self::Input) -> Self::Part1 {
+        let decoded_displays = decode_four_digit_displays(parsed_displays);
+
+        count_digits(&decoded_displays)
+    }
 
-    println!("{}", count);
-    println!("{}", sum);
+    fn part2(parsed_displays: &Self::Input) -> Self::Part2 {
+        let decoded_displays = decode_four_digit_displays(parsed_displays);
+
+        sum_displays(&decoded_displays)
+    }
 }
 
-fn parse_four_digit_displays(lines: Vec<String>) -> Vec<FourDigitDisplay> {
-    lines
-        .par_iter()
-        .map(|line| parse_four_digit_display(line))
+fn parse_four_digit_displays(lines: &[String]) -> ParseResult<Vec<FourDigitDisplay>> {
+    aoc_utils::numbered(lines)
+        .map(|line| parse_four_digit_display(&line))
         .collect()
 }
 
-fn parse_four_digit_display(line: &str) -> FourDigitDisplay {
-    let mut parts = line.split("|");
+fn parse_four_digit_display(line: &Line) -> ParseResult<FourDigitDisplay> {
+    let (patterns, outputs) = line
+        .split_once('|')
+        .ok_or_else(|| line.error_end("'|' between patterns and outputs"))?;
+
+    if let Some(offset) = line.find(|char: char| !matches!(char, 'a'..='g' | ' ' | '|')) {
+        return Err(line.error_at(offset, "segment 'a' to 'g'"));
+    }
 
-    let patterns = parse_seven_segment_displays(parts.next().expect("patterns"));
-    let outputs = parse_seven_segment_displays(parts.next().expect("outputs"));
+    let patterns = parse_seven_segment_displays(patterns);
+    let outputs = parse_seven_segment_displays(outputs);
 
-    (patterns, outputs)
+    Ok((patterns, outputs))
 }
 
 fn parse_seven_segment_displays(line: &str) -> Vec<SevenSegmentDisplay> {
@@ -63,10 +80,7 @@ fn sum_displays(displays: &[DecodedFourDigitDisplay]) -> usize {
 }
 
 fn decode_four_digit_displays(displays: &[FourDigitDisplay]) -> Vec<DecodedFourDigitDisplay> {
-    displays
-        .par_iter()
-        .map(|display| decode_four_digit_display(display))
-        .collect()
+    displays.par_iter().map(decode_four_digit_display).collect()
 }
 
 fn decode_four_digit_display((patterns, outputs): &FourDigitDisplay) -> DecodedFourDigitDisplay {
@@ -117,9 +131,20 @@ fn decode_patterns(patterns: &[Pattern]) -> HashMap<String, usize> {
         .collect()
 }
 
-fn count_segments(pattern: &Pattern, mask: &Pattern) -> usize {
+fn count_segments(pattern: &str, mask: &str) -> usize {
     pattern
         .chars()
         .filter(|segments| !mask.chars().contains(segments))
         .count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day08;
+
+    aoc_utils::examples! {
+        Day08,
+        example: "day08.txt" => part1 "26", part2 "61229";
+        example_single: "day08-2.txt" => part1 "0", part2 "5353";
+    }
+}
@@ -1,49 +1,46 @@
-use aoc_utils;
-use colored::*;
+use aoc_utils::{Grid, Input, ParseResult, Position, Solution};
 use itertools::Itertools;
 use rayon::prelude::*;
 
-type X = isize;
-type Y = isize;
-type Point = (X, Y);
+type Point = Position;
 
 type Height = usize;
-type Heightmap = Vec<Vec<Height>>;
+type Heightmap = Grid<Height>;
 
 type Data = (Point, Height);
 
 const HEIGHT_BLOCKED: usize = 9;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day09.txt", true).collect();
+pub struct Day09;
 
-    let heightmap = parse_heightmap(lines);
+impl Solution for Day09 {
+    type Input = Heightmap;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let low_points = find_low_points(&heightmap);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let basins = find_basins(&heightmap, &low_points);
+        parse_heightmap(&lines)
+    }
+
+    fn part1(heightmap: &Self::Input) -> Self::Part1 {
+        let low_points = find_low_points(heightmap);
 
-    let risk_levels = sum_risk_levels(&low_points);
+        sum_risk_levels(&low_points)
+    }
 
-    let largest_basins = size_largest_basins(&basins, 3);
+    fn part2(heightmap: &Self::Input) -> Self::Part2 {
+        let low_points = find_low_points(heightmap);
 
-    draw_heightmap(&heightmap, &low_points, &basins);
+        let basins = find_basins(heightmap, &low_points);
 
-    println!("{}", risk_levels);
-    println!("{}", largest_basins);
+        size_largest_basins(&basins, 3)
+    }
 }
 
-fn parse_heightmap(lines: Vec<String>) -> Heightmap {
-    lines
-        .par_iter()
-        .map(|line| {
-            line.split("")
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .map(|value| value.parse().expect("number"))
-                .collect()
-        })
-        .collect()
+fn parse_heightmap(lines: &[String]) -> ParseResult<Heightmap> {
+    Grid::parse_digits(aoc_utils::numbered(lines))
 }
 
 fn sum_risk_levels(low_points: &[Data]) -> usize {
@@ -61,27 +58,18 @@ fn size_largest_basins(basins: &[Vec<Data>], count: usize) -> usize {
 
 fn find_low_points(heightmap: &Heightmap) -> Vec<Data> {
     heightmap
-        .par_iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.par_iter()
-                .enumerate()
-                .map(|(x, &height)| ((x as isize, y as isize), height))
-                .filter(|(point, _)| is_low_point(heightmap, point))
-                .collect::<Vec<Data>>()
-        })
+        .iter()
+        .filter(|&(point, _)| is_low_point(heightmap, &point))
+        .map(|(point, &height)| (point, height))
         .collect()
 }
 
-fn is_low_point(heightmap: &Heightmap, &(x, y): &Point) -> bool {
-    let height = get_height(heightmap, &(x, y));
-
-    let top = get_height(heightmap, &(x, y - 1));
-    let down = get_height(heightmap, &(x, y + 1));
-    let left = get_height(heightmap, &(x - 1, y));
-    let right = get_height(heightmap, &(x + 1, y));
+fn is_low_point(heightmap: &Heightmap, point: &Point) -> bool {
+    let height = heightmap[*point];
 
-    height < top.min(down).min(left).min(right)
+    heightmap
+        .neighbors4(*point)
+        .all(|neighbor| height < heightmap[neighbor])
 }
 
 fn find_basins(heightmap: &Heightmap, low_points: &[Data]) -> Vec<Vec<Data>> {
@@ -115,51 +103,20 @@ fn find_basin(heightmap: &Heightmap, &low_point: &Data) -> Vec<Data> {
     basin
 }
 
-fn get_neighbors(heightmap: &Heightmap, &(x, y): &Point) -> Vec<Data> {
-    vec![(0, -1), (0, 1), (-1, 0), (1, 0)]
-        .par_iter()
-        .map(|(direction_x, direction_y)| {
-            let point = ((x + direction_x), (y + direction_y));
-            let height = get_height(heightmap, &point);
-
-            (point, height)
-        })
+fn get_neighbors(heightmap: &Heightmap, point: &Point) -> Vec<Data> {
+    heightmap
+        .neighbors4(*point)
+        .map(|neighbor| (neighbor, heightmap[neighbor]))
         .filter(|&(_, height)| height < HEIGHT_BLOCKED)
         .collect()
 }
 
-fn get_height(heightmap: &Heightmap, &(x, y): &Point) -> Height {
-    heightmap
-        .get(y as usize)
-        .map(|row| row.get(x as usize).map(|&height| height))
-        .flatten()
-        .unwrap_or(HEIGHT_BLOCKED)
-}
-
-fn draw_heightmap(heightmap: &Heightmap, low_points: &[Data], basins: &[Vec<Data>]) -> () {
-    let first = basins.get(0).expect("first");
-    let second = basins.get(1).expect("second");
-    let third = basins.get(2).expect("third");
-
-    for (y, row) in heightmap.iter().enumerate() {
-        for (x, &height) in row.iter().enumerate() {
-            let data = ((x as isize, y as isize), height);
-
-            if low_points.contains(&data) {
-                print!("{}", "██".bright_white());
-            } else if first.contains(&data) {
-                print!("{}", "██".red());
-            } else if second.contains(&data) {
-                print!("{}", "██".green());
-            } else if third.contains(&data) {
-                print!("{}", "██".blue());
-            } else if height == HEIGHT_BLOCKED {
-                print!("{}", "██".black());
-            } else {
-                print!("{}", "██".white());
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::Day09;
 
-        println!();
+    aoc_utils::examples! {
+        Day09,
+        example: "day09.txt" => part1 "15", part2 "1134";
     }
 }
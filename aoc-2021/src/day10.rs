@@ -1,11 +1,11 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use itertools::Itertools;
 use rayon::prelude::*;
 
 type Score = u64;
 
 #[derive(Debug, Clone)]
-struct Validation {
+pub struct Validation {
     full: String,
     status: Status,
     error: usize,
@@ -19,16 +19,32 @@ enum Status {
     Corrupted,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day10.txt", true).collect();
+pub struct Day10;
 
-    let validations = check_lines(&lines);
+impl Solution for Day10 {
+    type Input = Vec<Validation>;
+    type Part1 = Score;
+    type Part2 = Score;
 
-    let syntax_error = sum_syntax_error_score(&validations);
-    let autocomplete = median_autocomplete_score(&validations);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", syntax_error);
-    println!("{}", autocomplete);
+        for line in aoc_utils::numbered(&lines) {
+            if let Some(offset) = line.find(|symbol| !"()[]{}<>".contains(symbol)) {
+                return Err(line.error_at(offset, "bracket"));
+            }
+        }
+
+        Ok(check_lines(&lines))
+    }
+
+    fn part1(validations: &Self::Input) -> Self::Part1 {
+        sum_syntax_error_score(validations)
+    }
+
+    fn part2(validations: &Self::Input) -> Self::Part2 {
+        median_autocomplete_score(validations)
+    }
 }
 
 fn check_lines(lines: &[String]) -> Vec<Validation> {
@@ -78,7 +94,7 @@ fn sum_syntax_error_score(validations: &[Validation]) -> Score {
     validations
         .iter()
         .filter(|validation| validation.status == Status::Corrupted)
-        .map(|validation| syntax_error_score(validation))
+        .map(syntax_error_score)
         .sum()
 }
 
@@ -102,7 +118,7 @@ fn median_autocomplete_score(validations: &[Validation]) -> Score {
     let scores: Vec<Score> = validations
         .iter()
         .filter(|validation| validation.status == Status::Incomplete)
-        .map(|validation| autocomplete_score(validation))
+        .map(autocomplete_score)
         .sorted()
         .collect();
 
@@ -113,7 +129,7 @@ fn autocomplete_score(validation: &Validation) -> Score {
     validation
         .stack
         .chars()
-        .map(|symbol| invert(symbol))
+        .map(invert)
         .rev()
         .fold(0, |acc, symbol| {
             let score = match symbol {
@@ -141,3 +157,13 @@ fn invert(symbol: char) -> char {
         _ => ' ',
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day10;
+
+    aoc_utils::examples! {
+        Day10,
+        example: "day10.txt" => part1 "26397", part2 "288957";
+    }
+}
@@ -1,47 +1,44 @@
-use aoc_utils;
-use colored::*;
-use rayon::prelude::*;
+use aoc_utils::{Grid, Input, ParseResult, Solution};
 
 type Flash = usize;
 type Step = usize;
 type Energy = usize;
-type Grid = Vec<Vec<Energy>>;
+type Octopuses = Grid<Energy>;
 
-const ENERGY_BEFORE_FLASH: Energy = 9;
 const ENERGY_FLASH: Energy = 10;
 const ENERGY_AFTER_FLASH: Energy = 11;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day11.txt", true).collect();
+pub struct Day11;
 
-    let grid = parse_grid(lines);
+impl Solution for Day11 {
+    type Input = Octopuses;
+    type Part1 = Flash;
+    type Part2 = Step;
 
-    let (grid_flash, flash) = find_flash_after_steps(&grid, 100);
-    let (grid_step, step) = find_step_flash_all(&grid);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("Flash {}", flash);
-    draw_grid(&grid_flash);
+        parse_grid(&lines)
+    }
+
+    fn part1(grid: &Self::Input) -> Self::Part1 {
+        let (_, flash) = find_flash_after_steps(grid, 100);
 
-    println!();
+        flash
+    }
 
-    println!("Step {}", step);
-    draw_grid(&grid_step);
+    fn part2(grid: &Self::Input) -> Self::Part2 {
+        let (_, step) = find_step_flash_all(grid);
+
+        step
+    }
 }
 
-fn parse_grid(lines: Vec<String>) -> Grid {
-    lines
-        .par_iter()
-        .map(|line| {
-            line.split("")
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .map(|value| value.parse().expect("energy"))
-                .collect()
-        })
-        .collect()
+fn parse_grid(lines: &[String]) -> ParseResult<Octopuses> {
+    Grid::parse_digits(aoc_utils::numbered(lines))
 }
 
-fn find_flash_after_steps(grid: &Grid, steps: usize) -> (Grid, Flash) {
+fn find_flash_after_steps(grid: &Octopuses, steps: usize) -> (Octopuses, Flash) {
     let mut grid = grid.clone();
     let mut flash = 0;
 
@@ -55,10 +52,8 @@ fn find_flash_after_steps(grid: &Grid, steps: usize) -> (Grid, Flash) {
     (grid, flash)
 }
 
-fn find_step_flash_all(grid: &Grid) -> (Grid, Step) {
-    let rows = grid.len();
-    let columns = grid.first().map(|row| row.len()).unwrap_or(0);
-    let count = rows * columns;
+fn find_step_flash_all(grid: &Octopuses) -> (Octopuses, Step) {
+    let count = grid.width() * grid.height();
 
     let mut grid = grid.clone();
 
@@ -75,7 +70,7 @@ fn find_step_flash_all(grid: &Grid) -> (Grid, Step) {
     (grid, Step::MAX)
 }
 
-fn simulate_step(grid: &Grid) -> (Grid, Flash) {
+fn simulate_step(grid: &Octopuses) -> (Octopuses, Flash) {
     let mut next_grid = grid.clone();
 
     increase_energy(&mut next_grid);
@@ -89,71 +84,53 @@ fn simulate_step(grid: &Grid) -> (Grid, Flash) {
     (next_grid, next_flash)
 }
 
-fn increase_energy(grid: &mut Grid) {
-    grid.iter_mut()
-        .for_each(|row| row.iter_mut().for_each(|energy| *energy += 1));
+fn increase_energy(grid: &mut Octopuses) {
+    grid.values_mut().for_each(|energy| *energy += 1);
 }
 
-fn flash_adjacent(grid: &mut Grid) {
-    let rows = grid.len();
-    let columns = grid.first().map(|row| row.len()).unwrap_or(0);
-
+/// Flashes cascade within a single step until no octopus is left to flash, so an octopus's
+/// next energy depends on the whole grid rather than on its neighbours' current energy alone.
+/// That keeps this day off the cellular automaton engine.
+fn flash_adjacent(grid: &mut Octopuses) {
     loop {
-        let mut flash = false;
-
-        for y in 0..rows {
-            for x in 0..columns {
-                if grid[y][x] == ENERGY_FLASH {
-                    grid[y][x] = ENERGY_AFTER_FLASH;
-                    flash = true;
-
-                    let min_x = (x as isize - 1).max(0) as usize;
-                    let max_x = (x as isize + 1).min(columns as isize - 1) as usize;
-                    let min_y = (y as isize - 1).max(0) as usize;
-                    let max_y = (y as isize + 1).min(rows as isize - 1) as usize;
-
-                    for adjacent_y in min_y..max_y + 1 {
-                        for adjacent_x in min_x..max_x + 1 {
-                            let is_adjacent = adjacent_y != y || adjacent_x != x;
-                            let is_flash = grid[adjacent_y][adjacent_x] >= ENERGY_FLASH;
-
-                            if is_adjacent && !is_flash {
-                                grid[adjacent_y][adjacent_x] += 1;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let flashing: Vec<_> = grid.find_all(&ENERGY_FLASH).collect();
 
-        if !flash {
+        if flashing.is_empty() {
             break;
         }
+
+        for position in flashing {
+            grid[position] = ENERGY_AFTER_FLASH;
+
+            let adjacent: Vec<_> = grid.neighbors8(position).collect();
+
+            for adjacent in adjacent {
+                if grid[adjacent] < ENERGY_FLASH {
+                    grid[adjacent] += 1;
+                }
+            }
+        }
     }
 }
 
-fn count_flash(grid: &Grid) -> Flash {
-    grid.iter()
-        .flat_map(|row| row.iter().filter(|&&energy| energy >= ENERGY_FLASH))
+fn count_flash(grid: &Octopuses) -> Flash {
+    grid.values()
+        .filter(|&&energy| energy >= ENERGY_FLASH)
         .count()
 }
 
-fn reset_flash(grid: &mut Grid) {
-    grid.iter_mut()
-        .flat_map(|row| row.iter_mut().filter(|&&mut energy| energy >= ENERGY_FLASH))
+fn reset_flash(grid: &mut Octopuses) {
+    grid.values_mut()
+        .filter(|energy| **energy >= ENERGY_FLASH)
         .for_each(|energy| *energy = 0);
 }
 
-fn draw_grid(grid: &Grid) {
-    for row in grid {
-        for &energy in row {
-            if energy >= ENERGY_BEFORE_FLASH {
-                print!("{}", format!(" {} ", energy).black().on_bright_white());
-            } else {
-                print!("{}", format!(" {} ", energy).white().on_black());
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::Day11;
 
-        println!();
+    aoc_utils::examples! {
+        Day11,
+        example: "day11.txt" => part1 "1656", part2 "195";
     }
 }
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use std::collections::{HashMap, HashSet};
 
 type Node = String;
@@ -9,38 +9,52 @@ type Graph = HashMap<Node, NodeSet>;
 const NODE_START: &str = "start";
 const NODE_END: &str = "end";
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day12.txt", true).collect();
+pub struct Day12;
 
-    let graph = parse_graph(lines);
+impl Solution for Day12 {
+    type Input = Graph;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let paths_once = compute_paths(&graph, false);
-    let paths_twice = compute_paths(&graph, true);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", paths_once.len());
-    println!("{}", paths_twice.len());
+        parse_graph(&lines)
+    }
+
+    fn part1(graph: &Self::Input) -> Self::Part1 {
+        compute_paths(graph, false).len()
+    }
+
+    fn part2(graph: &Self::Input) -> Self::Part2 {
+        compute_paths(graph, true).len()
+    }
 }
 
-fn parse_graph(lines: Vec<String>) -> Graph {
-    let edges: Vec<Edge> = lines
-        .iter()
-        .flat_map(|line| parse_edge_data(line))
+fn parse_graph(lines: &[String]) -> ParseResult<Graph> {
+    let edges: Vec<Edge> = aoc_utils::numbered(lines)
+        .map(|line| parse_edge_data(&line))
+        .collect::<ParseResult<Vec<_>>>()?
+        .into_iter()
+        .flatten()
         .filter(|(left, right)| right != NODE_START && left != NODE_END)
         .collect();
 
-    parse_graph_data(&edges)
+    Ok(parse_graph_data(&edges))
 }
 
-fn parse_edge_data(line: &str) -> Vec<Edge> {
-    let mut parts = line.split("-");
+fn parse_edge_data(line: &Line) -> ParseResult<Vec<Edge>> {
+    let (left, right) = line
+        .split_once('-')
+        .ok_or_else(|| line.error_end("'-' between caves"))?;
 
-    let left = parts.next().expect("left").trim();
-    let right = parts.next().expect("right").trim();
+    let left = left.trim();
+    let right = right.trim();
 
-    vec![
+    Ok(vec![
         (left.to_string(), right.to_string()),
         (right.to_string(), left.to_string()),
-    ]
+    ])
 }
 
 fn parse_graph_data(edges: &[Edge]) -> Graph {
@@ -79,7 +93,7 @@ fn compute_paths(graph: &Graph, twice: bool) -> Vec<String> {
                     &neighbors.clone() - &previous
                 }
             })
-            .unwrap_or(NodeSet::new());
+            .unwrap_or_default();
 
         neighbors
             .into_iter()
@@ -102,3 +116,13 @@ fn compute_paths(graph: &Graph, twice: bool) -> Vec<String> {
 
     compute_loop(graph, NODE_START.to_string(), NodeSet::new(), twice)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day12;
+
+    aoc_utils::examples! {
+        Day12,
+        example: "day12.txt" => part1 "10", part2 "36";
+    }
+}
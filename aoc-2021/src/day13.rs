@@ -1,7 +1,8 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Solution};
 use colored::*;
 use itertools::Itertools;
 use regex::Regex;
+use std::fmt::{Display, Formatter, Result};
 
 type X = isize;
 type Y = isize;
@@ -12,47 +13,117 @@ type Value = isize;
 type Fold = (Axis, Value);
 
 #[derive(Debug, Clone)]
-struct Manual {
+pub struct Manual {
     dots: Vec<Dot>,
     folds: Vec<Fold>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day13.txt", true).collect();
+#[derive(Debug, Clone)]
+pub struct Paper {
+    dots: Vec<Dot>,
+}
+
+impl Display for Paper {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let min_x: X = self
+            .dots
+            .iter()
+            .map(|(x, _)| x)
+            .min()
+            .expect("min")
+            .to_owned();
+        let max_x: X = self
+            .dots
+            .iter()
+            .map(|(x, _)| x)
+            .max()
+            .expect("max")
+            .to_owned();
+        let min_y: Y = self
+            .dots
+            .iter()
+            .map(|(_, y)| y)
+            .min()
+            .expect("min")
+            .to_owned();
+        let max_y: Y = self
+            .dots
+            .iter()
+            .map(|(_, y)| y)
+            .max()
+            .expect("max")
+            .to_owned();
+
+        for y in min_y..max_y + 1 {
+            writeln!(f)?;
+
+            for x in min_x..max_x + 1 {
+                let dot = self.dots.contains(&(x, y));
+
+                if dot {
+                    write!(f, "{}", "██".bright_white())?;
+                } else {
+                    write!(f, "{}", "██".black())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Day13;
 
-    let manual = parse_manual(lines);
+impl Solution for Day13 {
+    type Input = Manual;
+    type Part1 = usize;
+    type Part2 = Paper;
 
-    let fold_count = apply_folds(&manual, 1);
-    let fold_all = apply_folds(&manual, usize::MAX);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", fold_count.len());
-    draw_paper(&fold_all);
+        parse_manual(&lines)
+    }
+
+    fn part1(manual: &Self::Input) -> Self::Part1 {
+        apply_folds(manual, 1).len()
+    }
+
+    fn part2(manual: &Self::Input) -> Self::Part2 {
+        let dots = apply_folds(manual, usize::MAX);
+
+        Paper { dots }
+    }
 }
 
-fn parse_manual(lines: Vec<String>) -> Manual {
+fn parse_manual(lines: &[String]) -> ParseResult<Manual> {
     let re_dot = Regex::new(r"(?P<x>\d+),(?P<y>\d+)").expect("regex dot");
     let re_fold = Regex::new(r"fold along (?P<axis>[xy])=(?P<value>\d+)").expect("regex fold");
 
     let mut dots = vec![];
     let mut folds = vec![];
 
-    for line in lines {
+    for line in aoc_utils::numbered(lines) {
         if line.contains("fold") {
-            let caps = re_fold.captures(&line).expect("captures");
+            let caps = re_fold
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "fold along <x|y>=<value>"))?;
             let axis = caps["axis"].trim().to_string().to_lowercase();
-            let value = caps["value"].trim().to_string().parse().expect("number");
+            let value = line.parse(&caps["value"], "number")?;
 
             folds.push((axis, value));
         } else {
-            let caps = re_dot.captures(&line).expect("captures");
-            let x = caps["x"].trim().to_string().parse().expect("number");
-            let y = caps["y"].trim().to_string().parse().expect("number");
+            let caps = re_dot
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "dot x,y"))?;
+            let x = line.parse(&caps["x"], "number")?;
+            let y = line.parse(&caps["y"], "number")?;
 
             dots.push((x, y));
         }
     }
 
-    Manual { dots, folds }
+    Ok(Manual { dots, folds })
 }
 
 fn apply_folds(manual: &Manual, count: usize) -> Vec<Dot> {
@@ -78,23 +149,12 @@ fn fold_dot(&(x, y): &Dot, (axis, value): &Fold) -> Dot {
     }
 }
 
-fn draw_paper(dots: &[Dot]) {
-    let min_x: X = dots.iter().map(|(x, _)| x).min().expect("min").to_owned();
-    let max_x: X = dots.iter().map(|(x, _)| x).max().expect("max").to_owned();
-    let min_y: Y = dots.iter().map(|(_, y)| y).min().expect("min").to_owned();
-    let max_y: Y = dots.iter().map(|(_, y)| y).max().expect("max").to_owned();
-
-    for y in min_y..max_y + 1 {
-        for x in min_x..max_x + 1 {
-            let dot = dots.contains(&(x, y));
-
-            if dot {
-                print!("{}", "██".bright_white());
-            } else {
-                print!("{}", "██".black());
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::Day13;
 
-        println!();
+    aoc_utils::examples! {
+        Day13,
+        example: "day13.txt" => part1 "17";
     }
 }
@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashMap;
@@ -15,46 +15,69 @@ type PairMap = HashMap<ElementPair, Count>;
 type ElementMap = HashMap<Element, Count>;
 
 #[derive(Debug, Clone)]
-struct Manual {
+pub struct Manual {
     template: Polymer,
     rules: RuleMap,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day14.txt", true).collect();
+pub struct Day14;
 
-    let manual = parse_manual(lines);
+impl Solution for Day14 {
+    type Input = Manual;
+    type Part1 = Count;
+    type Part2 = Count;
 
-    let pairs_10 = find_formula_pairs(&manual, 10);
-    let pairs_40 = find_formula_pairs(&manual, 40);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let ((_, most_10), (_, least_10)) = find_most_least_common(&manual, &pairs_10);
-    let ((_, most_40), (_, least_40)) = find_most_least_common(&manual, &pairs_40);
+        parse_manual(&lines)
+    }
+
+    fn part1(manual: &Self::Input) -> Self::Part1 {
+        let pairs_10 = find_formula_pairs(manual, 10);
+
+        let ((_, most_10), (_, least_10)) = find_most_least_common(manual, &pairs_10);
+
+        most_10 - least_10
+    }
 
-    println!("{}", most_10 - least_10);
-    println!("{}", most_40 - least_40);
+    fn part2(manual: &Self::Input) -> Self::Part2 {
+        let pairs_40 = find_formula_pairs(manual, 40);
+
+        let ((_, most_40), (_, least_40)) = find_most_least_common(manual, &pairs_40);
+
+        most_40 - least_40
+    }
 }
 
-fn parse_manual(lines: Vec<String>) -> Manual {
+fn parse_manual(lines: &[String]) -> ParseResult<Manual> {
     let re = Regex::new(r"(?P<left>\w)(?P<right>\w) -> (?P<element>\w)").expect("regex");
 
-    let template = lines.first().expect("template").trim().to_uppercase();
+    let mut lines = aoc_utils::numbered(lines);
+
+    let template = lines.next().unwrap_or(Line::new(1, ""));
+
+    if template.trim().is_empty() {
+        return Err(template.error_end("polymer template"));
+    }
+
+    let template = template.trim().to_uppercase();
 
     let rules = lines
-        .iter()
-        .skip(1)
         .map(|line| {
-            let caps = re.captures(&line).expect("captures");
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "pair insertion AB -> C"))?;
 
             let left = caps["left"].trim().to_uppercase();
             let right = caps["right"].trim().to_uppercase();
             let element = caps["element"].trim().to_uppercase();
 
-            ((left, right), element)
+            Ok(((left, right), element))
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    Manual { template, rules }
+    Ok(Manual { template, rules })
 }
 
 fn find_formula_pairs(manual: &Manual, steps: usize) -> PairMap {
@@ -121,3 +144,13 @@ fn get_element_map(polymer: &Polymer, pairs: &PairMap) -> ElementMap {
 
     elements
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day14;
+
+    aoc_utils::examples! {
+        Day14,
+        example: "day14.txt" => part1 "1588", part2 "2188189693529";
+    }
+}
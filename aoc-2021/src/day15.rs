@@ -1,156 +1,78 @@
-use aoc_utils;
-use rayon::prelude::*;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use aoc_utils::{Grid, Input, ParseResult, Position, Solution};
 
 type Cost = usize;
-type Grid = Vec<Vec<Cost>>;
+type Cavern = Grid<Cost>;
 
-type Axis = isize;
-type X = Axis;
-type Y = Axis;
-type Position = (X, Y);
-
-const COST_MAX: Cost = Cost::MAX;
 const COST_WRAP: Cost = 9;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct State {
-    position: Position,
-    cost: Cost,
-}
+pub struct Day15;
 
-impl State {
-    fn new(position: Position, cost: Cost) -> Self {
-        Self { position, cost }
-    }
-}
+impl Solution for Day15 {
+    type Input = Cavern;
+    type Part1 = Cost;
+    type Part2 = Cost;
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| other.position.cmp(&self.position))
-    }
-}
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+        parse_grid(&lines)
     }
-}
-
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day15.txt", true).collect();
-
-    let grid = parse_grid(lines);
 
-    let expanded_grid = expand_grid(&grid, 5);
+    fn part1(grid: &Self::Input) -> Self::Part1 {
+        find_lowest_total_cost(grid).expect("cost")
+    }
 
-    let cost = find_lowest_total_cost(&grid).expect("cost");
-    let cost_expanded = find_lowest_total_cost(&expanded_grid).expect("cost expanded");
+    fn part2(grid: &Self::Input) -> Self::Part2 {
+        let expanded_grid = expand_grid(grid, 5);
 
-    println!("{}", cost);
-    println!("{}", cost_expanded);
+        find_lowest_total_cost(&expanded_grid).expect("cost expanded")
+    }
 }
 
-fn parse_grid(lines: Vec<String>) -> Grid {
-    lines
-        .par_iter()
-        .map(|line| {
-            line.split("")
-                .map(|value| value.trim())
-                .filter(|value| !value.is_empty())
-                .map(|value| value.parse().expect("cost"))
-                .collect()
-        })
-        .collect()
+fn parse_grid(lines: &[String]) -> ParseResult<Cavern> {
+    Grid::parse_digits(aoc_utils::numbered(lines))
 }
 
-fn expand_grid(grid: &Grid, count: usize) -> Grid {
-    let rows = grid.len();
-    let columns = grid.first().map(|row| row.len()).unwrap_or(0);
-
-    (0..rows * count)
-        .map(|row_index| {
-            (0..columns * count)
-                .map(|column_index| {
-                    let original_row = row_index % rows;
-                    let original_column = column_index % columns;
-                    let original_cost = grid[original_row][original_column];
-
-                    let expanded_row = row_index / rows;
-                    let expanded_column = column_index / columns;
-
-                    (original_cost + expanded_row + expanded_column - 1) % COST_WRAP + 1
-                })
-                .collect()
-        })
-        .collect()
-}
+fn expand_grid(grid: &Cavern, count: usize) -> Cavern {
+    let rows = grid.height();
+    let columns = grid.width();
 
-fn find_lowest_total_cost(grid: &Grid) -> Option<Cost> {
-    let rows = grid.len();
-    let columns = grid.first().map(|row| row.len()).unwrap_or(0);
+    Grid::from_fn(
+        columns * count,
+        rows * count,
+        |(column_index, row_index)| {
+            let original_cost = grid[(column_index % columns, row_index % rows)];
 
-    let start = (0, 0);
-    let goal = (columns as Axis - 1, rows as Axis - 1);
+            let expanded_row = row_index / rows;
+            let expanded_column = column_index / columns;
 
-    dijkstra_search(&grid, start, goal)
+            (original_cost + expanded_row + expanded_column - 1) % COST_WRAP + 1
+        },
+    )
 }
 
-fn dijkstra_search(grid: &Grid, start: Position, goal: Position) -> Option<Cost> {
-    let mut heap: BinaryHeap<State> = BinaryHeap::new();
-    let mut distance: HashMap<Position, Cost> = HashMap::new();
-
-    heap.push(State::new(start, 0));
-    distance.insert(start, 0);
-
-    while let Some(State { position, cost }) = heap.pop() {
-        if position == goal {
-            return Some(cost);
-        }
-
-        for next in get_neighbors(grid, position) {
-            let new_next_cost = cost + get_cost(grid, next);
+fn find_lowest_total_cost(grid: &Cavern) -> Option<Cost> {
+    let start = (0, 0);
+    let goal = (grid.width().checked_sub(1)?, grid.height().checked_sub(1)?);
 
-            let next_cost = distance.get(&next).map(|&cost| cost).unwrap_or(COST_MAX);
+    dijkstra_search(grid, start, goal)
+}
 
-            if new_next_cost < next_cost {
-                heap.push(State::new(next, new_next_cost));
-                distance.insert(next, new_next_cost);
-            }
-        }
-    }
+fn dijkstra_search(grid: &Cavern, start: Position, goal: Position) -> Option<Cost> {
+    let successors = |&position: &Position| {
+        grid.neighbors4(position)
+            .map(move |next| (next, grid[next]))
+    };
 
-    None
+    aoc_utils::dijkstra([start], successors, |&position| position == goal).map(|result| result.cost)
 }
 
-fn get_neighbors(grid: &Grid, (current_x, current_y): Position) -> Vec<Position> {
-    let rows = grid.len();
-    let columns = grid.first().map(|row| row.len()).unwrap_or(0);
-
-    vec![(0, -1), (0, 1), (-1, 0), (1, 0)]
-        .into_iter()
-        .map(|(direction_x, direction_y)| {
-            let x = current_x + direction_x;
-            let y = current_y + direction_y;
-
-            (x, y)
-        })
-        .filter(|&(neighbor_x, neighbor_y)| {
-            let valid_x = neighbor_x >= 0 && neighbor_x < columns as Axis;
-            let valid_y = neighbor_y >= 0 && neighbor_y < rows as Axis;
-
-            valid_x && valid_y
-        })
-        .collect()
-}
+#[cfg(test)]
+mod tests {
+    use super::Day15;
 
-fn get_cost(grid: &Grid, (x, y): Position) -> Cost {
-    grid.get(y as usize)
-        .map(|row| row.get(x as usize).map(|&cost| cost))
-        .flatten()
-        .unwrap_or(COST_MAX)
+    aoc_utils::examples! {
+        Day15,
+        example: "day15.txt" => part1 "40", part2 "315";
+    }
 }
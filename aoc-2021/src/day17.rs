@@ -1,54 +1,67 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution, Vec2};
 use regex::Regex;
 
 type Value = i64;
 
-#[derive(Debug, Copy, Clone)]
-struct Point {
-    x: Value,
-    y: Value,
-}
+type Point = Vec2<Value>;
 
 #[derive(Debug, Copy, Clone)]
-struct Area {
+pub struct Area {
     left: Value,
     top: Value,
     right: Value,
     bottom: Value,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day17.txt", true).collect();
+pub struct Day17;
 
-    let area = parse_area(lines);
+impl Solution for Day17 {
+    type Input = Area;
+    type Part1 = Value;
+    type Part2 = usize;
 
-    let heights = solve_height_trajectories(&area);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_area(&lines)
+    }
 
-    let height = heights.iter().max().expect("height");
-    let count = heights.len();
+    fn part1(area: &Self::Input) -> Self::Part1 {
+        let heights = solve_height_trajectories(area);
 
-    println!("{}", height);
-    println!("{}", count);
+        *heights.iter().max().expect("height")
+    }
+
+    fn part2(area: &Self::Input) -> Self::Part2 {
+        solve_height_trajectories(area).len()
+    }
 }
 
-fn parse_area(lines: Vec<String>) -> Area {
+fn parse_area(lines: &[String]) -> ParseResult<Area> {
     let re = r"target area: x=(?P<left>.+)[.]{2}(?P<right>.+), y=(?P<bottom>.+)[.]{2}(?P<top>.+)";
     let re = Regex::new(re).expect("regex");
 
-    let line = lines.iter().next().expect("line");
-    let caps = re.captures(&line).expect("captures");
-
-    let left = caps["left"].trim().parse().expect("left");
-    let top = caps["top"].trim().parse().expect("top");
-    let right = caps["right"].trim().parse().expect("right");
-    let bottom = caps["bottom"].trim().parse().expect("bottom");
-
-    Area {
+    let line = aoc_utils::numbered(lines)
+        .next()
+        .unwrap_or(Line::new(1, ""));
+    let caps = re.captures(line.text).ok_or_else(|| {
+        line.error(
+            line.text,
+            "target area: x=<left>..<right>, y=<bottom>..<top>",
+        )
+    })?;
+
+    let left = line.parse(&caps["left"], "left")?;
+    let top = line.parse(&caps["top"], "top")?;
+    let right = line.parse(&caps["right"], "right")?;
+    let bottom = line.parse(&caps["bottom"], "bottom")?;
+
+    Ok(Area {
         left,
         top,
         right,
         bottom,
-    }
+    })
 }
 
 fn solve_height_trajectories(area: &Area) -> Vec<Value> {
@@ -69,29 +82,25 @@ fn get_velocities(area: &Area) -> Vec<Point> {
     (min_velocity_x..=max_velocity_x)
         .flat_map(|velocity_x| {
             (min_velocity_y..=max_velocity_y)
-                .map(|velocity_y| Point {
-                    x: velocity_x,
-                    y: velocity_y,
-                })
+                .map(|velocity_y| Point::new(velocity_x, velocity_y))
                 .collect::<Vec<Point>>()
         })
         .collect()
 }
 
 fn solve_height_trajectory(initial_velocity: &Point, area: &Area) -> Option<Value> {
-    let mut position = Point { x: 0, y: 0 };
-    let mut velocity = initial_velocity.clone();
+    let mut position = Point::default();
+    let mut velocity = *initial_velocity;
     let mut height = Value::MIN;
 
-    let drag = 1;
-    let gravity = -1;
+    let drag = Point::new(1, 0);
+    let gravity = Point::new(0, -1);
 
     loop {
-        position.x += velocity.x;
-        position.y += velocity.y;
+        position += velocity;
 
-        velocity.x += velocity.x.signum() * -drag;
-        velocity.y += gravity;
+        velocity -= drag * velocity.x.signum();
+        velocity += gravity;
 
         height = height.max(position.y);
 
@@ -109,3 +118,13 @@ fn solve_height_trajectory(initial_velocity: &Point, area: &Area) -> Option<Valu
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day17;
+
+    aoc_utils::examples! {
+        Day17,
+        example: "day17.txt" => part1 "45", part2 "112";
+    }
+}
@@ -1,10 +1,10 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use itertools::Itertools;
 
 type Value = u64;
 
 #[derive(Debug, Copy, Clone)]
-enum Token {
+pub enum Token {
     LeftBracket,
     Number(Value),
     RightBracket,
@@ -15,43 +15,69 @@ type TokenVec = Vec<Token>;
 const THRESHOLD_EXPLODE: Value = 4;
 const THRESHOLD_SPLIT: Value = 10;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day18.txt", true).collect();
+pub struct Day18;
 
-    let list = parse_list(lines);
+impl Solution for Day18 {
+    type Input = Vec<TokenVec>;
+    type Part1 = Value;
+    type Part2 = Value;
 
-    let all = compute_assignment_all(&list);
-    let permutations = compute_assignment_permutations(&list);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", all);
-    println!("{}", permutations);
+        parse_list(&lines)
+    }
+
+    fn part1(list: &Self::Input) -> Self::Part1 {
+        compute_assignment_all(list)
+    }
+
+    fn part2(list: &Self::Input) -> Self::Part2 {
+        compute_assignment_permutations(list)
+    }
 }
 
-fn parse_list(lines: Vec<String>) -> Vec<TokenVec> {
-    lines.iter().map(|line| parse_token_vec(line)).collect()
+fn parse_list(lines: &[String]) -> ParseResult<Vec<TokenVec>> {
+    aoc_utils::numbered(lines)
+        .map(|line| parse_token_vec(&line))
+        .collect()
 }
 
-fn parse_token_vec(line: &str) -> TokenVec {
+fn parse_token_vec(line: &Line) -> ParseResult<TokenVec> {
     let mut token_vec = vec![];
+    let mut depth = 0;
 
-    for character in line.chars() {
+    for (offset, character) in line.char_indices() {
         match character {
-            '[' => token_vec.push(Token::LeftBracket),
-            ']' => token_vec.push(Token::RightBracket),
+            '[' => {
+                depth += 1;
+
+                token_vec.push(Token::LeftBracket);
+            }
+            ']' if depth > 0 => {
+                depth -= 1;
+
+                token_vec.push(Token::RightBracket);
+            }
+            ']' => return Err(line.error_at(offset, "matching '['")),
             ',' => (),
             _ => {
-                let number = character.to_string().parse().expect("number");
+                let number = line.parse(&line[offset..offset + 1], "digit, bracket or comma")?;
 
                 token_vec.push(Token::Number(number));
             }
         }
     }
 
-    token_vec
+    if depth > 0 {
+        return Err(line.error_end("']'"));
+    }
+
+    Ok(token_vec)
 }
 
 fn compute_assignment_all(list: &[TokenVec]) -> Value {
-    let list: Vec<TokenVec> = list.iter().map(|item| item.clone()).collect();
+    let list: Vec<TokenVec> = list.to_vec();
 
     let total = list
         .into_iter()
@@ -76,7 +102,7 @@ fn compute_assignment_permutations(list: &[TokenVec]) -> Value {
         .expect("max")
 }
 
-fn add_token_vec(left: &TokenVec, right: &TokenVec) -> TokenVec {
+fn add_token_vec(left: &[Token], right: &[Token]) -> TokenVec {
     let mut token_vec = vec![];
 
     token_vec.push(Token::LeftBracket);
@@ -87,8 +113,8 @@ fn add_token_vec(left: &TokenVec, right: &TokenVec) -> TokenVec {
     reduce_token_vec(&token_vec)
 }
 
-fn reduce_token_vec(token_vec: &TokenVec) -> TokenVec {
-    let mut token_vec = token_vec.clone();
+fn reduce_token_vec(token_vec: &[Token]) -> TokenVec {
+    let mut token_vec = token_vec.to_vec();
 
     loop {
         if let Some(next) = try_explode(&token_vec) {
@@ -105,9 +131,9 @@ fn reduce_token_vec(token_vec: &TokenVec) -> TokenVec {
     token_vec
 }
 
-fn try_explode(token_vec: &TokenVec) -> Option<TokenVec> {
+fn try_explode(token_vec: &[Token]) -> Option<TokenVec> {
     try_explode_index(token_vec).map(|(index, left, right)| {
-        let mut token_vec = token_vec.clone();
+        let mut token_vec = token_vec.to_vec();
 
         token_vec.remove(index);
         token_vec.remove(index);
@@ -123,9 +149,9 @@ fn try_explode(token_vec: &TokenVec) -> Option<TokenVec> {
             }
         }
 
-        for next_index in index + 1..token_vec.len() {
-            if let Token::Number(next) = token_vec[next_index] {
-                token_vec[next_index] = Token::Number(next + right);
+        for token in token_vec.iter_mut().skip(index + 1) {
+            if let Token::Number(next) = *token {
+                *token = Token::Number(next + right);
                 break;
             }
         }
@@ -134,7 +160,7 @@ fn try_explode(token_vec: &TokenVec) -> Option<TokenVec> {
     })
 }
 
-fn try_explode_index(token_vec: &TokenVec) -> Option<(usize, Value, Value)> {
+fn try_explode_index(token_vec: &[Token]) -> Option<(usize, Value, Value)> {
     let mut depth = 0;
 
     for (index, token) in token_vec.iter().enumerate() {
@@ -142,7 +168,7 @@ fn try_explode_index(token_vec: &TokenVec) -> Option<(usize, Value, Value)> {
             Token::LeftBracket => depth += 1,
             Token::RightBracket => depth -= 1,
             &Token::Number(left) => {
-                if depth >= THRESHOLD_EXPLODE + 1 {
+                if depth > THRESHOLD_EXPLODE {
                     if let Token::Number(right) = token_vec[index + 1] {
                         return Some((index - 1, left, right));
                     }
@@ -154,9 +180,9 @@ fn try_explode_index(token_vec: &TokenVec) -> Option<(usize, Value, Value)> {
     None
 }
 
-fn try_split(token_vec: &TokenVec) -> Option<TokenVec> {
+fn try_split(token_vec: &[Token]) -> Option<TokenVec> {
     try_split_index(token_vec).map(|(index, number)| {
-        let mut token_vec = token_vec.clone();
+        let mut token_vec = token_vec.to_vec();
 
         token_vec.remove(index + 1);
 
@@ -169,7 +195,7 @@ fn try_split(token_vec: &TokenVec) -> Option<TokenVec> {
     })
 }
 
-fn try_split_index(token_vec: &TokenVec) -> Option<(usize, Value)> {
+fn try_split_index(token_vec: &[Token]) -> Option<(usize, Value)> {
     for (index, token) in token_vec.iter().enumerate() {
         if let &Token::Number(number) = token {
             if number >= THRESHOLD_SPLIT {
@@ -181,7 +207,7 @@ fn try_split_index(token_vec: &TokenVec) -> Option<(usize, Value)> {
     None
 }
 
-fn compute_magnitude(token_vec: &TokenVec) -> Value {
+fn compute_magnitude(token_vec: &[Token]) -> Value {
     fn compute(it: &mut dyn Iterator<Item = &Token>) -> Value {
         let mut numbers = vec![];
 
@@ -206,3 +232,41 @@ fn compute_magnitude(token_vec: &TokenVec) -> Value {
 
     compute(&mut token_vec.iter())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::examples! {
+        Day18,
+        example: "day18.txt" => part1 "4140", part2 "3993";
+    }
+
+    fn token_vec(text: &str) -> TokenVec {
+        parse_token_vec(&Line::new(1, text)).expect("token vec")
+    }
+
+    fn assert_reduces_to(reduced: &[Token], expected: &str) {
+        assert_eq!(
+            format!("{:?}", reduced),
+            format!("{:?}", token_vec(expected))
+        );
+    }
+
+    #[test]
+    fn reduce_explodes_leftmost_pair() {
+        let reduced = reduce_token_vec(&token_vec("[[[[[9,8],1],2],3],4]"));
+
+        assert_reduces_to(&reduced, "[[[[0,9],2],3],4]");
+    }
+
+    #[test]
+    fn reduce_after_addition() {
+        let sum = add_token_vec(
+            &token_vec("[[[[4,3],4],4],[7,[[8,4],9]]]"),
+            &token_vec("[1,1]"),
+        );
+
+        assert_reduces_to(&sum, "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+}
@@ -1,97 +1,32 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseResult, Rotation, Solution, Vec3};
 use itertools::Itertools;
 use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashSet;
 
 type Value = i64;
-type Rotation = Box<dyn Fn(&Point) -> Point>;
+type Point = Vec3<Value>;
 
 const SCANNER_MATCH: usize = 12;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct Point {
-    x: Value,
-    y: Value,
-    z: Value,
-}
-
-impl Point {
-    fn new(x: Value, y: Value, z: Value) -> Self {
-        Self { x, y, z }
-    }
-
-    fn add(&self, other: &Self) -> Self {
-        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
-    }
-
-    fn sub(&self, other: &Self) -> Self {
-        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
-    }
-
-    fn distance(&self, other: &Self) -> Value {
-        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
-    }
-
-    fn rotate(&self, rotation: &Rotation) -> Point {
-        rotation(self)
-    }
-
-    fn rotations() -> Vec<Rotation> {
-        vec![
-            Box::new(|&Point { x, y, z }| Point::new(x, y, z)),
-            Box::new(|&Point { x, y, z }| Point::new(x, -y, -z)),
-            Box::new(|&Point { x, y, z }| Point::new(x, z, -y)),
-            Box::new(|&Point { x, y, z }| Point::new(x, -z, y)),
-            Box::new(|&Point { x, y, z }| Point::new(-x, -z, -y)),
-            Box::new(|&Point { x, y, z }| Point::new(-x, z, y)),
-            Box::new(|&Point { x, y, z }| Point::new(-x, -y, z)),
-            Box::new(|&Point { x, y, z }| Point::new(-x, y, -z)),
-            Box::new(|&Point { x, y, z }| Point::new(y, z, x)),
-            Box::new(|&Point { x, y, z }| Point::new(y, -z, -x)),
-            Box::new(|&Point { x, y, z }| Point::new(y, x, -z)),
-            Box::new(|&Point { x, y, z }| Point::new(y, -x, z)),
-            Box::new(|&Point { x, y, z }| Point::new(-y, -x, -z)),
-            Box::new(|&Point { x, y, z }| Point::new(-y, x, z)),
-            Box::new(|&Point { x, y, z }| Point::new(-y, -z, x)),
-            Box::new(|&Point { x, y, z }| Point::new(-y, z, -x)),
-            Box::new(|&Point { x, y, z }| Point::new(z, x, y)),
-            Box::new(|&Point { x, y, z }| Point::new(z, -x, -y)),
-            Box::new(|&Point { x, y, z }| Point::new(z, y, -x)),
-            Box::new(|&Point { x, y, z }| Point::new(z, -y, x)),
-            Box::new(|&Point { x, y, z }| Point::new(-z, -y, -x)),
-            Box::new(|&Point { x, y, z }| Point::new(-z, y, x)),
-            Box::new(|&Point { x, y, z }| Point::new(-z, -x, y)),
-            Box::new(|&Point { x, y, z }| Point::new(-z, x, -y)),
-        ]
-    }
-}
-
 #[derive(Debug, Clone)]
 struct ScannerMatch {
     id: String,
     position: Point,
     distances: Vec<Distance>,
     global_beacons: Vec<Point>,
-    local_beacons: Vec<Point>,
 }
 
 impl ScannerMatch {
     fn from_scanner(scanner: &Scanner, position: &Point) -> ScannerMatch {
         let id = scanner.id.clone();
-        let position = position.clone();
+        let position = *position;
         let distances = scanner.distances.clone();
 
         let global_beacons = scanner
             .beacons
             .iter()
-            .map(|beacon| position.add(beacon))
-            .collect();
-
-        let local_beacons = scanner
-            .beacons
-            .iter()
-            .map(|beacon| beacon.clone())
+            .map(|&beacon| position + beacon)
             .collect();
 
         Self {
@@ -99,17 +34,16 @@ impl ScannerMatch {
             position,
             distances,
             global_beacons,
-            local_beacons,
         }
     }
 }
 
 #[derive(Debug, Clone)]
-struct Scanner {
+pub struct Scanner {
     id: String,
     beacons: Vec<Point>,
     distances: Vec<Distance>,
-    rotations: Vec<Box<Scanner>>,
+    rotations: Vec<Scanner>,
 }
 
 impl Scanner {
@@ -130,24 +64,21 @@ impl Scanner {
         }
     }
 
-    fn from_rotations(id: &str, beacons: &[Point]) -> Vec<Box<Scanner>> {
-        Point::rotations()
-            .iter()
-            .map(|rotation| Self::from_rotation(id, beacons, rotation))
+    fn from_rotations(id: &str, beacons: &[Point]) -> Vec<Scanner> {
+        Rotation::all()
+            .map(|rotation| Self::from_rotation(id, beacons, &rotation))
             .collect()
     }
 
-    fn from_rotation(id: &str, beacons: &[Point], rotation: &Rotation) -> Box<Scanner> {
+    fn from_rotation(id: &str, beacons: &[Point], rotation: &Rotation) -> Scanner {
         let id = id.to_string();
 
         let beacons = beacons
             .iter()
-            .map(|beacon| beacon.rotate(rotation))
+            .map(|&beacon| rotation.apply(beacon))
             .collect();
 
-        let scanner = Scanner::new(id, beacons, false);
-
-        Box::new(scanner)
+        Scanner::new(id, beacons, false)
     }
 }
 
@@ -170,35 +101,47 @@ impl Distance {
     }
 
     fn from_origin_beacons(&origin: &Point, beacons: &[Point]) -> Self {
-        let vectors = beacons.iter().map(|beacon| beacon.sub(&origin)).collect();
+        let vectors = beacons.iter().map(|&beacon| beacon - origin).collect();
 
         Self::new(origin, vectors)
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day19.txt", true).collect();
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Scanner>;
+    type Part1 = usize;
+    type Part2 = Value;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_scanners(&lines)
+    }
 
-    let scanners = parse_scanners(lines);
+    fn part1(scanners: &Self::Input) -> Self::Part1 {
+        let (position_beacons, _) = scanner_match(scanners);
 
-    let (position_beacons, position_scanners) = scanner_match(&scanners);
+        position_beacons.len()
+    }
 
-    let distance_scanners = find_largest_distance(&position_scanners);
+    fn part2(scanners: &Self::Input) -> Self::Part2 {
+        let (_, position_scanners) = scanner_match(scanners);
 
-    println!("{}", position_beacons.len());
-    println!("{}", distance_scanners);
+        find_largest_distance(&position_scanners)
+    }
 }
 
-fn parse_scanners(lines: Vec<String>) -> Vec<Scanner> {
+fn parse_scanners(lines: &[String]) -> ParseResult<Vec<Scanner>> {
     let re_id = Regex::new(r"--- scanner (?P<id>.+) ---").expect("regex id");
-    let re_point = Regex::new(r"(?P<x>.+),(?P<y>.+),(?P<z>.+)").expect("regex point");
 
     let mut scanners = vec![];
 
     let mut current_id = String::from("");
     let mut current_beacons = vec![];
 
-    for line in lines {
+    for line in aoc_utils::numbered(lines) {
         if line.contains("scanner") {
             if !current_beacons.is_empty() {
                 let scanner = Scanner::new(current_id, current_beacons, true);
@@ -206,18 +149,15 @@ fn parse_scanners(lines: Vec<String>) -> Vec<Scanner> {
                 scanners.push(scanner);
             }
 
-            let caps = re_id.captures(&line).expect("captures");
+            let caps = re_id
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "--- scanner <id> ---"))?;
             let id = caps["id"].trim().to_string();
 
             current_id = id;
             current_beacons = vec![];
         } else {
-            let caps = re_point.captures(&line).expect("captures");
-            let x = caps["x"].trim().to_string().parse().expect("number");
-            let y = caps["y"].trim().to_string().parse().expect("number");
-            let z = caps["z"].trim().to_string().parse().expect("number");
-
-            let beacon = Point::new(x, y, z);
+            let beacon = line.parse(line.text, "beacon x,y,z")?;
 
             current_beacons.push(beacon);
         }
@@ -229,11 +169,11 @@ fn parse_scanners(lines: Vec<String>) -> Vec<Scanner> {
         scanners.push(scanner);
     }
 
-    scanners
+    Ok(scanners)
 }
 
 fn scanner_match(scanners: &[Scanner]) -> (HashSet<Point>, HashSet<Point>) {
-    let mut scanners: Vec<Scanner> = scanners.iter().map(|scanner| scanner.clone()).collect();
+    let mut scanners: Vec<Scanner> = scanners.to_vec();
 
     let mut position_beacons: HashSet<Point> = HashSet::new();
     let mut position_scanners: HashSet<Point> = HashSet::new();
@@ -244,7 +184,7 @@ fn scanner_match(scanners: &[Scanner]) -> (HashSet<Point>, HashSet<Point>) {
     let scanner_match = ScannerMatch::from_scanner(&scanner, &position);
 
     position_beacons.extend(scanner_match.global_beacons.iter());
-    position_scanners.insert(scanner_match.position.clone());
+    position_scanners.insert(scanner_match.position);
     scanner_matches.push(scanner_match);
 
     while !scanners.is_empty() {
@@ -260,7 +200,7 @@ fn scanner_match(scanners: &[Scanner]) -> (HashSet<Point>, HashSet<Point>) {
         scanners.retain(|scanner| scanner.id != scanner_match.id);
 
         position_beacons.extend(scanner_match.global_beacons.iter());
-        position_scanners.insert(scanner_match.position.clone());
+        position_scanners.insert(scanner_match.position);
         scanner_matches.push(scanner_match);
     }
 
@@ -278,8 +218,8 @@ fn try_scanner_match(left_scanner: &ScannerMatch, right_scanner: &Scanner) -> Op
                     .count();
 
                 if match_count >= SCANNER_MATCH {
-                    let right_offset = left_distance.origin.sub(&right_distance.origin);
-                    let right_position = left_scanner.position.add(&right_offset);
+                    let right_offset = left_distance.origin - right_distance.origin;
+                    let right_position = left_scanner.position + right_offset;
 
                     let scanner_match = ScannerMatch::from_scanner(right_scanner, &right_position);
 
@@ -296,7 +236,17 @@ fn find_largest_distance(positions: &HashSet<Point>) -> Value {
     positions
         .iter()
         .permutations(2)
-        .map(|pair| pair[0].distance(pair[1]))
+        .map(|pair| pair[0].manhattan(*pair[1]))
         .max()
         .expect("max")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day19;
+
+    aoc_utils::examples! {
+        Day19,
+        example: "day19.txt" => part1 "79", part2 "3621";
+    }
+}
@@ -1,131 +1,110 @@
-use aoc_utils;
-use itertools::Itertools;
-use std::collections::HashMap;
+use aoc_utils::{Input, Line, ParseResult, Solution, Sparse, Square8, Vec2};
 
 type Pixel = bool;
 
-type Value = i64;
-type X = Value;
-type Y = Value;
-type Point = (X, Y);
+type Point = Vec2<i64>;
 
-type Grid = HashMap<Point, Pixel>;
+type Image = Sparse<Square8>;
 type Algorithm = Vec<Pixel>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day20.txt", true).collect();
+pub struct Day20;
 
-    let (algorithm, grid) = parse_enhancement_algorithm(lines);
+impl Solution for Day20 {
+    type Input = (Algorithm, Image);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let grid_2 = simulate_steps(&algorithm, &grid, 2);
-    let grid_50 = simulate_steps(&algorithm, &grid, 50);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let count_2 = count_pixels(&grid_2);
-    let count_50 = count_pixels(&grid_50);
+        parse_enhancement_algorithm(&lines)
+    }
 
-    println!("{}", count_2);
-    println!("{}", count_50);
-}
+    fn part1((algorithm, image): &Self::Input) -> Self::Part1 {
+        count_pixels(algorithm, image, 2)
+    }
 
-fn parse_enhancement_algorithm(lines: Vec<String>) -> (Algorithm, Grid) {
-    let algorithm = lines[0].chars().map(|pixel| pixel == '#').collect();
-
-    let grid = lines[1..]
-        .iter()
-        .enumerate()
-        .flat_map(|(row_index, row)| {
-            row.chars()
-                .enumerate()
-                .map(move |(column_index, character)| {
-                    let point = (column_index as X, row_index as Y);
-                    let pixel = character == '#';
-
-                    (point, pixel)
-                })
-        })
-        .collect();
-
-    (algorithm, grid)
+    fn part2((algorithm, image): &Self::Input) -> Self::Part2 {
+        count_pixels(algorithm, image, 50)
+    }
 }
 
-fn simulate_steps(algorithm: &Algorithm, initial_grid: &Grid, steps: usize) -> Grid {
-    let mut grid = initial_grid.clone();
+fn parse_enhancement_algorithm(lines: &[String]) -> ParseResult<(Algorithm, Image)> {
+    for line in aoc_utils::numbered(lines) {
+        if let Some(offset) = line.find(|pixel| pixel != '#' && pixel != '.') {
+            return Err(line.error_at(offset, "'#' or '.'"));
+        }
+    }
 
-    let mut min_x = grid.keys().map(|&(x, _)| x).min().expect("min x") - 1;
-    let mut max_x = grid.keys().map(|&(x, _)| x).max().expect("max x") + 1;
-    let mut min_y = grid.keys().map(|&(_, y)| y).min().expect("min y") - 1;
-    let mut max_y = grid.keys().map(|&(_, y)| y).max().expect("max y") + 1;
+    let first = aoc_utils::numbered(lines)
+        .next()
+        .unwrap_or(Line::new(1, ""));
 
-    let first = algorithm.first().expect("first").clone();
-    let last = algorithm.last().expect("last").clone();
+    if first.len() != 512 {
+        return Err(first.error_end("algorithm of 512 pixels"));
+    }
 
-    let mut border = false;
+    // A lit dark window lights the infinite background, and a lit lit window keeps it lit.
+    if first.starts_with('#') && first.ends_with('#') {
+        return Err(first.error_at(511, "'.' as the last pixel when the first is '#'"));
+    }
 
-    for _ in 0..steps {
-        grid = next_state(&algorithm, &grid, border, (min_x, max_x, min_y, max_y));
+    let algorithm = first.chars().map(|pixel| pixel == '#').collect();
 
-        min_x -= 1;
-        max_x += 1;
-        min_y -= 1;
-        max_y += 1;
+    let lit = lines[1..].iter().enumerate().flat_map(|(row_index, row)| {
+        row.chars()
+            .enumerate()
+            .filter(|&(_, character)| character == '#')
+            .map(move |(column_index, _)| Point::new(column_index as i64, row_index as i64))
+    });
 
-        if !border && first {
-            border = first;
-        } else if border && !last {
-            border = last;
-        }
-    }
-
-    grid
+    Ok((algorithm, Image::new(Square8, lit)))
 }
 
-fn next_state(
-    algorithm: &Algorithm,
-    grid: &Grid,
-    border: Pixel,
-    (min_x, max_x, min_y, max_y): (X, X, Y, Y),
-) -> Grid {
-    (min_x..=max_x)
-        .into_iter()
-        .flat_map(|x| {
-            (min_y..=max_y).into_iter().map(move |y| {
-                let point = (x, y);
-                let pixel = get_algorithm_pixel(algorithm, grid, point, border);
-
-                (point, pixel)
-            })
-        })
-        .collect()
-}
+fn count_pixels(algorithm: &[Pixel], image: &Image, steps: usize) -> usize {
+    let mut image = image.clone();
 
-fn get_algorithm_pixel(algorithm: &Algorithm, grid: &Grid, (x, y): Point, border: Pixel) -> Pixel {
-    let binary = (y - 1..=y + 1)
-        .flat_map(|y| {
-            (x - 1..=x + 1).map(move |x| {
-                let point = (x, y);
-                let pixel = get_grid_pixel(grid, point, border);
+    image.run(
+        &|pixel, neighbors: &[Pixel]| enhance(algorithm, pixel, neighbors),
+        steps,
+    );
 
-                match pixel {
-                    true => 1,
-                    false => 0,
-                }
-            })
-        })
-        .join("");
+    // Parsing rules out a background that stays lit, and both parts take an even number of steps.
+    image.population().expect("finite lit pixels")
+}
 
-    let index = to_decimal(&binary);
+/// Looks the 3x3 window up in the algorithm, reading it row by row.
+fn enhance(algorithm: &[Pixel], pixel: Pixel, neighbors: &[Pixel]) -> Pixel {
+    let &[north, east, south, west, north_east, south_east, south_west, north_west] = neighbors
+    else {
+        return pixel;
+    };
+
+    let index = [
+        north_west, north, north_east, west, pixel, east, south_west, south, south_east,
+    ]
+    .into_iter()
+    .fold(0, |index, bit| index << 1 | bit as usize);
 
     algorithm[index]
 }
 
-fn get_grid_pixel(grid: &Grid, point: Point, border: Pixel) -> Pixel {
-    grid.get(&point).map(|&pixel| pixel).unwrap_or(border)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn to_decimal(binary: &str) -> usize {
-    usize::from_str_radix(binary, 2).expect("decimal")
-}
+    aoc_utils::examples! {
+        Day20,
+        example: "day20.txt" => part1 "35", part2 "3351";
+    }
 
-fn count_pixels(grid: &Grid) -> usize {
-    grid.values().filter(|&&pixel| pixel).count()
+    #[test]
+    fn rejects_background_that_stays_lit() {
+        let text = format!("#{}#\n\n#.\n", ".".repeat(510));
+        let error = Day20::parse(&Input::literal(&text)).expect_err("lit background");
+
+        assert!(error
+            .to_string()
+            .ends_with("1:512: expected '.' as the last pixel when the first is '#'"));
+    }
 }
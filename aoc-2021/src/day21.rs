@@ -1,4 +1,4 @@
-use aoc_utils;
+use aoc_utils::{Input, Line, ParseResult, Solution};
 
 type Precision = u128;
 
@@ -14,33 +14,46 @@ const DICE_ROLLS: Position = 3;
 const SCORE_DETERMINISTIC: Score = 1000;
 const SCORE_QUANTUM: Score = 21;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day21.txt", true).collect();
+pub struct Day21;
 
-    let (player1, player2) = parse_positions(lines);
+impl Solution for Day21 {
+    type Input = (Position, Position);
+    type Part1 = Precision;
+    type Part2 = Count;
 
-    let (count, score) = play_deterministic(player1, player2);
-    let (count1, count2) = play_quantum(player1, player2);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", count * score);
-    println!("{:?}", count1.max(count2));
+        parse_positions(&lines)
+    }
+
+    fn part1(&(player1, player2): &Self::Input) -> Self::Part1 {
+        let (count, score) = play_deterministic(player1, player2);
+
+        count * score
+    }
+
+    fn part2(&(player1, player2): &Self::Input) -> Self::Part2 {
+        let (count1, count2) = play_quantum(player1, player2);
+
+        count1.max(count2)
+    }
 }
 
-fn parse_positions(lines: Vec<String>) -> (Position, Position) {
-    fn read_position(line: Option<&String>) -> Position {
-        line.expect("line")
-            .chars()
-            .last()
-            .expect("last")
-            .to_string()
-            .parse()
-            .expect("number")
+fn parse_positions(lines: &[String]) -> ParseResult<(Position, Position)> {
+    fn read_position(line: Line) -> ParseResult<Position> {
+        match line.len() {
+            0 => Err(line.error_end("starting position")),
+            length => line.parse(&line[length - 1..], "starting position"),
+        }
     }
 
-    let player1 = read_position(lines.first());
-    let player2 = read_position(lines.last());
+    let mut lines = aoc_utils::numbered(lines);
+
+    let player1 = read_position(lines.next().unwrap_or(Line::new(1, "")))?;
+    let player2 = read_position(lines.next().unwrap_or(Line::new(2, "")))?;
 
-    (player1, player2)
+    Ok((player1, player2))
 }
 
 fn play_deterministic(start_current: Position, start_next: Position) -> (Count, Score) {
@@ -148,3 +161,13 @@ fn roll_quantum() -> Vec<(Roll, Count)> {
 fn wrap_position(position: Position) -> Position {
     (position - 1) % GAME_POSITIONS + 1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day21;
+
+    aoc_utils::examples! {
+        Day21,
+        example: "day21.txt" => part1 "739785", part2 "444356092776315";
+    }
+}
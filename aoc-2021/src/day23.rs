@@ -1,7 +1,6 @@
-use aoc_utils;
+use aoc_utils::{Input, ParseError, ParseResult, Solution};
 use itertools::Itertools;
-use std::cmp::Ordering;
-use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::collections::{BTreeSet, HashMap};
 
 type Unit = usize;
 type Cost = usize;
@@ -11,8 +10,16 @@ const DIAGRAM_A: char = 'A';
 const DIAGRAM_B: char = 'B';
 const DIAGRAM_C: char = 'C';
 const DIAGRAM_D: char = 'D';
+const DIAGRAM_AMPHIPOD: char = '?';
 
-const COST_MAX: Cost = Cost::MAX;
+/// The burrow every diagram must show, with `?` where an amphipod starts.
+const DIAGRAM: [&str; 5] = [
+    "#############",
+    "#...........#",
+    "###?#?#?#?###",
+    "  #?#?#?#?#",
+    "  #########",
+];
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 enum Amphipod {
@@ -63,75 +70,85 @@ impl Point {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
 struct Map {
     hallway: Vec<Point>,
     rooms: HashMap<Amphipod, Vec<Point>>,
 }
 
-impl Default for Map {
-    fn default() -> Self {
-        Self {
-            hallway: Vec::new(),
-            rooms: HashMap::new(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
 struct State {
     amphipods: BTreeSet<(Point, Amphipod)>,
 }
 
-impl Default for State {
-    fn default() -> Self {
-        Self {
-            amphipods: BTreeSet::new(),
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<String>;
+    type Part1 = Cost;
+    type Part2 = Cost;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        if lines.len() != DIAGRAM.len() {
+            return Err(ParseError::new(lines.len() + 1, 1, "diagram of 5 lines"));
         }
-    }
-}
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-struct SearchState {
-    state: State,
-    cost: Cost,
-}
+        let mut counts = HashMap::new();
 
-impl SearchState {
-    fn new(state: State, cost: Cost) -> Self {
-        Self { state, cost }
-    }
-}
+        for (line, expected) in aoc_utils::numbered(&lines).zip(DIAGRAM) {
+            let text = line.trim_end();
 
-impl Ord for SearchState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
+            for (offset, (tile, expected)) in text.chars().zip(expected.chars()).enumerate() {
+                match expected {
+                    DIAGRAM_AMPHIPOD => {
+                        let amphipod = Amphipod::try_from(tile)
+                            .map_err(|_| line.error_at(offset, "amphipod A to D"))?;
+                        let count = counts.entry(amphipod).or_insert(0);
+
+                        *count += 1;
+
+                        if *count > 2 {
+                            return Err(line.error_at(offset, "two amphipods of each kind"));
+                        }
+                    }
+                    _ if tile != expected => {
+                        return Err(line.error_at(offset, &format!("{:?}", expected)));
+                    }
+                    _ => {}
+                }
+            }
+
+            if text.len() < expected.len() {
+                return Err(line.error_at(text.len(), "the rest of the burrow"));
+            }
 
-impl PartialOrd for SearchState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+            if text.len() > expected.len() {
+                return Err(line.error_at(expected.len(), "end of line"));
+            }
+        }
+
+        Ok(lines)
     }
-}
 
-pub fn run() {
-    let lines_folded: Vec<String> = aoc_utils::read_lines("inputs/day23.txt", true).collect();
+    fn part1(lines_folded: &Self::Input) -> Self::Part1 {
+        let (map_folded, state_folded) = parse_diagram(lines_folded);
 
-    let lines_unfolded = unfold_diagram(&lines_folded);
+        dijkstra_search(&map_folded, &state_folded).expect("cost")
+    }
 
-    let (map_folded, state_folded) = parse_diagram(&lines_folded);
-    let (map_unfolded, state_unfolded) = parse_diagram(&lines_unfolded);
+    fn part2(lines_folded: &Self::Input) -> Self::Part2 {
+        let lines_unfolded = unfold_diagram(lines_folded);
 
-    let cost_folded = dijkstra_search(&map_folded, &state_folded);
-    let cost_unfolded = dijkstra_search(&map_unfolded, &state_unfolded);
+        let (map_unfolded, state_unfolded) = parse_diagram(&lines_unfolded);
 
-    println!("{}", cost_folded.expect("cost"));
-    println!("{}", cost_unfolded.expect("cost"));
+        dijkstra_search(&map_unfolded, &state_unfolded).expect("cost")
+    }
 }
 
 fn unfold_diagram(lines: &[String]) -> Vec<String> {
-    let mut new_lines: Vec<String> = lines.iter().map(|line| line.clone()).collect();
+    let mut new_lines: Vec<String> = lines.to_vec();
 
     new_lines.insert(3, "  #D#C#B#A#".to_string());
     new_lines.insert(4, "  #D#B#A#C#".to_string());
@@ -145,14 +162,14 @@ fn parse_diagram(lines: &[String]) -> (Map, State) {
 
     let mut cycle = Amphipod::all().into_iter().cycle();
 
-    for (row, line) in lines.iter().enumerate() {
-        for (column, tile) in line.chars().enumerate() {
+    for (row, line) in lines.iter().enumerate().skip(1) {
+        for (column, tile) in line.chars().enumerate().skip(1) {
             let point = Point::new(column - 1, row - 1);
 
             if tile == DIAGRAM_HALLWAY {
                 let down = lines[row + 1].chars().nth(column).expect("down");
 
-                if let Err(_) = Amphipod::try_from(down) {
+                if Amphipod::try_from(down).is_err() {
                     map.hallway.push(point);
                 }
             } else if let Ok(amphipod) = Amphipod::try_from(tile) {
@@ -169,30 +186,12 @@ fn parse_diagram(lines: &[String]) -> (Map, State) {
 }
 
 fn dijkstra_search(map: &Map, initial_state: &State) -> Option<Cost> {
-    let mut heap: BinaryHeap<SearchState> = BinaryHeap::new();
-    let mut distance: HashMap<State, Cost> = HashMap::new();
-
-    heap.push(SearchState::new(initial_state.clone(), 0));
-    distance.insert(initial_state.clone(), 0);
-
-    while let Some(SearchState { state, cost }) = heap.pop() {
-        if is_completed(&map, &state) {
-            return Some(cost);
-        }
-
-        for (next, next_cost_state) in get_next_states(&map, &state) {
-            let new_next_cost = cost + next_cost_state;
-
-            let next_cost = distance.get(&next).map(|&cost| cost).unwrap_or(COST_MAX);
-
-            if new_next_cost < next_cost {
-                heap.push(SearchState::new(next.clone(), new_next_cost));
-                distance.insert(next.clone(), new_next_cost);
-            }
-        }
-    }
-
-    None
+    aoc_utils::dijkstra(
+        [initial_state.clone()],
+        |state| get_next_states(map, state),
+        |state| is_completed(map, state),
+    )
+    .map(|result| result.cost)
 }
 
 fn is_completed(map: &Map, state: &State) -> bool {
@@ -223,8 +222,7 @@ fn get_amphipod_next_states(
         let next_room = get_amphipod_next_room(map, state, amphipod);
 
         next_room
-            .map(|room| move_amphipod(state, point, &room, amphipod))
-            .flatten()
+            .and_then(|room| move_amphipod(state, point, &room, amphipod))
             .map(|next_state| vec![next_state])
             .unwrap_or(vec![])
     } else {
@@ -233,7 +231,7 @@ fn get_amphipod_next_states(
         map.hallway
             .iter()
             .filter(|_| should_move)
-            .flat_map(|hallway| move_amphipod(state, point, &hallway, amphipod))
+            .flat_map(|hallway| move_amphipod(state, point, hallway, amphipod))
             .collect()
     }
 }
@@ -246,10 +244,9 @@ fn get_amphipod_next_room(map: &Map, state: &State, room_amphipod: &Amphipod) ->
     room_amphipods
         .iter()
         .rev()
-        .take_while(|&&(_, amphipod)| amphipod.is_none() || amphipod == Some(room_amphipod.clone()))
-        .filter(|(_, room_amphipod)| room_amphipod.is_none())
-        .next()
-        .map(|(point, _)| point.clone())
+        .take_while(|&&(_, amphipod)| amphipod.is_none() || amphipod == Some(*room_amphipod))
+        .find(|(_, room_amphipod)| room_amphipod.is_none())
+        .map(|(point, _)| *point)
 }
 
 fn should_amphipod_move_hallway(map: &Map, state: &State, point: &Point) -> bool {
@@ -264,13 +261,13 @@ fn should_amphipod_move_hallway(map: &Map, state: &State, point: &Point) -> bool
     let should_keep = room_amphipods
         .iter()
         .rev()
-        .take_while(|&&(_, amphipod)| amphipod == Some(room_amphipod.clone()))
+        .take_while(|&&(_, amphipod)| amphipod == Some(*room_amphipod))
         .any(|(room_point, _)| room_point == point);
 
     !should_keep
 }
 
-fn join_amphipods(state: &State, points: &Vec<Point>) -> Vec<(Point, Option<Amphipod>)> {
+fn join_amphipods(state: &State, points: &[Point]) -> Vec<(Point, Option<Amphipod>)> {
     points
         .iter()
         .map(|point| {
@@ -278,9 +275,9 @@ fn join_amphipods(state: &State, points: &Vec<Point>) -> Vec<(Point, Option<Amph
                 .amphipods
                 .iter()
                 .find(|(amphipod_point, _)| amphipod_point == point)
-                .map(|(_, amphipod)| amphipod.clone());
+                .map(|(_, amphipod)| *amphipod);
 
-            (point.clone(), amphipod)
+            (*point, amphipod)
         })
         .collect()
 }
@@ -291,13 +288,13 @@ fn move_amphipod(
     end: &Point,
     amphipod: &Amphipod,
 ) -> Option<(State, Cost)> {
-    let movement = get_movement(state, start, &end, amphipod);
+    let movement = get_movement(state, start, end, amphipod);
 
     movement.map(|cost| {
         let mut next_state = state.clone();
 
         next_state.amphipods.retain(|(point, _)| point != start);
-        next_state.amphipods.insert((end.clone(), amphipod.clone()));
+        next_state.amphipods.insert((*end, *amphipod));
 
         (next_state, cost)
     })
@@ -337,3 +334,13 @@ fn range(from: usize, to: usize) -> Box<dyn Iterator<Item = usize>> {
         Box::new((to..=from).rev())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day23;
+
+    aoc_utils::examples! {
+        Day23,
+        example: "day23.txt" => part1 "12521", part2 "44169";
+    }
+}
@@ -1,119 +1,77 @@
-use aoc_utils;
-use colored::*;
+use aoc_utils::{Dense, Grid, Input, ParseResult, Solution, Square8};
 
-type Seafloor = Vec<Vec<char>>;
+type Seafloor = Grid<char>;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("inputs/day25.txt", true).collect();
+pub struct Day25;
 
-    let initial_seafloor = parse_seafloor(lines);
+impl Solution for Day25 {
+    type Input = Seafloor;
+    type Part1 = usize;
+    type Part2 = &'static str;
 
-    let (seafloor, step) = compute_seafloor(&initial_seafloor);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    draw_seafloor(&seafloor, step);
-}
-
-fn parse_seafloor(lines: Vec<String>) -> Seafloor {
-    lines.iter().map(|line| line.chars().collect()).collect()
-}
-
-fn compute_seafloor(initial: &Seafloor) -> (Seafloor, usize) {
-    let mut current = initial.clone();
+        parse_seafloor(&lines)
+    }
 
-    for step in 1.. {
-        let next = step_seafloor(&current);
+    fn part1(initial_seafloor: &Self::Input) -> Self::Part1 {
+        let (_, step) = compute_seafloor(initial_seafloor);
 
-        if next == current {
-            return (next, step);
-        } else {
-            current = next;
-        }
+        step
     }
 
-    panic!()
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        "-"
+    }
+}
+fn parse_seafloor(lines: &[String]) -> ParseResult<Seafloor> {
+    Grid::parse_with(
+        aoc_utils::numbered(lines),
+        |char| matches!(char, '>' | 'v' | '.').then_some(char),
+        "'>', 'v' or '.'",
+    )
 }
 
-fn step_seafloor(seafloor: &Seafloor) -> Seafloor {
-    let seafloor = step_seafloor_east(seafloor);
-    let seafloor = step_seafloor_south(&seafloor);
+fn compute_seafloor(initial: &Seafloor) -> (Seafloor, usize) {
+    let mut seafloor = Dense::wrapping(Square8, initial.clone());
+    let steps = seafloor.settle(&step_cucumber);
 
-    seafloor
+    (seafloor.grid().clone(), steps + 1)
 }
 
-fn step_seafloor_east(seafloor: &Seafloor) -> Seafloor {
-    let columns = seafloor.first().map(|row| row.len()).unwrap_or(0);
-
-    seafloor
-        .iter()
-        .enumerate()
-        .map(|(index_row, tiles)| {
-            tiles
-                .iter()
-                .enumerate()
-                .map(|(index_column, &current)| {
-                    let previous_column = (index_column + columns - 1) % columns;
-                    let next_column = (index_column + columns + 1) % columns;
-
-                    let previous = seafloor[index_row][previous_column];
-                    let next = seafloor[index_row][next_column];
-
-                    if current == '.' && previous == '>' {
-                        '>'
-                    } else if current == '>' && next == '.' {
-                        '.'
-                    } else {
-                        current
-                    }
-                })
-                .collect()
-        })
-        .collect()
+/// The east-facing herd moves first and the south-facing one second, so where a cell ends up
+/// depends on what the east herd does in the rows above and below it as well as its own.
+fn step_cucumber(current: char, neighbors: &[char]) -> char {
+    let &[north, east, south, west, north_east, south_east, south_west, north_west] = neighbors
+    else {
+        return current;
+    };
+
+    let here = step_herd('>', west, current, east);
+    let above = step_herd('>', north_west, north, north_east);
+    let below = step_herd('>', south_west, south, south_east);
+
+    step_herd('v', above, here, below)
 }
 
-fn step_seafloor_south(seafloor: &Seafloor) -> Seafloor {
-    let rows = seafloor.len();
-
-    seafloor
-        .iter()
-        .enumerate()
-        .map(|(index_row, tiles)| {
-            tiles
-                .iter()
-                .enumerate()
-                .map(|(index_column, &current)| {
-                    let previous_row = (index_row + rows - 1) % rows;
-                    let next_row = (index_row + rows + 1) % rows;
-
-                    let previous = seafloor[previous_row][index_column];
-                    let next = seafloor[next_row][index_column];
-
-                    if current == '.' && previous == 'v' {
-                        'v'
-                    } else if current == 'v' && next == '.' {
-                        '.'
-                    } else {
-                        current
-                    }
-                })
-                .collect()
-        })
-        .collect()
+/// One cell of a herd moving from `previous` towards `next`.
+fn step_herd(herd: char, previous: char, current: char, next: char) -> char {
+    if current == '.' && previous == herd {
+        herd
+    } else if current == herd && next == '.' {
+        '.'
+    } else {
+        current
+    }
 }
 
-fn draw_seafloor(seafloor: &Seafloor, step: usize) {
-    println!("Step {}", step);
-
-    for rows in seafloor {
-        for &tile in rows {
-            if tile == '>' {
-                print!("{}", ">".bright_red().on_black());
-            } else if tile == 'v' {
-                print!("{}", "v".bright_green().on_black());
-            } else {
-                print!("{}", ".".bright_blue().on_black());
-            }
-        }
-
-        println!();
+#[cfg(test)]
+mod tests {
+    use super::Day25;
+
+    aoc_utils::examples! {
+        Day25,
+        example: "day25.txt" => part1 "58";
     }
 }
@@ -1,22 +1,58 @@
+use aoc_utils::{Input, ParseResult, Solution};
 use fancy_regex::Regex;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day01.txt", true).collect();
+pub struct Day01;
 
-    let sum_digits: u32 = parse_numbers(&lines, true).iter().sum();
-    let sum_all: u32 = parse_numbers(&lines, false).iter().sum();
+impl Solution for Day01 {
+    type Input = Vec<String>;
+    type Part1 = String;
+    type Part2 = String;
 
-    println!("{}", sum_digits);
-    println!("{}", sum_all);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        let re = digits_regex(false);
+
+        for line in aoc_utils::numbered(&lines) {
+            if let Some(offset) = line.find(|char: char| !char.is_ascii_alphanumeric()) {
+                return Err(line.error_at(offset, "letter or digit"));
+            }
+
+            if !re.is_match(line.text).unwrap_or(false) {
+                return Err(line.error_end("a digit or spelled-out digit"));
+            }
+        }
+
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Input) -> Self::Part1 {
+        match sum_calibration_values(lines, true) {
+            Some(sum) => sum.to_string(),
+            None => "no answer".to_string(),
+        }
+    }
+
+    fn part2(lines: &Self::Input) -> Self::Part2 {
+        match sum_calibration_values(lines, false) {
+            Some(sum) => sum.to_string(),
+            None => "no answer".to_string(),
+        }
+    }
 }
 
-fn parse_numbers(lines: &[String], only_digits: bool) -> Vec<u32> {
-    let re = if only_digits {
+fn digits_regex(only_digits: bool) -> Regex {
+    if only_digits {
         Regex::new(r"(?=(\d))")
     } else {
-        Regex::new(r"(?=(\d|one|two|three|four|five|six|seven|eight|nine|ten))")
+        Regex::new(r"(?=(\d|one|two|three|four|five|six|seven|eight|nine))")
     }
-    .expect("regex");
+    .expect("regex")
+}
+
+/// `None` when a line has no digit to read, which parsing only rules out for spelled-out digits.
+fn sum_calibration_values(lines: &[String], only_digits: bool) -> Option<u32> {
+    let re = digits_regex(only_digits);
 
     lines
         .iter()
@@ -37,10 +73,21 @@ fn parse_numbers(lines: &[String], only_digits: bool) -> Vec<u32> {
                     digit => digit,
                 });
 
-            let first = digits.next().expect("first");
+            let first = digits.next()?;
             let last = digits.last().unwrap_or(first);
 
-            format!("{first}{last}").parse().expect("number")
+            format!("{first}{last}").parse::<u32>().ok()
         })
-        .collect()
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day01;
+
+    aoc_utils::examples! {
+        Day01,
+        example: "day01.txt" => part1 "142", part2 "142";
+        example_spelled: "day01-2.txt" => part2 "281";
+    }
 }
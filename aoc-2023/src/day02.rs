@@ -1,5 +1,7 @@
+use aoc_utils::{Input, Line, ParseResult, Solution};
+
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     sets: Vec<Set>,
 }
@@ -11,60 +13,72 @@ struct Set {
     blue: u32,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day02.txt", true).collect();
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let games = parse_games(&lines);
+        parse_games(&lines)
+    }
 
-    let sum_games: u32 = find_games(&games, 12, 13, 14).iter().map(|g| g.id).sum();
-    let sum_powers: u32 = find_powers(&games).iter().sum();
+    fn part1(games: &Self::Input) -> Self::Part1 {
+        find_games(games, 12, 13, 14).iter().map(|g| g.id).sum()
+    }
 
-    println!("{:?}", sum_games);
-    println!("{:?}", sum_powers);
+    fn part2(games: &Self::Input) -> Self::Part2 {
+        find_powers(games).iter().sum()
+    }
 }
 
-fn parse_games(lines: &[String]) -> Vec<Game> {
-    lines.iter().map(|line| parse_game(line)).collect()
+fn parse_games(lines: &[String]) -> ParseResult<Vec<Game>> {
+    aoc_utils::numbered(lines)
+        .map(|line| parse_game(&line))
+        .collect()
 }
 
-fn parse_game(line: &str) -> Game {
-    let mut parts = line.split(':');
+fn parse_game(line: &Line) -> ParseResult<Game> {
+    let (id, sets) = line
+        .split_once(':')
+        .ok_or_else(|| line.error_end("':' after game id"))?;
 
-    let id = parts
-        .next()
-        .expect("id")
-        .replace("Game ", "")
-        .parse()
-        .expect("id");
+    let id = id
+        .strip_prefix("Game ")
+        .ok_or_else(|| line.error_at(0, "'Game <id>'"))?;
+    let id = line.parse(id, "game id")?;
 
-    let sets = parts
-        .next()
-        .expect("sets")
+    let sets = sets
         .split(';')
-        .map(parse_set)
-        .collect();
+        .map(|set| parse_set(line, set))
+        .collect::<ParseResult<_>>()?;
 
-    Game { id, sets }
+    Ok(Game { id, sets })
 }
 
-fn parse_set(line: &str) -> Set {
+fn parse_set(line: &Line, text: &str) -> ParseResult<Set> {
     let mut set = Set::default();
 
-    for part in line.split(',') {
-        let mut parts = part.trim().split(' ');
+    for part in text.split(',') {
+        let (count, color) = part
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| line.error(part, "<count> <color>"))?;
 
-        let count = parts.next().expect("count").parse().expect("number");
-        let color = parts.next().expect("color");
+        let count = line.parse(count, "count")?;
 
         match color {
             "red" => set.red = count,
             "green" => set.green = count,
             "blue" => set.blue = count,
-            _ => {}
+            _ => return Err(line.error(color, "red, green or blue")),
         }
     }
 
-    set
+    Ok(set)
 }
 
 fn find_games(games: &[Game], red: u32, green: u32, blue: u32) -> Vec<&Game> {
@@ -90,3 +104,13 @@ fn find_powers(games: &[Game]) -> Vec<u32> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day02;
+
+    aoc_utils::examples! {
+        Day02,
+        example: "day02.txt" => part1 "8", part2 "2286";
+    }
+}
@@ -1,30 +1,41 @@
+use aoc_utils::{Grid, Input, ParseResult, Position, Solution};
 use itertools::Itertools;
 
-type Schematic = Vec<Vec<char>>;
+type Schematic = Grid<char>;
 type Number = u32;
 type Symbol = char;
-type Position = (usize, usize);
 
 const EMPTY: char = '.';
 
 #[derive(Debug)]
-struct PartNumber {
+pub struct PartNumber {
     number: Number,
     symbol: Option<Symbol>,
     symbol_position: Option<Position>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day03.txt", true).collect();
+pub struct Day03;
 
-    let schematic = parse_schematic(&lines);
-    let part_numbers = parse_part_numbers(&schematic);
+impl Solution for Day03 {
+    type Input = Vec<PartNumber>;
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let sum = compute_sum_valid_parts(&part_numbers);
-    let ratio = compute_ratio_gears(&part_numbers);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", sum);
-    println!("{:?}", ratio);
+        let schematic = parse_schematic(&lines)?;
+
+        Ok(parse_part_numbers(&schematic))
+    }
+
+    fn part1(part_numbers: &Self::Input) -> Self::Part1 {
+        compute_sum_valid_parts(part_numbers)
+    }
+
+    fn part2(part_numbers: &Self::Input) -> Self::Part2 {
+        compute_ratio_gears(part_numbers)
+    }
 }
 
 fn compute_sum_valid_parts(part_numbers: &[PartNumber]) -> u32 {
@@ -63,25 +74,20 @@ fn compute_ratio_gears(part_numbers: &[PartNumber]) -> u32 {
         .sum()
 }
 
-fn parse_schematic(lines: &[String]) -> Schematic {
-    lines.iter().map(|line| line.chars().collect()).collect()
+fn parse_schematic(lines: &[String]) -> ParseResult<Schematic> {
+    Grid::parse_with(aoc_utils::numbered(lines), Some, "character")
 }
 
 fn parse_part_numbers(schematic: &Schematic) -> Vec<PartNumber> {
-    let rows = schematic.len();
-    let columns = schematic.first().map(|row| row.len()).unwrap_or(0);
-
     let mut part_numbers = Vec::new();
     let mut current_symbol: Option<Symbol> = None;
     let mut current_symbol_position: Option<Position> = None;
     let mut current_number: String = String::new();
 
-    for y in 0..rows {
+    for (y, row) in schematic.rows().enumerate() {
         let mut previous_char = EMPTY;
 
-        for x in 0..columns {
-            let current_char = schematic[y][x];
-
+        for (x, &current_char) in row.iter().enumerate() {
             if current_char.is_ascii_digit() {
                 if let Some((symbol, symbol_position)) = find_symbol_at(schematic, x, y) {
                     current_symbol = Some(symbol);
@@ -134,25 +140,23 @@ fn push_part_number(
 }
 
 fn find_symbol_at(schematic: &Schematic, x: usize, y: usize) -> Option<(Symbol, Position)> {
-    let rows = schematic.len();
-    let columns = schematic.first().map(|row| row.len()).unwrap_or(0);
-
-    let min_x = (x as isize - 1).max(0) as usize;
-    let max_x = (x as isize + 1).min(columns as isize - 1) as usize;
-    let min_y = (y as isize - 1).max(0) as usize;
-    let max_y = (y as isize + 1).min(rows as isize - 1) as usize;
+    schematic
+        .neighbors8((x, y))
+        .filter(|&position| {
+            let char = schematic[position];
 
-    let mut symbols = Vec::new();
+            !char.is_ascii_digit() && char != EMPTY
+        })
+        .min_by_key(|&(x, y)| (y, x))
+        .map(|position| (schematic[position], position))
+}
 
-    for adjacent_y in min_y..=max_y {
-        for adjacent_x in min_x..=max_x {
-            let char = schematic[adjacent_y][adjacent_x];
+#[cfg(test)]
+mod tests {
+    use super::Day03;
 
-            if !char.is_ascii_digit() && char != EMPTY {
-                symbols.push((char, (adjacent_x, adjacent_y)));
-            }
-        }
+    aoc_utils::examples! {
+        Day03,
+        example: "day03.txt" => part1 "4361", part2 "467835";
     }
-
-    symbols.into_iter().next()
 }
@@ -1,9 +1,10 @@
+use aoc_utils::{Input, ParseResult, Solution};
 use cached::proc_macro::cached;
 use rayon::prelude::*;
 use regex::Regex;
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: u32,
     winning_numbers: Vec<u32>,
     other_numbers: Vec<u32>,
@@ -28,55 +29,62 @@ impl Card {
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day04.txt", true).collect();
+pub struct Day04;
 
-    let cards = parse_cards(&lines);
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    let points: u32 = cards.iter().map(|card| card.points()).sum();
-    let count = count_winning_cards(&cards);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", points);
-    println!("{:?}", count);
+        parse_cards(&lines)
+    }
+
+    fn part1(cards: &Self::Input) -> Self::Part1 {
+        cards.iter().map(|card| card.points()).sum()
+    }
+
+    fn part2(cards: &Self::Input) -> Self::Part2 {
+        count_winning_cards(cards)
+    }
 }
 
-fn parse_cards(lines: &[String]) -> Vec<Card> {
+fn parse_cards(lines: &[String]) -> ParseResult<Vec<Card>> {
     let re = r"Card\s*(?P<id>\d+):(?P<winning>(?:\s*?\d+\s*?)+)[|](?P<other>(?:\s*?\d+\s*?)+)";
     let re = Regex::new(re).expect("regex");
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
-            let caps = re.captures(line).expect("captures");
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "Card <id>: <numbers> | <numbers>"))?;
 
-            let id = caps["id"].to_string().parse().expect("id");
+            let id = line.parse(&caps["id"], "id")?;
 
             let winning_numbers = caps["winning"]
                 .split_ascii_whitespace()
-                .map(|part| part.trim())
-                .filter(|part| !part.is_empty())
-                .map(|part| part.parse().expect("number"))
-                .collect();
+                .map(|part| line.parse(part, "number"))
+                .collect::<ParseResult<_>>()?;
 
             let other_numbers = caps["other"]
                 .split_ascii_whitespace()
-                .map(|part| part.trim())
-                .filter(|part| !part.is_empty())
-                .map(|part| part.parse().expect("number"))
-                .collect();
+                .map(|part| line.parse(part, "number"))
+                .collect::<ParseResult<_>>()?;
 
-            Card {
+            Ok(Card {
                 id,
                 winning_numbers,
                 other_numbers,
-            }
+            })
         })
         .collect()
 }
 
-fn count_winning_cards(cards: &Vec<Card>) -> u32 {
+fn count_winning_cards(cards: &[Card]) -> u32 {
     #[cached(key = "u32", convert = r#"{ card.id }"#)]
-    fn count_loop(cards: &Vec<Card>, card: &Card) -> u32 {
+    fn count_loop(cards: &[Card], card: &Card) -> u32 {
         let matches = card.matches();
 
         let from = card.id as usize;
@@ -91,3 +99,13 @@ fn count_winning_cards(cards: &Vec<Card>) -> u32 {
 
     cards.par_iter().map(|card| count_loop(cards, card)).sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day04;
+
+    aoc_utils::examples! {
+        Day04,
+        example: "day04.txt" => part1 "13", part2 "30";
+    }
+}
@@ -1,156 +1,176 @@
-use rayon::prelude::*;
-use std::ops::RangeInclusive;
+use aoc_utils::{Block, Input, IntervalSet, ParseError, ParseResult, Solution};
 
 type Number = u64;
-type Range = RangeInclusive<Number>;
+type Ranges = IntervalSet<Number>;
 type CategoryMaps = Vec<CategoryMap>;
 
 #[derive(Debug)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<Number>,
     categories: Vec<CategoryMaps>,
 }
 
 #[derive(Debug)]
-struct CategoryMap {
+pub struct CategoryMap {
     destination_range_start: Number,
     source_range_start: Number,
     range_length: Number,
 }
 
 impl CategoryMap {
-    fn map(&self, range: &Range) -> (Option<Range>, Vec<Range>) {
-        let source_start = self.source_range_start;
-        let source_end = self.source_range_start + self.range_length - 1u64;
+    /// The part of `ranges` this map moves, already moved, and the part it leaves alone.
+    fn map(&self, ranges: &Ranges) -> (Ranges, Ranges) {
+        let source = self.source_range_start..self.source_range_start + self.range_length;
 
-        if !(range.end() < &source_start || range.start() > &source_end) {
-            let intersection_start = *range.start().max(&source_start);
-            let intersection_end = *range.end().min(&source_end);
-
-            let mut remainder_ranges = Vec::new();
-
-            if range.start() < &intersection_start {
-                let remainder_left = *range.start()..=intersection_start - 1u64;
+        ranges.shift(source, self.destination_range_start)
+    }
+}
 
-                remainder_ranges.push(remainder_left);
-            }
+pub struct Day05;
 
-            if range.end() > &intersection_end {
-                let remainder_right = intersection_end + 1u64..=*range.end();
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Part1 = Number;
+    type Part2 = Number;
 
-                remainder_ranges.push(remainder_right);
-            }
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        parse_almanac(input)
+    }
 
-            let mapped_start =
-                intersection_start - self.source_range_start + self.destination_range_start;
-            let mapped_end =
-                intersection_end - self.source_range_start + self.destination_range_start;
+    fn part1(almanac: &Self::Input) -> Self::Part1 {
+        let seeds = almanac.seeds.iter().map(|&seed| seed..seed + 1).collect();
 
-            let mapped_range = mapped_start..=mapped_end;
+        compute_lowest_mapped_number(seeds, &almanac.categories)
+    }
 
-            (Some(mapped_range), remainder_ranges)
-        } else {
-            let remainder_ranges = vec![range.clone()];
+    fn part2(almanac: &Self::Input) -> Self::Part2 {
+        let seeds = almanac
+            .seeds
+            .chunks(2)
+            .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+            .collect();
 
-            (None, remainder_ranges)
-        }
+        compute_lowest_mapped_number(seeds, &almanac.categories)
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day05.txt", true).collect();
+fn parse_almanac(input: &Input) -> ParseResult<Almanac> {
+    let mut blocks = input.blocks();
 
-    let almanac = parse_almanac(&lines);
+    let seeds = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "'seeds:'"))?;
 
-    let lowest_single = almanac
-        .seeds
-        .par_iter()
-        .map(|&seed| compute_lowest_mapped_number(seed..=seed, &almanac.categories))
-        .min()
-        .expect("lowest_single");
+    let line = seeds[0];
 
-    let seed_ranges: Vec<_> = almanac
-        .seeds
-        .chunks(2)
-        .map(|chunk| chunk[0]..=chunk[0] + chunk[1] - 1u64)
-        .collect();
+    let seeds: Vec<Number> = line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| line.error_at(0, "'seeds:'"))?
+        .split_ascii_whitespace()
+        .map(|part| line.parse(part, "seed"))
+        .collect::<ParseResult<_>>()?;
 
-    let lowest_ranges = seed_ranges
-        .par_iter()
-        .map(|seed_range| compute_lowest_mapped_number(seed_range.clone(), &almanac.categories))
-        .min()
-        .expect("lowest_ranges");
+    if seeds.is_empty() {
+        return Err(line.error_end("seed"));
+    }
 
-    println!("{:?}", lowest_single);
-    println!("{:?}", lowest_ranges);
-}
+    if !seeds.len().is_multiple_of(2) {
+        return Err(line.error_end("a range length for the last seed"));
+    }
 
-fn parse_almanac(lines: &[String]) -> Almanac {
-    let mut iter = lines.iter();
+    let categories = blocks.map(parse_category).collect::<ParseResult<_>>()?;
 
-    let seeds: Vec<Number> = iter
-        .next()
-        .expect("seeds")
-        .split_ascii_whitespace()
-        .filter_map(|part| part.parse().ok())
-        .collect();
+    Ok(Almanac { seeds, categories })
+}
 
-    let mut categories = Vec::new();
-    let mut current_category_maps = Vec::new();
+fn parse_category(block: Block) -> ParseResult<CategoryMaps> {
+    if !block[0].contains("map:") {
+        return Err(block[0].error_end("'map:'"));
+    }
 
-    for line in iter {
-        if line.contains("map:") && !current_category_maps.is_empty() {
-            categories.push(current_category_maps);
-            current_category_maps = Vec::new();
-        } else if !line.contains("map:") && !line.trim().is_empty() {
+    block[1..]
+        .iter()
+        .map(|line| {
             let numbers: Vec<Number> = line
                 .split_ascii_whitespace()
-                .filter_map(|part| part.parse().ok())
-                .collect();
+                .map(|part| line.parse(part, "number"))
+                .collect::<ParseResult<_>>()?;
 
-            let category_map = CategoryMap {
+            if numbers.len() != 3 {
+                return Err(line.error_at(0, "destination, source and length"));
+            }
+
+            Ok(CategoryMap {
                 destination_range_start: numbers[0],
                 source_range_start: numbers[1],
                 range_length: numbers[2],
-            };
-
-            current_category_maps.push(category_map);
-        }
-    }
-
-    categories.push(current_category_maps);
-
-    Almanac { seeds, categories }
+            })
+        })
+        .collect()
 }
 
-fn compute_lowest_mapped_number(initial_range: Range, categories: &[CategoryMaps]) -> Number {
-    let mut ranges = vec![initial_range.clone()];
-
-    for category in categories.iter() {
-        let mut category_mapped_ranges = Vec::new();
+fn compute_lowest_mapped_number(seeds: Ranges, categories: &[CategoryMaps]) -> Number {
+    categories
+        .iter()
+        .fold(seeds, |ranges, category| {
+            let (mapped, remaining) = category.iter().fold(
+                (Ranges::new(), ranges),
+                |(mapped, remaining), category_map| {
+                    let (moved, remaining) = category_map.map(&remaining);
+
+                    (mapped.union(&moved), remaining)
+                },
+            );
+
+            mapped.union(&remaining)
+        })
+        .min()
+        .expect("lowest")
+}
 
-        for category_map in category {
-            let mut category_map_remainder_ranges = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            for range in ranges.iter() {
-                let (mapped_range, remainder_ranges) = category_map.map(range);
+    aoc_utils::examples! {
+        Day05,
+        example: "day05.txt" => part1 "35", part2 "46";
+    }
 
-                if let Some(mapped_range) = mapped_range {
-                    category_mapped_ranges.push(mapped_range);
-                }
+    const SEED_TO_SOIL: CategoryMap = CategoryMap {
+        destination_range_start: 52,
+        source_range_start: 50,
+        range_length: 48,
+    };
+
+    fn ranges(ranges: &[std::ops::RangeInclusive<Number>]) -> Ranges {
+        ranges
+            .iter()
+            .map(|range| *range.start()..*range.end() + 1)
+            .collect()
+    }
 
-                category_map_remainder_ranges.extend(remainder_ranges);
-            }
+    #[test]
+    fn map_range_inside() {
+        assert_eq!(
+            SEED_TO_SOIL.map(&ranges(&[79..=92])),
+            (ranges(&[81..=94]), Ranges::new())
+        );
+    }
 
-            ranges = category_map_remainder_ranges;
-        }
+    #[test]
+    fn map_range_overlapping_both_ends() {
+        let (mapped, remainder) = SEED_TO_SOIL.map(&ranges(&[45..=100]));
 
-        ranges.extend(category_mapped_ranges);
+        assert_eq!(mapped, ranges(&[52..=99]));
+        assert_eq!(remainder, ranges(&[45..=49, 98..=100]));
     }
 
-    *ranges
-        .iter()
-        .map(|range| range.start())
-        .min()
-        .expect("lowest")
+    #[test]
+    fn map_range_outside() {
+        assert_eq!(
+            SEED_TO_SOIL.map(&ranges(&[10..=20])),
+            (Ranges::new(), ranges(&[10..=20]))
+        );
+    }
 }
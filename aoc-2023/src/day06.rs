@@ -1,43 +1,45 @@
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use itertools::Itertools;
 
 type Number = u64;
 type Races = Vec<Race>;
 
 #[derive(Debug)]
-struct Race {
+pub struct Race {
     time: Number,
     distance: Number,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day06.txt", true).collect();
+pub struct Day06;
 
-    let races = parse_races(&lines, false);
-    let races_merged = parse_races(&lines, true);
+impl Solution for Day06 {
+    type Input = (Races, Races);
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let count = count_ways(&races);
-    let count_merged = count_ways(&races_merged);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", count);
-    println!("{:?}", count_merged);
-}
+        let races = parse_races(&lines, false)?;
+        let races_merged = parse_races(&lines, true)?;
 
-fn parse_races(lines: &[String], merge: bool) -> Races {
-    let mut iter = lines.iter();
+        Ok((races, races_merged))
+    }
 
-    let times: Vec<Number> = iter
-        .next()
-        .expect("times")
-        .split_ascii_whitespace()
-        .filter_map(|part| part.parse().ok())
-        .collect();
+    fn part1((races, _): &Self::Input) -> Self::Part1 {
+        count_ways(races)
+    }
 
-    let distances: Vec<Number> = iter
-        .next()
-        .expect("distances")
-        .split_ascii_whitespace()
-        .filter_map(|part| part.parse().ok())
-        .collect();
+    fn part2((_, races_merged): &Self::Input) -> Self::Part2 {
+        count_ways(races_merged)
+    }
+}
+
+fn parse_races(lines: &[String], merge: bool) -> ParseResult<Races> {
+    let mut iter = aoc_utils::numbered(lines);
+
+    let times = parse_values(iter.next().unwrap_or(Line::new(1, "")), "Time:")?;
+    let distances = parse_values(iter.next().unwrap_or(Line::new(2, "")), "Distance:")?;
 
     if merge {
         let time = times
@@ -54,16 +56,24 @@ fn parse_races(lines: &[String], merge: bool) -> Races {
             .parse()
             .expect("distance");
 
-        vec![Race { time, distance }]
+        Ok(vec![Race { time, distance }])
     } else {
-        times
+        Ok(times
             .iter()
             .zip(distances.iter())
             .map(|(&time, &distance)| Race { time, distance })
-            .collect()
+            .collect())
     }
 }
 
+fn parse_values(line: Line, label: &str) -> ParseResult<Vec<Number>> {
+    line.strip_prefix(label)
+        .ok_or_else(|| line.error_at(0, &format!("'{}'", label)))?
+        .split_ascii_whitespace()
+        .map(|part| line.parse(part, "number"))
+        .collect()
+}
+
 fn count_ways(races: &[Race]) -> Number {
     races
         .iter()
@@ -112,3 +122,13 @@ fn find_hold_times(race: &Race) -> (Number, Number) {
 fn distance(hold_time: Number, race_time: Number) -> Number {
     hold_time * (race_time - hold_time)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day06;
+
+    aoc_utils::examples! {
+        Day06,
+        example: "day06.txt" => part1 "288", part2 "71503";
+    }
+}
@@ -1,38 +1,56 @@
-use std::collections::HashMap;
-
+use aoc_utils::{Input, ParseResult, Solution};
 use rayon::prelude::*;
+use std::collections::HashMap;
 
 type Hands = Vec<Hand>;
 type Number = u128;
 
 #[derive(Debug)]
-struct Hand {
+pub struct Hand {
     cards: String,
     bid: Number,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day07.txt", true).collect();
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Hands;
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let hands = parse_hands(&lines);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let winnings = compute_total_winnings(&hands, false);
-    let winnings_joker = compute_total_winnings(&hands, true);
+        parse_hands(&lines)
+    }
+
+    fn part1(hands: &Self::Input) -> Self::Part1 {
+        compute_total_winnings(hands, false)
+    }
 
-    println!("{}", winnings);
-    println!("{}", winnings_joker);
+    fn part2(hands: &Self::Input) -> Self::Part2 {
+        compute_total_winnings(hands, true)
+    }
 }
 
-fn parse_hands(lines: &[String]) -> Hands {
-    lines
-        .iter()
+fn parse_hands(lines: &[String]) -> ParseResult<Hands> {
+    aoc_utils::numbered(lines)
         .map(|line| {
             let mut parts = line.split_ascii_whitespace();
 
-            let cards = parts.next().expect("cards").to_string();
-            let bid = parts.next().expect("bid").parse().expect("bid");
+            let cards = parts.next().ok_or_else(|| line.error_end("cards"))?;
+
+            if let Some(offset) = cards.find(|card| !"AKQJT98765432".contains(card)) {
+                return Err(line.error_at(offset, "card A, K, Q, J, T or 9 to 2"));
+            }
+
+            let bid = parts.next().ok_or_else(|| line.error_end("bid"))?;
+            let bid = line.parse(bid, "bid")?;
 
-            Hand { cards, bid }
+            Ok(Hand {
+                cards: cards.to_string(),
+                bid,
+            })
         })
         .collect()
 }
@@ -196,3 +214,13 @@ fn get_combinations_joker(initial_cards: &str) -> Vec<String> {
 
     combinations
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day07;
+
+    aoc_utils::examples! {
+        Day07,
+        example: "day07.txt" => part1 "6440", part2 "5905";
+    }
+}
@@ -1,3 +1,4 @@
+use aoc_utils::{Input, Line, ParseResult, Solution};
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -7,50 +8,67 @@ type Number = u128;
 type NodeStepMap = HashMap<Node, Number>;
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     instructions: Vec<Instruction>,
     network: HashMap<Node, (Node, Node)>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day08.txt", true).collect();
+pub struct Day08;
 
-    let map = parse_documents(&lines);
+impl Solution for Day08 {
+    type Input = Map;
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let steps_by_node = count_steps_map(&map);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let steps_aaa = steps_by_node.get("AAA").expect("node");
+        parse_documents(&lines)
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        count_steps_node(map, "AAA")
+    }
 
-    let steps_match = find_match_goal(&steps_by_node);
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        let steps_by_node = count_steps_map(map);
 
-    println!("{:?}", steps_aaa);
-    println!("{:?}", steps_match);
+        find_match_goal(&steps_by_node)
+    }
 }
 
-fn parse_documents(lines: &[String]) -> Map {
+fn parse_documents(lines: &[String]) -> ParseResult<Map> {
     let re = Regex::new(r"(?P<node>\w+) = [(](?P<left>\w+), (?P<right>\w+)[)]").expect("regex");
 
-    let mut iter = lines.iter();
+    let mut iter = aoc_utils::numbered(lines);
 
-    let instructions = iter.next().expect("instructions").chars().collect();
+    let line = iter.next().unwrap_or(Line::new(1, ""));
+
+    if let Some(offset) = line.find(|instruction| instruction != 'L' && instruction != 'R') {
+        return Err(line.error_at(offset, "instruction L or R"));
+    }
+
+    let instructions = line.chars().collect();
 
     let network = iter
         .filter(|line| !line.trim().is_empty())
         .map(|line| {
-            let caps = re.captures(line).expect("captures");
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "<node> = (<left>, <right>)"))?;
 
             let node = caps["node"].to_string();
             let left = caps["left"].to_string();
             let right = caps["right"].to_string();
 
-            (node, (left, right))
+            Ok((node, (left, right)))
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    Map {
+    Ok(Map {
         instructions,
         network,
-    }
+    })
 }
 
 fn count_steps_map(map: &Map) -> NodeStepMap {
@@ -94,19 +112,17 @@ fn count_steps_node(map: &Map, start_node: &str) -> Number {
 }
 
 fn find_match_goal(steps_by_node: &NodeStepMap) -> u128 {
-    let mut step = 1;
-    let mut increment = 1;
-
-    for node_steps in steps_by_node.values() {
-        loop {
-            if step % node_steps == 0 {
-                increment = num::integer::lcm(increment, *node_steps);
-                break;
-            } else {
-                step += increment;
-            }
-        }
-    }
+    aoc_utils::lcm_all(steps_by_node.values().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day08;
 
-    step
+    aoc_utils::examples! {
+        Day08,
+        example: "day08.txt" => part1 "2";
+        example_repeat: "day08-2.txt" => part1 "6";
+        example_ghosts: "day08-3.txt" => part2 "6";
+    }
 }
@@ -1,71 +1,62 @@
+use aoc_utils::{Input, ParseResult, Polynomial, Solution};
+
 type Number = i64;
+type Extrapolation = (Number, Number);
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day09.txt", true).collect();
+pub struct Day09;
 
-    let histories = parse_histories(&lines);
+impl Solution for Day09 {
+    type Input = Vec<Extrapolation>;
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let sum_forward: Number = histories
-        .iter()
-        .map(|history| sum_extrapolated_values(history))
-        .sum();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let sum_backward: Number = histories
-        .iter()
-        .map(|history| history.iter().cloned().rev().collect::<Vec<_>>())
-        .map(|history| sum_extrapolated_values(&history))
-        .sum();
+        parse_histories(&lines)
+    }
 
-    println!("{:?}", sum_forward);
-    println!("{:?}", sum_backward);
+    fn part1(extrapolations: &Self::Input) -> Self::Part1 {
+        extrapolations.iter().map(|&(_, next)| next).sum()
+    }
+
+    fn part2(extrapolations: &Self::Input) -> Self::Part2 {
+        extrapolations.iter().map(|&(previous, _)| previous).sum()
+    }
 }
 
-fn parse_histories(lines: &[String]) -> Vec<Vec<Number>> {
-    lines
-        .iter()
+/// Each history's values just before and just after it, which must fit in a `Number`.
+fn parse_histories(lines: &[String]) -> ParseResult<Vec<Extrapolation>> {
+    aoc_utils::numbered(lines)
         .map(|line| {
-            line.split_ascii_whitespace()
-                .filter_map(|part| part.parse().ok())
-                .collect()
+            let history = line
+                .split_ascii_whitespace()
+                .map(|part| line.parse(part, "number"))
+                .collect::<ParseResult<Vec<Number>>>()?;
+
+            let previous = extrapolate(&history, -1);
+            let next = extrapolate(&history, history.len() as i128);
+
+            previous
+                .zip(next)
+                .ok_or_else(|| line.error_at(0, "a history whose extrapolations fit in 64 bits"))
         })
         .collect()
 }
 
-fn sum_extrapolated_values(history: &[Number]) -> Number {
-    let sequences = generate_difference_sequences(history);
-
-    let mut sum = 0;
-
-    for sequence in sequences.iter().rev() {
-        let last = sequence.last().expect("last");
+fn extrapolate(history: &[Number], x: i128) -> Option<Number> {
+    let values: Vec<i128> = history.iter().map(|&value| i128::from(value)).collect();
+    let value = Polynomial::fit_sequence(&values).eval_integer(x)?;
 
-        sum += last
-    }
-
-    sum
+    Number::try_from(value).ok()
 }
 
-fn generate_difference_sequences(sequence: &[Number]) -> Vec<Vec<Number>> {
-    let mut sequences = vec![sequence.to_vec()];
-    let mut sequence = sequences.last().expect("last");
-
-    while !is_sequence_zero(sequence) {
-        let next_sequence = generate_difference_sequence(sequence);
+#[cfg(test)]
+mod tests {
+    use super::Day09;
 
-        sequences.push(next_sequence);
-        sequence = sequences.last().expect("last");
+    aoc_utils::examples! {
+        Day09,
+        example: "day09.txt" => part1 "114", part2 "2";
     }
-
-    sequences
-}
-
-fn generate_difference_sequence(sequence: &[Number]) -> Vec<Number> {
-    sequence
-        .windows(2)
-        .map(|numbers| numbers[1] - numbers[0])
-        .collect()
-}
-
-fn is_sequence_zero(sequence: &[Number]) -> bool {
-    sequence.iter().all(|number| number == &0)
 }
@@ -1,15 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use aoc_utils::{Grid, Input, ParseError, ParseResult, Position, Solution, NEIGHBORS_4};
 
-use colored::*;
-
-type Position = (usize, usize);
-type Positions = HashSet<Position>;
-type Distance = usize;
-type Path = HashMap<Position, Distance>;
-type Grid = Vec<Vec<Tile>>;
+type Path = Vec<Position>;
+type Pipes = Grid<Tile>;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
-enum Tile {
+pub enum Tile {
     NorthSouth,
     EastWest,
     NorthEast,
@@ -20,91 +15,80 @@ enum Tile {
     Start,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day10.txt", true).collect();
+pub struct Day10;
 
-    let grid = parse_grid(&lines);
+impl Solution for Day10 {
+    type Input = (Pipes, Position);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let path = find_path(&grid);
-    let enclosed = find_enclosed(&grid, &path);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    draw_grid(&grid, &path, &enclosed);
+        let grid = parse_grid(&lines)?;
 
-    println!("{:?}", path.len() / 2);
-    println!("{:?}", enclosed.len());
-}
+        let start = grid
+            .find(&Tile::Start)
+            .ok_or_else(|| ParseError::new(1, 1, "a start tile 'S'"))?;
 
-fn parse_grid(lines: &[String]) -> Grid {
-    lines
-        .iter()
-        .map(|line| line.chars().map(parse_tile).collect())
-        .collect()
-}
-
-fn parse_tile(char: char) -> Tile {
-    match char {
-        '|' => Tile::NorthSouth,
-        '-' => Tile::EastWest,
-        'L' => Tile::NorthEast,
-        'J' => Tile::NorthWest,
-        '7' => Tile::SouthWest,
-        'F' => Tile::SouthEast,
-        '.' => Tile::Ground,
-        'S' => Tile::Start,
-        _ => Tile::Ground,
+        Ok((grid, start))
     }
-}
-
-fn find_path(grid: &Grid) -> Path {
-    let start = find_start(grid).expect("start");
 
-    let mut path = Path::new();
-    let mut entry = Some((start, path.len()));
+    fn part1((grid, start): &Self::Input) -> Self::Part1 {
+        let path = find_path(grid, *start);
 
-    while let Some((position, distance)) = entry {
-        path.insert(position, distance);
+        path.len() / 2
+    }
 
-        let next_entry =
-            find_next_position(grid, &path, position).map(|(position, _)| (position, path.len()));
+    fn part2((grid, start): &Self::Input) -> Self::Part2 {
+        let path = find_path(grid, *start);
 
-        entry = next_entry
+        count_enclosed(&path)
     }
+}
 
-    path
+fn parse_grid(lines: &[String]) -> ParseResult<Pipes> {
+    Grid::parse_with(aoc_utils::numbered(lines), parse_tile, "pipe, '.' or 'S'")
 }
 
-fn find_start(grid: &Grid) -> Option<Position> {
-    grid.iter()
-        .enumerate()
-        .flat_map(|(row_index, columns)| {
-            columns
-                .iter()
-                .enumerate()
-                .map(move |(column_index, tile)| (column_index, row_index, tile))
-        })
-        .find(|(_, _, tile)| tile == &&Tile::Start)
-        .map(|(column_index, row_index, _)| (column_index, row_index))
+fn parse_tile(char: char) -> Option<Tile> {
+    match char {
+        '|' => Some(Tile::NorthSouth),
+        '-' => Some(Tile::EastWest),
+        'L' => Some(Tile::NorthEast),
+        'J' => Some(Tile::NorthWest),
+        '7' => Some(Tile::SouthWest),
+        'F' => Some(Tile::SouthEast),
+        '.' => Some(Tile::Ground),
+        'S' => Some(Tile::Start),
+        _ => None,
+    }
 }
 
-fn find_next_position(grid: &Grid, path: &Path, (x, y): Position) -> Option<(Position, Tile)> {
-    let current = ((x, y), get_tile(grid, (x, y)).expect("current"));
+/// The loop's tiles in walking order: a tile next to the start, the long way round back to it.
+fn find_path(grid: &Pipes, start: Position) -> Path {
+    let Some(first) = find_connections(grid, start).next() else {
+        return Path::new();
+    };
+
+    aoc_utils::bfs(
+        [first],
+        |&position| {
+            find_connections(grid, position).filter(move |&next| position != first || next != start)
+        },
+        |&position| position == start,
+    )
+    .map(|result| result.path)
+    .unwrap_or_default()
+}
 
-    let neighbors = vec![
-        (x as isize, y as isize - 1),
-        (x as isize, y as isize + 1),
-        (x as isize - 1, y as isize),
-        (x as isize + 1, y as isize),
-    ]
-    .into_iter()
-    .filter(|(x, y)| x >= &0 && y >= &0)
-    .map(|(x, y)| (x as usize, y as usize));
+fn find_connections(grid: &Pipes, position: Position) -> impl Iterator<Item = Position> + '_ {
+    let current = (position, grid[position]);
 
-    neighbors
+    NEIGHBORS_4
         .into_iter()
-        .filter(|position| !path.contains_key(position))
-        .filter_map(|position| get_tile(grid, position).map(|tile| (position, tile)))
-        .filter(|(_, tile)| tile != &Tile::Start)
-        .find(|next| contains_path_connection(&current, next))
+        .filter_map(move |offset| grid.offset(position, offset))
+        .filter(move |&next| contains_path_connection(&current, &(next, grid[next])))
 }
 
 fn contains_path_connection(
@@ -116,7 +100,7 @@ fn contains_path_connection(
         Tile::NorthSouth | Tile::NorthWest | Tile::NorthEast | Tile::Start
     ) && matches!(
         next_tile,
-        Tile::SouthEast | Tile::SouthWest | Tile::NorthSouth
+        Tile::SouthEast | Tile::SouthWest | Tile::NorthSouth | Tile::Start
     ) && current_y > next_y;
 
     let south_connection = matches!(
@@ -124,7 +108,7 @@ fn contains_path_connection(
         Tile::SouthEast | Tile::SouthWest | Tile::NorthSouth | Tile::Start
     ) && matches!(
         next_tile,
-        Tile::NorthSouth | Tile::NorthWest | Tile::NorthEast
+        Tile::NorthSouth | Tile::NorthWest | Tile::NorthEast | Tile::Start
     ) && current_y < next_y;
 
     let west_connection = matches!(
@@ -132,7 +116,7 @@ fn contains_path_connection(
         Tile::NorthWest | Tile::SouthWest | Tile::EastWest | Tile::Start
     ) && matches!(
         next_tile,
-        Tile::NorthEast | Tile::SouthEast | Tile::EastWest
+        Tile::NorthEast | Tile::SouthEast | Tile::EastWest | Tile::Start
     ) && current_x > next_x;
 
     let east_connection = matches!(
@@ -140,97 +124,29 @@ fn contains_path_connection(
         Tile::NorthEast | Tile::SouthEast | Tile::EastWest | Tile::Start
     ) && matches!(
         next_tile,
-        Tile::NorthWest | Tile::SouthWest | Tile::EastWest
+        Tile::NorthWest | Tile::SouthWest | Tile::EastWest | Tile::Start
     ) && current_x < next_x;
 
     north_connection || south_connection || west_connection || east_connection
 }
 
-fn find_enclosed(grid: &Grid, path: &Path) -> Positions {
-    let mut positions = Positions::new();
-
-    for (row_index, columns) in grid.iter().enumerate() {
-        let mut crossings: isize = 0;
-
-        for (column_index, _) in columns.iter().enumerate() {
-            let current_position = (column_index, row_index);
-            let current_tile = get_tile(grid, current_position);
-            let current_distance = path.get(&current_position);
-            let current_is_path = path.contains_key(&current_position);
-
-            let bottom_position = (column_index, row_index + 1);
-            let bottom_tile = get_tile(grid, bottom_position);
-            let bottom_distance = path.get(&bottom_position);
-
-            if let (
-                Some(current_tile),
-                Some(current_distance),
-                Some(bottom_tile),
-                Some(bottom_distance),
-            ) = (current_tile, current_distance, bottom_tile, bottom_distance)
-            {
-                let current = (current_position, current_tile);
-                let bottom = (bottom_position, bottom_tile);
-
-                let contains_connection = contains_path_connection(&current, &bottom);
-
-                if contains_connection && current_is_path {
-                    let value = if current_distance > bottom_distance {
-                        1
-                    } else {
-                        -1
-                    };
-
-                    crossings += value;
-                }
-            }
-
-            if !current_is_path {
-                let is_enclosed = crossings != 0;
-
-                if is_enclosed {
-                    positions.insert((column_index, row_index));
-                }
-            }
-        }
-    }
-
-    positions
-}
+/// Tiles inside the loop, counted from the loop's own tiles taken in walking order.
+fn count_enclosed(path: &Path) -> usize {
+    let vertices: Vec<_> = path.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
 
-fn get_tile(grid: &Grid, (x, y): Position) -> Option<Tile> {
-    grid.get(y).and_then(|columns| columns.get(x)).cloned()
+    aoc_utils::interior_points(&vertices) as usize
 }
 
-fn draw_grid(grid: &Grid, path: &Path, enclosed: &Positions) {
-    for (row_index, columns) in grid.iter().enumerate() {
-        for (column_index, tile) in columns.iter().enumerate() {
-            let position = (column_index, row_index);
-            let is_path = path.contains_key(&position);
-            let is_enclosed = enclosed.contains(&position);
-
-            let tile = match tile {
-                Tile::NorthSouth => "|".white(),
-                Tile::EastWest => "─".white(),
-                Tile::NorthEast => "└".white(),
-                Tile::NorthWest => "┘".white(),
-                Tile::SouthWest => "┐".white(),
-                Tile::SouthEast => "┌".white(),
-                Tile::Ground => " ".black(),
-                Tile::Start => "+".white(),
-            };
-
-            let tile = if is_path {
-                tile.red()
-            } else if is_enclosed {
-                tile.blue()
-            } else {
-                tile
-            };
-
-            print!("{}", tile.on_black());
-        }
-
-        println!();
+#[cfg(test)]
+mod tests {
+    use super::Day10;
+
+    aoc_utils::examples! {
+        Day10,
+        example_square: "day10.txt" => part1 "4";
+        example_complex: "day10-2.txt" => part1 "8";
+        example_enclosed: "day10-3.txt" => part2 "4";
+        example_enclosed_larger: "day10-4.txt" => part2 "8";
+        example_enclosed_junk: "day10-5.txt" => part2 "10";
     }
 }
@@ -1,37 +1,54 @@
+use aoc_utils::{Grid, Input, ParseResult, Solution, Vec2};
 use itertools::Itertools;
 
 type Number = isize;
 type Pixel = char;
-type Image = Vec<Vec<Pixel>>;
-type Position = (Number, Number);
+type Image = Grid<Pixel>;
+type Position = Vec2<Number>;
 type Positions = Vec<Position>;
 type EmptySpace = Vec<bool>;
 
 const EMPTY: char = '.';
 const GALAXY: char = '#';
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day11.txt", true).collect();
+pub struct Day11;
 
-    let image = parse_image(&lines);
-    let empty_space = get_empty_space(&image);
+impl Solution for Day11 {
+    type Input = (Positions, (EmptySpace, EmptySpace));
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let galaxies = find_galaxies(&image);
-    let distance_two = find_shortest_path(&galaxies, &empty_space, 2);
-    let distance_million = find_shortest_path(&galaxies, &empty_space, 1000000);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", distance_two);
-    println!("{:?}", distance_million);
+        let image = parse_image(&lines)?;
+        let empty_space = get_empty_space(&image);
+        let galaxies = find_galaxies(&image);
+
+        Ok((galaxies, empty_space))
+    }
+
+    fn part1((galaxies, empty_space): &Self::Input) -> Self::Part1 {
+        find_shortest_path(galaxies, empty_space, 2)
+    }
+
+    fn part2((galaxies, empty_space): &Self::Input) -> Self::Part2 {
+        find_shortest_path(galaxies, empty_space, 1000000)
+    }
 }
 
-fn parse_image(lines: &[String]) -> Image {
-    lines.iter().map(|line| line.chars().collect()).collect()
+fn parse_image(lines: &[String]) -> ParseResult<Image> {
+    Grid::parse_with(
+        aoc_utils::numbered(lines),
+        |char| matches!(char, EMPTY | GALAXY).then_some(char),
+        "'.' or '#'",
+    )
 }
 
 fn get_empty_space(image: &Image) -> (EmptySpace, EmptySpace) {
     let empty_rows = get_empty_rows(image);
 
-    let image = transpose_image(image);
+    let image = image.transpose();
 
     let empty_columns = get_empty_rows(&image);
 
@@ -40,37 +57,20 @@ fn get_empty_space(image: &Image) -> (EmptySpace, EmptySpace) {
 
 fn get_empty_rows(image: &Image) -> EmptySpace {
     image
-        .iter()
+        .rows()
         .map(|row| row.iter().all(|pixel| pixel == &EMPTY))
         .collect()
 }
 
-fn transpose_image(image: &Image) -> Image {
-    let rows = image.len();
-    let columns = image.first().map(|row| row.len()).unwrap_or_default();
-
-    (0..columns)
-        .map(|column| (0..rows).map(|row| image[row][column]).collect())
-        .collect()
-}
-
 fn find_galaxies(image: &Image) -> Positions {
     image
-        .iter()
-        .enumerate()
-        .flat_map(|(row_index, columns)| {
-            columns
-                .iter()
-                .enumerate()
-                .map(move |(column_index, pixel)| (column_index, row_index, pixel))
-        })
-        .filter(|(_, _, pixel)| pixel == &&GALAXY)
-        .map(|(column_index, row_index, _)| (column_index as isize, row_index as isize))
+        .find_all(&GALAXY)
+        .map(|(x, y)| Position::new(x as Number, y as Number))
         .collect()
 }
 
 fn find_shortest_path(
-    galaxies: &Positions,
+    galaxies: &[Position],
     empty_space: &(EmptySpace, EmptySpace),
     expand_size: Number,
 ) -> Number {
@@ -84,16 +84,15 @@ fn find_shortest_path(
 }
 
 fn expanded_manhattan_distance(
-    (a_x, a_y): &Position,
-    (b_x, b_y): &Position,
+    &a: &Position,
+    &b: &Position,
     (empty_columns, empty_rows): &(EmptySpace, EmptySpace),
     expand_size: Number,
 ) -> Number {
-    let distance_x = (b_x - a_x).abs();
-    let distance_y = (b_y - a_y).abs();
+    let distance = (b - a).abs();
 
-    let range_columns = *b_x.min(a_x) as usize..=*b_x.max(a_x) as usize;
-    let range_rows = *b_y.min(a_y) as usize..=*b_y.max(a_y) as usize;
+    let range_columns = b.x.min(a.x) as usize..=b.x.max(a.x) as usize;
+    let range_rows = b.y.min(a.y) as usize..=b.y.max(a.y) as usize;
 
     let count_empty_columns = empty_columns[range_columns]
         .iter()
@@ -105,8 +104,18 @@ fn expanded_manhattan_distance(
         .filter(|empty| **empty)
         .count() as isize;
 
-    let distance_x = distance_x - count_empty_columns + (count_empty_columns * expand_size);
-    let distance_y = distance_y - count_empty_rows + (count_empty_rows * expand_size);
+    let count_empty = Position::new(count_empty_columns, count_empty_rows);
+    let Vec2 { x, y } = distance - count_empty + count_empty * expand_size;
+
+    x + y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day11;
 
-    distance_x + distance_y
+    aoc_utils::examples! {
+        Day11,
+        example: "day11.txt" => part1 "374", part2 "82000210";
+    }
 }
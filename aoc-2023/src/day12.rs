@@ -1,58 +1,68 @@
+use aoc_utils::{Input, ParseResult, Solution};
 use cached::proc_macro::cached;
 
 type Number = u64;
 type Records = Vec<Record>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-enum State {
+pub enum State {
     Operational,
     Damaged,
     Unknown,
 }
 
 #[derive(Debug)]
-struct Record {
+pub struct Record {
     springs: Vec<State>,
     groups: Vec<Number>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day12.txt", true).collect();
+pub struct Day12;
 
-    let records = parse_records(&lines, false);
-    let records_unfolded = parse_records(&lines, true);
+impl Solution for Day12 {
+    type Input = (Records, Records);
+    type Part1 = Number;
+    type Part2 = Number;
 
-    let sum: Number = records.iter().map(count_arrangements).sum();
-    let sum_unfolded: Number = records_unfolded.iter().map(count_arrangements).sum();
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{:?}", sum);
-    println!("{:?}", sum_unfolded);
+        let records = parse_records(&lines, false)?;
+        let records_unfolded = parse_records(&lines, true)?;
+
+        Ok((records, records_unfolded))
+    }
+
+    fn part1((records, _): &Self::Input) -> Self::Part1 {
+        records.iter().map(count_arrangements).sum()
+    }
+
+    fn part2((_, records_unfolded): &Self::Input) -> Self::Part2 {
+        records_unfolded.iter().map(count_arrangements).sum()
+    }
 }
 
-fn parse_records(lines: &[String], unfold: bool) -> Records {
-    lines
-        .iter()
+fn parse_records(lines: &[String], unfold: bool) -> ParseResult<Records> {
+    aoc_utils::numbered(lines)
         .map(|line| {
-            let mut parts = line.split_ascii_whitespace();
-
-            let springs: Vec<_> = parts
-                .next()
-                .expect("springs")
-                .chars()
-                .map(|part| match part {
-                    '.' => State::Operational,
-                    '#' => State::Damaged,
-                    '?' => State::Unknown,
-                    _ => unreachable!(),
+            let (springs, groups) = line
+                .split_once(' ')
+                .ok_or_else(|| line.error_end("<springs> <groups>"))?;
+
+            let springs: Vec<_> = springs
+                .char_indices()
+                .map(|(offset, part)| match part {
+                    '.' => Ok(State::Operational),
+                    '#' => Ok(State::Damaged),
+                    '?' => Ok(State::Unknown),
+                    _ => Err(line.error_at(offset, "'.', '#' or '?'")),
                 })
-                .collect();
+                .collect::<ParseResult<_>>()?;
 
-            let groups: Vec<Number> = parts
-                .next()
-                .expect("groups")
+            let groups: Vec<Number> = groups
                 .split(',')
-                .filter_map(|part| part.parse().ok())
-                .collect();
+                .map(|part| line.parse(part, "group size"))
+                .collect::<ParseResult<_>>()?;
 
             if unfold {
                 let springs = [
@@ -77,9 +87,9 @@ fn parse_records(lines: &[String], unfold: bool) -> Records {
                 ]
                 .concat();
 
-                Record { springs, groups }
+                Ok(Record { springs, groups })
             } else {
-                Record { springs, groups }
+                Ok(Record { springs, groups })
             }
         })
         .collect()
@@ -159,3 +169,13 @@ fn count_loop(springs: Vec<State>, groups: Vec<Number>) -> Number {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day12;
+
+    aoc_utils::examples! {
+        Day12,
+        example: "day12.txt" => part1 "21", part2 "525152";
+    }
+}
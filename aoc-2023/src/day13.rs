@@ -1,53 +1,51 @@
-type Pattern = Vec<Vec<Tile>>;
+use aoc_utils::{Block, Grid, Input, ParseResult, Solution};
+
+type Pattern = Grid<Tile>;
 type Patterns = Vec<Pattern>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Ash,
     Rock,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day13.txt", false).collect();
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-    let patterns = parse_patterns(&lines);
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '.' => Ok(Tile::Ash),
+            '#' => Ok(Tile::Rock),
+            _ => Err(char),
+        }
+    }
+}
 
-    let summary = summarize_patterns(&patterns, false);
-    let summary_smudge = summarize_patterns(&patterns, true);
+pub struct Day13;
 
-    println!("{}", summary);
-    println!("{}", summary_smudge);
-}
+impl Solution for Day13 {
+    type Input = Patterns;
+    type Part1 = usize;
+    type Part2 = usize;
 
-fn parse_patterns(lines: &[String]) -> Patterns {
-    let mut patterns = Patterns::new();
-
-    let mut pattern = Pattern::new();
-
-    for line in lines {
-        if line.is_empty() {
-            patterns.push(pattern);
-            pattern = Pattern::new();
-        } else {
-            let row = line
-                .chars()
-                .map(|char| match char {
-                    '.' => Tile::Ash,
-                    '#' => Tile::Rock,
-                    _ => unreachable!(),
-                })
-                .collect();
-
-            pattern.push(row);
-        }
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        input.parse_blocks(parse_pattern)
     }
 
-    patterns.push(pattern);
+    fn part1(patterns: &Self::Input) -> Self::Part1 {
+        summarize_patterns(patterns, false)
+    }
 
-    patterns
+    fn part2(patterns: &Self::Input) -> Self::Part2 {
+        summarize_patterns(patterns, true)
+    }
 }
 
-fn summarize_patterns(patterns: &Patterns, smudge_mode: bool) -> usize {
+fn parse_pattern(block: Block) -> ParseResult<Pattern> {
+    Grid::parse(block.lines(), "'.' or '#'")
+}
+
+fn summarize_patterns(patterns: &[Pattern], smudge_mode: bool) -> usize {
     patterns
         .iter()
         .map(|pattern| {
@@ -61,7 +59,7 @@ fn summarize_patterns(patterns: &Patterns, smudge_mode: bool) -> usize {
 fn count_reflections(pattern: &Pattern, smudge_mode: bool) -> (usize, usize) {
     let horizontal = count_horizontal_reflections(pattern, smudge_mode).unwrap_or_default();
 
-    let transposed = transpose_pattern(pattern);
+    let transposed = pattern.transpose();
 
     let vertical = count_horizontal_reflections(&transposed, smudge_mode).unwrap_or_default();
 
@@ -69,23 +67,21 @@ fn count_reflections(pattern: &Pattern, smudge_mode: bool) -> (usize, usize) {
 }
 
 fn count_horizontal_reflections(pattern: &Pattern, smudge_mode: bool) -> Option<usize> {
-    pattern
-        .windows(2)
-        .enumerate()
-        .find(|&(index, _)| is_horizontal_reflection(pattern, index, smudge_mode))
-        .map(|(index, _)| index + 1)
+    (0..pattern.height().saturating_sub(1))
+        .find(|&index| is_horizontal_reflection(pattern, index, smudge_mode))
+        .map(|index| index + 1)
 }
 
 fn is_horizontal_reflection(pattern: &Pattern, top_index: usize, smudge_mode: bool) -> bool {
     let remaining_top = top_index + 1;
-    let remaining_bottom = pattern.len() - remaining_top;
+    let remaining_bottom = pattern.height() - remaining_top;
     let remaining = remaining_top.min(remaining_bottom);
 
     let mut smudge_fixed = false;
 
     for index in 0..remaining {
-        let top_row = &pattern[top_index - index];
-        let bottom_row = &pattern[top_index + 1 + index];
+        let top_row = pattern.row(top_index - index);
+        let bottom_row = pattern.row(top_index + 1 + index);
 
         let differences = count_differences(top_row, bottom_row);
 
@@ -107,11 +103,12 @@ fn count_differences(a: &[Tile], b: &[Tile]) -> usize {
     a.iter().zip(b.iter()).filter(|(a, b)| a != b).count()
 }
 
-fn transpose_pattern(pattern: &Pattern) -> Pattern {
-    let rows = pattern.len();
-    let columns = pattern.first().map(|row| row.len()).unwrap_or_default();
+#[cfg(test)]
+mod tests {
+    use super::Day13;
 
-    (0..columns)
-        .map(|column| (0..rows).map(|row| pattern[row][column]).collect())
-        .collect()
+    aoc_utils::examples! {
+        Day13,
+        example: "day13.txt" => part1 "405", part2 "400";
+    }
 }
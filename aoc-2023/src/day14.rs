@@ -1,98 +1,84 @@
-use std::collections::HashMap;
+use aoc_utils::{Grid, Input, ParseResult, Solution};
 use std::iter;
 
-type Platform = Vec<Vec<Tile>>;
+type Platform = Grid<Tile>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-enum Tile {
+pub enum Tile {
     Sphere,
     Cube,
     Empty,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day14.txt", true).collect();
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-    let platform = parse_platform(&lines);
-
-    let tilted_top = tilt_vertical(&platform, true);
-    let load_top = compute_platform_load(&tilted_top);
-
-    let tilted_cycle = find_platform_at_cycle(&platform, 1000000000);
-    let load_cycle = compute_platform_load(&tilted_cycle);
-
-    println!("{}", load_top);
-    println!("{}", load_cycle);
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            'O' => Ok(Tile::Sphere),
+            '#' => Ok(Tile::Cube),
+            '.' => Ok(Tile::Empty),
+            _ => Err(char),
+        }
+    }
 }
 
-fn parse_platform(lines: &[String]) -> Platform {
-    lines
-        .iter()
-        .map(|line| {
-            line.chars()
-                .map(|char| match char {
-                    'O' => Tile::Sphere,
-                    '#' => Tile::Cube,
-                    '.' => Tile::Empty,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
-}
+pub struct Day14;
 
-fn find_platform_at_cycle(initial_platform: &Platform, cycles: usize) -> Platform {
-    let loop_offset;
-    let loop_length;
+impl Solution for Day14 {
+    type Input = Platform;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let mut previous_states = HashMap::new();
-    let mut current_platform = initial_platform.clone();
-    let mut current_cycle = 0;
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    loop {
-        if let Some(previous_cycle) = previous_states.get(&current_platform) {
-            loop_offset = *previous_cycle;
-            loop_length = current_cycle - previous_cycle;
-            break;
-        }
+        parse_platform(&lines)
+    }
 
-        previous_states.insert(current_platform.clone(), current_cycle);
+    fn part1(platform: &Self::Input) -> Self::Part1 {
+        let tilted_top = tilt_vertical(platform, true);
 
-        let next_platform = execute_spin_cycle(current_platform);
+        compute_platform_load(&tilted_top)
+    }
 
-        current_cycle += 1;
-        current_platform = next_platform;
+    fn part2(platform: &Self::Input) -> Self::Part2 {
+        let tilted_cycle = find_platform_at_cycle(platform, 1000000000);
+
+        compute_platform_load(&tilted_cycle)
     }
+}
 
-    let final_cycle = ((cycles - loop_offset) % loop_length) + loop_offset;
+fn parse_platform(lines: &[String]) -> ParseResult<Platform> {
+    Grid::parse(aoc_utils::numbered(lines), "'O', '#' or '.'")
+}
 
-    previous_states
-        .iter()
-        .find(|(_, cycle)| cycle == &&final_cycle)
-        .map(|(platform, _)| platform.clone())
-        .expect("platform")
+fn find_platform_at_cycle(initial_platform: &Platform, cycles: usize) -> Platform {
+    aoc_utils::state_after(initial_platform, execute_spin_cycle, cycles)
 }
 
-fn execute_spin_cycle(platform: Platform) -> Platform {
-    let north = tilt_vertical(&platform, true);
+fn execute_spin_cycle(platform: &Platform) -> Platform {
+    let north = tilt_vertical(platform, true);
     let west = tilt_horizontal(&north, true);
     let south = tilt_vertical(&west, false);
     tilt_horizontal(&south, false)
 }
 
 fn tilt_vertical(platform: &Platform, top: bool) -> Platform {
-    let transposed = transpose_platform(platform);
+    let transposed = platform.transpose();
 
     let tilted = tilt_horizontal(&transposed, top);
 
-    transpose_platform(&tilted)
+    tilted.transpose()
 }
 
 fn tilt_horizontal(platform: &Platform, left: bool) -> Platform {
-    platform
-        .iter()
+    let rows = platform
+        .rows()
         .map(|row| tilt_horizontal_row(row, left))
-        .collect()
+        .collect();
+
+    Grid::from_rows(rows)
 }
 
 fn tilt_horizontal_row(initial_row: &[Tile], left: bool) -> Vec<Tile> {
@@ -123,7 +109,7 @@ fn tilt_horizontal_row(initial_row: &[Tile], left: bool) -> Vec<Tile> {
 
 fn compute_platform_load(platform: &Platform) -> usize {
     platform
-        .iter()
+        .rows()
         .rev()
         .enumerate()
         .map(|(index, row)| {
@@ -148,11 +134,12 @@ fn get_iterator_row(row: &[Tile]) -> impl Iterator<Item = (Option<&Tile>, &Tile,
     })
 }
 
-fn transpose_platform(platform: &Platform) -> Platform {
-    let rows = platform.len();
-    let columns = platform.first().map(|row| row.len()).unwrap_or_default();
+#[cfg(test)]
+mod tests {
+    use super::Day14;
 
-    (0..columns)
-        .map(|column| (0..rows).map(|row| platform[row][column]).collect())
-        .collect()
+    aoc_utils::examples! {
+        Day14,
+        example: "day14.txt" => part1 "136", part2 "64";
+    }
 }
@@ -1,67 +1,76 @@
-use std::collections::HashMap;
-
+use aoc_utils::{Input, ParseResult, Solution};
 use regex::Regex;
+use std::collections::HashMap;
 
 type Steps = Vec<Step>;
 type Boxes = HashMap<u32, Vec<Step>>;
 
 #[derive(Debug, Clone)]
-struct Step {
+pub struct Step {
     raw: String,
     label: String,
     operation: Operation,
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Operation {
+pub enum Operation {
     Dash,
     Equals(u32),
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day15.txt", true).collect();
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Steps;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    let steps = parse_steps(&lines);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let sum: u32 = steps.iter().map(|step| compute_hash(&step.raw)).sum();
+        parse_steps(&lines)
+    }
+
+    fn part1(steps: &Self::Input) -> Self::Part1 {
+        steps.iter().map(|step| compute_hash(&step.raw)).sum()
+    }
 
-    let boxes = compute_hashmap(&steps);
-    let power = compute_focusing_power(&boxes);
+    fn part2(steps: &Self::Input) -> Self::Part2 {
+        let boxes = compute_hashmap(steps);
 
-    println!("{}", sum);
-    println!("{}", power);
+        compute_focusing_power(&boxes)
+    }
 }
 
-fn parse_steps(lines: &[String]) -> Steps {
-    let re = Regex::new(r"(?P<label>\w+)(?P<operation>[-=])(?P<length>\d+)?").expect("regex");
+fn parse_steps(lines: &[String]) -> ParseResult<Steps> {
+    let re = Regex::new(r"^(?P<label>\w+)(?:(?P<dash>-)|=(?P<length>\d+))$").expect("regex");
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .flat_map(|line| {
-            line.split(',')
+            line.text
+                .split(',')
                 .map(|part| part.trim())
                 .filter(|part| !part.is_empty())
+                .map(move |part| (line, part))
         })
-        .map(|part| {
-            let caps = re.captures(part).expect("captures");
+        .map(|(line, part)| {
+            let caps = re
+                .captures(part)
+                .ok_or_else(|| line.error(part, "<label>- or <label>=<length>"))?;
 
             let raw = part.to_string();
             let label = caps["label"].to_string();
-            let operation = &caps["operation"];
-
-            let operation = if operation == "=" {
-                let length = caps["length"].to_string().parse().expect("length");
 
-                Operation::Equals(length)
-            } else {
-                Operation::Dash
+            let operation = match caps.name("dash") {
+                Some(_) => Operation::Dash,
+                None => Operation::Equals(line.parse(&caps["length"], "focal length")?),
             };
 
-            Step {
+            Ok(Step {
                 raw,
                 label,
                 operation,
-            }
+            })
         })
         .collect()
 }
@@ -125,3 +134,13 @@ fn compute_focusing_power(boxes: &Boxes) -> u32 {
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day15;
+
+    aoc_utils::examples! {
+        Day15,
+        example: "day15.txt" => part1 "1320", part2 "145";
+    }
+}
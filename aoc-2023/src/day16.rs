@@ -1,17 +1,16 @@
-use std::collections::HashSet;
-
-use colored::*;
+use aoc_utils::{Direction, Grid, Input, ParseResult, Solution, Vec2};
 use rayon::prelude::*;
+use std::collections::HashSet;
 
 type Number = i64;
-type Position = (Number, Number);
+type Position = Vec2<Number>;
 type Positions = HashSet<Position>;
 type Ray = (Position, Direction);
 type Rays = HashSet<Ray>;
-type Grid = Vec<Vec<Tile>>;
+type Contraption = Grid<Tile>;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Empty,
     MirrorForward,
     MirrorBackward,
@@ -19,31 +18,44 @@ enum Tile {
     SplitterHorizontal,
 }
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::MirrorForward),
+            '\\' => Ok(Tile::MirrorBackward),
+            '-' => Ok(Tile::SplitterHorizontal),
+            '|' => Ok(Tile::SplitterVertical),
+            _ => Err(char),
+        }
+    }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day16.txt", true).collect();
+pub struct Day16;
 
-    let grid = parse_grid(&lines);
+impl Solution for Day16 {
+    type Input = Contraption;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let traces = trace_rays(&grid, ((-1, 0), Direction::Right));
-    let traces_best = compute_best_configuration(&grid);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_grid(&lines)
+    }
 
-    draw_grid(&grid, &traces);
-    println!();
-    draw_grid(&grid, &traces_best);
+    fn part1(grid: &Self::Input) -> Self::Part1 {
+        trace_rays(grid, (Position::new(-1, 0), Direction::Right)).len()
+    }
 
-    println!("{}", traces.len());
-    println!("{}", traces_best.len());
+    fn part2(grid: &Self::Input) -> Self::Part2 {
+        compute_best_configuration(grid).len()
+    }
 }
 
-fn trace_rays(grid: &Grid, initial_ray: Ray) -> Positions {
+fn trace_rays(grid: &Contraption, initial_ray: Ray) -> Positions {
     let mut traced_rays = Rays::new();
     let mut current_rays = Rays::new();
 
@@ -70,62 +82,39 @@ fn trace_rays(grid: &Grid, initial_ray: Ray) -> Positions {
         .collect()
 }
 
-fn step_ray(grid: &Grid, &((ray_position_x, ray_position_y), ray_direction): &Ray) -> Rays {
-    let next_position = match ray_direction {
-        Direction::Up => (ray_position_x, ray_position_y - 1),
-        Direction::Down => (ray_position_x, ray_position_y + 1),
-        Direction::Left => (ray_position_x - 1, ray_position_y),
-        Direction::Right => (ray_position_x + 1, ray_position_y),
-    };
-
+fn step_ray(grid: &Contraption, &(ray_position, ray_direction): &Ray) -> Rays {
+    let next_position = ray_position + ray_direction.into();
     let next_tile = get_tile(grid, &next_position);
 
-    let next_rays = match next_tile {
+    let horizontal = !ray_direction.is_vertical();
+    let turns = [ray_direction.turn_left(), ray_direction.turn_right()];
+
+    let next_directions = match next_tile {
         None => vec![],
-        Some(Tile::Empty) => vec![(next_position, ray_direction)],
-        Some(Tile::MirrorForward) => match ray_direction {
-            Direction::Right => vec![(next_position, Direction::Up)],
-            Direction::Left => vec![(next_position, Direction::Down)],
-            Direction::Down => vec![(next_position, Direction::Left)],
-            Direction::Up => vec![(next_position, Direction::Right)],
-        },
-        Some(Tile::MirrorBackward) => match ray_direction {
-            Direction::Right => vec![(next_position, Direction::Down)],
-            Direction::Left => vec![(next_position, Direction::Up)],
-            Direction::Down => vec![(next_position, Direction::Right)],
-            Direction::Up => vec![(next_position, Direction::Left)],
-        },
-        Some(Tile::SplitterVertical) => match ray_direction {
-            Direction::Up | Direction::Down => vec![(next_position, ray_direction)],
-            Direction::Left | Direction::Right => {
-                vec![
-                    (next_position, Direction::Up),
-                    (next_position, Direction::Down),
-                ]
-            }
-        },
-        Some(Tile::SplitterHorizontal) => match ray_direction {
-            Direction::Left | Direction::Right => vec![(next_position, ray_direction)],
-            Direction::Up | Direction::Down => {
-                vec![
-                    (next_position, Direction::Left),
-                    (next_position, Direction::Right),
-                ]
-            }
-        },
+        Some(Tile::Empty) => vec![ray_direction],
+        Some(Tile::MirrorForward) if horizontal => vec![ray_direction.turn_left()],
+        Some(Tile::MirrorForward) => vec![ray_direction.turn_right()],
+        Some(Tile::MirrorBackward) if horizontal => vec![ray_direction.turn_right()],
+        Some(Tile::MirrorBackward) => vec![ray_direction.turn_left()],
+        Some(Tile::SplitterVertical) if horizontal => turns.to_vec(),
+        Some(Tile::SplitterHorizontal) if !horizontal => turns.to_vec(),
+        Some(Tile::SplitterVertical | Tile::SplitterHorizontal) => vec![ray_direction],
     };
 
-    Rays::from_iter(next_rays)
+    next_directions
+        .into_iter()
+        .map(|next_direction| (next_position, next_direction))
+        .collect()
 }
 
-fn compute_best_configuration(grid: &Grid) -> Positions {
-    let rows = grid.len() as Number;
-    let columns = grid.first().map(|row| row.len()).unwrap_or_default() as Number;
+fn compute_best_configuration(grid: &Contraption) -> Positions {
+    let rows = grid.height() as Number;
+    let columns = grid.width() as Number;
 
-    let down = (0..columns).map(|index| ((index, -1), Direction::Down));
-    let up = (0..columns).map(|index| ((index, rows), Direction::Up));
-    let right = (0..rows).map(|index| ((-1, index), Direction::Right));
-    let left = (0..rows).map(|index| ((columns, index), Direction::Left));
+    let down = (0..columns).map(|index| (Position::new(index, -1), Direction::Down));
+    let up = (0..columns).map(|index| (Position::new(index, rows), Direction::Up));
+    let right = (0..rows).map(|index| (Position::new(-1, index), Direction::Right));
+    let left = (0..rows).map(|index| (Position::new(columns, index), Direction::Left));
 
     let rays: Vec<_> = down.chain(up).chain(right).chain(left).collect();
 
@@ -135,55 +124,24 @@ fn compute_best_configuration(grid: &Grid) -> Positions {
         .expect("configuration")
 }
 
-fn get_tile(grid: &Grid, &(x, y): &Position) -> Option<Tile> {
+fn get_tile(grid: &Contraption, &Vec2 { x, y }: &Position) -> Option<Tile> {
     if x < 0 || y < 0 {
         None
     } else {
-        grid.get(y as usize)
-            .and_then(|row| row.get(x as usize))
-            .cloned()
+        grid.get((x as usize, y as usize)).copied()
     }
 }
 
-fn parse_grid(lines: &[String]) -> Grid {
-    lines
-        .iter()
-        .map(|line| {
-            line.chars()
-                .map(|char| match char {
-                    '.' => Tile::Empty,
-                    '/' => Tile::MirrorForward,
-                    '\\' => Tile::MirrorBackward,
-                    '-' => Tile::SplitterHorizontal,
-                    '|' => Tile::SplitterVertical,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
+fn parse_grid(lines: &[String]) -> ParseResult<Contraption> {
+    Grid::parse(aoc_utils::numbered(lines), "'.', mirror or splitter")
 }
 
-fn draw_grid(grid: &Grid, traces: &Positions) {
-    for (row_index, row) in grid.iter().enumerate() {
-        for (column_index, tile) in row.iter().enumerate() {
-            let position = (column_index as Number, row_index as Number);
-            let is_traced = traces.contains(&position);
-
-            let tile = match tile {
-                Tile::Empty if !is_traced => " ".black(),
-                Tile::Empty if is_traced => ".".red(),
-                Tile::MirrorForward => "/".blue(),
-                Tile::MirrorBackward => "\\".blue(),
-                Tile::SplitterVertical => "|".bright_blue(),
-                Tile::SplitterHorizontal => "─".bright_blue(),
-                _ => unreachable!(),
-            };
-
-            let tile = if is_traced { tile.red() } else { tile };
-
-            print!("{}", tile.on_black());
-        }
+#[cfg(test)]
+mod tests {
+    use super::Day16;
 
-        println!();
+    aoc_utils::examples! {
+        Day16,
+        example: "day16.txt" => part1 "46", part2 "51";
     }
 }
@@ -1,56 +1,59 @@
-use std::iter;
+use aoc_utils::{Direction, Input, ParseResult, Point, Solution, Vec2};
+use regex::Regex;
 
 type Plan = Vec<Instruction>;
 type Number = i64;
-type Vertex = (Number, Number);
-type Vertices = Vec<Vertex>;
-type Perimeter = Number;
-type Polygon = (Vertices, Perimeter);
+type Vertices = Vec<Point>;
 
 #[derive(Debug)]
-struct Instruction {
+pub struct Instruction {
     direction: Direction,
     meters: Number,
 }
 
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = (Plan, Plan);
+    type Part1 = Number;
+    type Part2 = Number;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day18.txt", true).collect();
+        let plan = parse_plan(&lines, false)?;
+        let plan_hex = parse_plan(&lines, true)?;
 
-    let plan = parse_plan(&lines, false);
-    let plan_hex = parse_plan(&lines, true);
+        Ok((plan, plan_hex))
+    }
+
+    fn part1((plan, _): &Self::Input) -> Self::Part1 {
+        let vertices = get_vertices(plan);
 
-    let polygon = get_polygon(&plan);
-    let area = compute_area(&polygon);
+        compute_area(&vertices)
+    }
 
-    let polygon_hex = get_polygon(&plan_hex);
-    let area_hex = compute_area(&polygon_hex);
+    fn part2((_, plan_hex): &Self::Input) -> Self::Part2 {
+        let vertices_hex = get_vertices(plan_hex);
 
-    println!("{:?}", area);
-    println!("{:?}", area_hex);
+        compute_area(&vertices_hex)
+    }
 }
 
-fn parse_plan(lines: &[String], hex: bool) -> Plan {
-    lines
-        .iter()
+fn parse_plan(lines: &[String], hex: bool) -> ParseResult<Plan> {
+    let re = Regex::new(r"^(?P<direction>[UDLR]) (?P<meters>\d+) [(]#(?P<color>[0-9a-f]{5})(?P<hex_direction>[0-3])[)]$")
+        .expect("regex");
+
+    aoc_utils::numbered(lines)
         .map(|line| {
-            let mut parts = line.split_ascii_whitespace();
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "<direction> <meters> (#<color>)"))?;
 
             if hex {
-                let color = parts.nth(2).expect("color");
-                let meters = &color[2..7];
-                let direction = &color[7..8];
-
-                let meters = Number::from_str_radix(meters, 16).expect("meters");
+                let meters = Number::from_str_radix(&caps["color"], 16).expect("meters");
 
-                let direction = match direction {
+                let direction = match &caps["hex_direction"] {
                     "0" => Direction::Right,
                     "1" => Direction::Down,
                     "2" => Direction::Left,
@@ -58,12 +61,11 @@ fn parse_plan(lines: &[String], hex: bool) -> Plan {
                     _ => unreachable!(),
                 };
 
-                Instruction { direction, meters }
+                Ok(Instruction { direction, meters })
             } else {
-                let direction = parts.next().expect("direction");
-                let meters = parts.next().expect("meters").parse().expect("meters");
+                let meters = line.parse(&caps["meters"], "meters")?;
 
-                let direction = match direction {
+                let direction = match &caps["direction"] {
                     "U" => Direction::Up,
                     "D" => Direction::Down,
                     "L" => Direction::Left,
@@ -71,58 +73,36 @@ fn parse_plan(lines: &[String], hex: bool) -> Plan {
                     _ => unreachable!(),
                 };
 
-                Instruction { direction, meters }
+                Ok(Instruction { direction, meters })
             }
         })
         .collect()
 }
 
-fn get_polygon(plan: &Plan) -> Polygon {
+fn get_vertices(plan: &Plan) -> Vertices {
     let mut vertices = Vertices::new();
-    let mut perimeter = 0;
 
-    let mut x = 0;
-    let mut y = 0;
+    let mut position = Vec2::new(0, 0);
 
     for instruction in plan {
-        match instruction.direction {
-            Direction::Right => x += instruction.meters,
-            Direction::Left => x -= instruction.meters,
-            Direction::Up => y += instruction.meters,
-            Direction::Down => y -= instruction.meters,
-        }
-
-        vertices.push((x, y));
-        perimeter += instruction.meters;
+        position += Vec2::from(instruction.direction) * instruction.meters;
+
+        vertices.push(position.into());
     }
 
-    (vertices, perimeter)
+    vertices
 }
 
-fn compute_area((vertices, perimeter): &Polygon) -> Number {
-    // Pick's theorem
-    // A = i + (b / 2) - 1
-    // i = A + 1 - (b / 2)
-    let approximate_area = shoelace_formula(vertices);
-    let boundary_points = perimeter;
-    let interior_points = approximate_area + 1 - (boundary_points / 2);
-
-    interior_points + boundary_points
+fn compute_area(vertices: &Vertices) -> Number {
+    aoc_utils::interior_points(vertices) + aoc_utils::boundary_points(vertices)
 }
 
-fn shoelace_formula(vertices: &Vertices) -> Number {
-    let first = vertices.first().expect("first");
-    let vertices: Vec<_> = vertices.iter().chain(iter::once(first)).collect();
+#[cfg(test)]
+mod tests {
+    use super::Day18;
 
-    let sum: Number = vertices
-        .windows(2)
-        .map(|window| {
-            let (x, y) = window[0];
-            let (next_x, next_y) = window[1];
-
-            (x * next_y) - (next_x * y)
-        })
-        .sum();
-
-    (sum / 2).abs()
+    aoc_utils::examples! {
+        Day18,
+        example: "day18.txt" => part1 "62", part2 "952408144115";
+    }
 }
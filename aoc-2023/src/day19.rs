@@ -1,9 +1,10 @@
-use std::ops::RangeInclusive;
-
+use aoc_utils::{Input, IntervalSet, Line, ParseError, ParseResult, Solution};
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 
 type Name = String;
 type Rate = u128;
+type Ratings = [IntervalSet<Rate>; 4];
 type Workflows = Vec<Workflow>;
 type MachineParts = Vec<MachinePart>;
 
@@ -11,27 +12,37 @@ const ACCEPTED: &str = "A";
 const REJECTED: &str = "R";
 
 #[derive(Debug, Clone)]
-struct Workflow {
+pub struct Workflow {
     name: Name,
     rules: Vec<Rule>,
 }
 
 #[derive(Debug, Clone)]
-enum Rule {
+pub enum Rule {
     Condition(ConditionRule),
     Destination(DestinationRule),
 }
 
+impl Rule {
+    fn destination(&self) -> &str {
+        match self {
+            Rule::Condition(rule) => &rule.destination,
+            Rule::Destination(rule) => &rule.destination,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
-struct ConditionRule {
+pub struct ConditionRule {
     variable: Variable,
     condition: Condition,
     value: Rate,
     destination: Name,
 }
 
+/// Indexes the ratings of a [`MachinePart`].
 #[derive(Debug, Copy, Clone)]
-enum Variable {
+pub enum Variable {
     X,
     M,
     A,
@@ -39,100 +50,134 @@ enum Variable {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Condition {
+pub enum Condition {
     LessThan,
     GreaterThan,
 }
 
 #[derive(Debug, Clone)]
-struct DestinationRule {
+pub struct DestinationRule {
     destination: String,
 }
 
 #[derive(Debug, Clone)]
-struct MachinePart {
-    x: RangeInclusive<Rate>,
-    m: RangeInclusive<Rate>,
-    a: RangeInclusive<Rate>,
-    s: RangeInclusive<Rate>,
+pub struct MachinePart {
+    ratings: Ratings,
     destination: Option<Name>,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day19.txt", false).collect();
+pub struct Day19;
 
-    let (workflows, machine_parts) = parse_workflows_parts(&lines);
+impl Solution for Day19 {
+    type Input = (Workflows, MachineParts);
+    type Part1 = Rate;
+    type Part2 = Rate;
 
-    let workflow_in = workflows
-        .iter()
-        .find(|workflow| workflow.name == "in")
-        .expect("in");
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        parse_workflows_parts(input)
+    }
 
-    let accepted: MachineParts = machine_parts
-        .iter()
-        .map(|machine_part| sort_machine_part(machine_part, workflow_in, &workflows))
-        .flat_map(|(accepted, _)| accepted)
-        .collect();
+    fn part1((workflows, machine_parts): &Self::Input) -> Self::Part1 {
+        let workflow_in = &workflows[0];
 
-    let sum = sum_machine_parts(&accepted);
+        let accepted: MachineParts = machine_parts
+            .iter()
+            .map(|machine_part| sort_machine_part(machine_part, workflow_in, workflows))
+            .flat_map(|(accepted, _)| accepted)
+            .collect();
 
-    let machine_part_combination = MachinePart {
-        x: 1..=4000,
-        m: 1..=4000,
-        a: 1..=4000,
-        s: 1..=4000,
-        destination: None,
-    };
+        sum_machine_parts(&accepted)
+    }
 
-    let (accepted_combination, _) =
-        sort_machine_part(&machine_part_combination, workflow_in, &workflows);
+    fn part2((workflows, _): &Self::Input) -> Self::Part2 {
+        let workflow_in = &workflows[0];
 
-    let combination = combination_machine_parts(&accepted_combination);
+        let machine_part_combination = MachinePart {
+            ratings: [(); 4].map(|_| IntervalSet::from(1..=4000)),
+            destination: None,
+        };
+
+        let (accepted_combination, _) =
+            sort_machine_part(&machine_part_combination, workflow_in, workflows);
 
-    println!("{}", sum);
-    println!("{}", combination);
+        combination_machine_parts(&accepted_combination)
+    }
 }
 
-fn parse_workflows_parts(lines: &[String]) -> (Workflows, MachineParts) {
-    let mut workflows = Workflows::new();
-    let mut machine_parts = MachineParts::new();
+fn parse_workflows_parts(input: &Input) -> ParseResult<(Workflows, MachineParts)> {
+    let mut blocks = input.blocks();
 
-    let mut is_workflow_line = true;
+    let workflows = blocks
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "workflows"))?;
+    let machine_parts = blocks
+        .next()
+        .ok_or_else(|| workflows.error_end("blank line before parts"))?;
 
-    for line in lines {
-        if line.is_empty() {
-            is_workflow_line = false;
-            continue;
-        }
+    let names: HashSet<&str> = workflows
+        .lines()
+        .filter_map(|line| line.text.split_once('{'))
+        .map(|(name, _)| name)
+        .collect();
 
-        if is_workflow_line {
-            let workflow = parse_workflow(line);
+    if !names.contains("in") {
+        return Err(workflows.error_end("a workflow named 'in'"));
+    }
 
-            workflows.push(workflow);
-        } else {
-            let machine_part = parse_machine_part(line);
+    let lines: Vec<Line> = workflows.lines().collect();
+    let mut workflows: Workflows = lines
+        .iter()
+        .map(|line| parse_workflow(line, &names))
+        .collect::<ParseResult<_>>()?;
 
-            machine_parts.push(machine_part);
-        }
+    if let Some(index) = find_loop(&workflows) {
+        return Err(lines[index].error_at(0, "workflows that do not loop"));
     }
 
-    (workflows, machine_parts)
+    // The parts start at `in`, so it goes first.
+    let index_in = workflows
+        .iter()
+        .position(|workflow| workflow.name == "in")
+        .unwrap_or_default();
+
+    workflows.swap(0, index_in);
+
+    let machine_parts = machine_parts
+        .lines()
+        .map(|line| parse_machine_part(&line))
+        .collect::<ParseResult<_>>()?;
+
+    Ok((workflows, machine_parts))
 }
 
-fn parse_workflow(line: &str) -> Workflow {
-    let re = Regex::new(r"(?P<name>\w+)[{](?P<rules>.+)[}]").expect("regex");
-    let caps = re.captures(line).expect("captures");
+/// Sorting follows rules from workflow to workflow, so every destination must exist.
+fn parse_workflow(line: &Line, names: &HashSet<&str>) -> ParseResult<Workflow> {
+    let re = Regex::new(r"^(?P<name>\w+)[{](?P<rules>.+)[}]$").expect("regex");
+    let caps = re
+        .captures(line.text)
+        .ok_or_else(|| line.error(line.text, "<name>{<rules>}"))?;
 
     let name = caps["name"].to_string();
-    let rules = caps["rules"].split(',').map(parse_rule).collect();
+    let rules = caps["rules"]
+        .split(',')
+        .map(|rule| parse_rule(line, rule, names))
+        .collect::<ParseResult<_>>()?;
 
-    Workflow { name, rules }
+    Ok(Workflow { name, rules })
 }
 
-fn parse_rule(line: &str) -> Rule {
-    let re = r"(?:(?P<variable>[xmas])(?P<condition>[<>])(?P<value>\d+)[:])?(?P<destination>\w+)";
+fn parse_rule(line: &Line, rule: &str, names: &HashSet<&str>) -> ParseResult<Rule> {
+    let re = r"^(?:(?P<variable>[xmas])(?P<condition>[<>])(?P<value>\d+)[:])?(?P<destination>\w+)$";
     let re = Regex::new(re).expect("regex");
-    let caps = re.captures(line).expect("captures");
+    let caps = re
+        .captures(rule)
+        .ok_or_else(|| line.error(rule, "[<variable><condition><value>:]<destination>"))?;
+
+    let destination = caps.name("destination").map_or("", |name| name.as_str());
+
+    if ![ACCEPTED, REJECTED].contains(&destination) && !names.contains(destination) {
+        return Err(line.error(destination, "a known workflow, A or R"));
+    }
 
     let is_condition_rule = caps.name("variable").is_some();
 
@@ -151,7 +196,7 @@ fn parse_rule(line: &str) -> Rule {
             _ => unreachable!(),
         };
 
-        let value = caps["value"].parse().expect("value");
+        let value = line.parse(&caps["value"], "rating")?;
         let destination = caps["destination"].to_string();
 
         let rule = ConditionRule {
@@ -161,33 +206,78 @@ fn parse_rule(line: &str) -> Rule {
             destination,
         };
 
-        Rule::Condition(rule)
+        Ok(Rule::Condition(rule))
     } else {
         let destination = caps["destination"].to_string();
 
         let rule = DestinationRule { destination };
 
-        Rule::Destination(rule)
+        Ok(Rule::Destination(rule))
     }
 }
 
-fn parse_machine_part(line: &str) -> MachinePart {
-    let re = r"[{]x=(?P<x>\d+),m=(?P<m>\d+),a=(?P<a>\d+),s=(?P<s>\d+)[}]";
+/// Index of a workflow whose rules lead back to it, which sorting would follow forever.
+fn find_loop(workflows: &[Workflow]) -> Option<usize> {
+    let indices: HashMap<&str, usize> = workflows
+        .iter()
+        .enumerate()
+        .map(|(index, workflow)| (workflow.name.as_str(), index))
+        .collect();
+
+    let mut visiting = vec![false; workflows.len()];
+    let mut done = vec![false; workflows.len()];
+
+    fn visit(
+        index: usize,
+        workflows: &[Workflow],
+        indices: &HashMap<&str, usize>,
+        visiting: &mut [bool],
+        done: &mut [bool],
+    ) -> Option<usize> {
+        if done[index] {
+            return None;
+        }
+
+        if visiting[index] {
+            return Some(index);
+        }
+
+        visiting[index] = true;
+
+        for rule in &workflows[index].rules {
+            if let Some(&next) = indices.get(rule.destination()) {
+                if let Some(found) = visit(next, workflows, indices, visiting, done) {
+                    return Some(found);
+                }
+            }
+        }
+
+        done[index] = true;
+
+        None
+    }
+
+    (0..workflows.len())
+        .find_map(|index| visit(index, workflows, &indices, &mut visiting, &mut done))
+}
+
+fn parse_machine_part(line: &Line) -> ParseResult<MachinePart> {
+    let re = r"^[{]x=(?P<x>\d+),m=(?P<m>\d+),a=(?P<a>\d+),s=(?P<s>\d+)[}]$";
     let re = Regex::new(re).expect("regex");
-    let caps = re.captures(line).expect("captures");
-
-    let x: Rate = caps["x"].parse().expect("x");
-    let m: Rate = caps["m"].parse().expect("m");
-    let a: Rate = caps["a"].parse().expect("a");
-    let s: Rate = caps["s"].parse().expect("s");
-
-    MachinePart {
-        x: x..=x,
-        m: m..=m,
-        a: a..=a,
-        s: s..=s,
+    let caps = re
+        .captures(line.text)
+        .ok_or_else(|| line.error(line.text, "{x=<x>,m=<m>,a=<a>,s=<s>}"))?;
+
+    let rating = |variable| -> ParseResult<IntervalSet<Rate>> {
+        let rating: Rate = line.parse(&caps[variable], "rating")?;
+
+        Ok(IntervalSet::from(rating..=rating))
+    };
+
+    Ok(MachinePart {
+        ratings: [rating("x")?, rating("m")?, rating("a")?, rating("s")?],
         destination: None,
-    }
+    })
 }
 
 fn sort_machine_part(
@@ -214,14 +304,15 @@ fn sort_machine_part(
                 Some(destination) => {
                     let next_workflow = workflows
                         .iter()
-                        .find(|workflow| &workflow.name == destination)
-                        .expect("workflow");
+                        .find(|workflow| &workflow.name == destination);
 
-                    let (next_accepted, next_rejected) =
-                        sort_machine_part(&mapped, next_workflow, workflows);
+                    if let Some(next_workflow) = next_workflow {
+                        let (next_accepted, next_rejected) =
+                            sort_machine_part(&mapped, next_workflow, workflows);
 
-                    accepted.extend(next_accepted);
-                    rejected.extend(next_rejected);
+                        accepted.extend(next_accepted);
+                        rejected.extend(next_rejected);
+                    }
                 }
                 None => unreachable!(),
             }
@@ -240,161 +331,97 @@ fn sort_machine_part(
 fn map_rule(machine_part: &MachinePart, rule: &Rule) -> (Option<MachinePart>, Option<MachinePart>) {
     match rule {
         Rule::Condition(condition_rule) => {
-            let variable_range = get_variable_range(machine_part, condition_rule);
-
-            let (mapped_range, remaining_range) = map_range(
-                variable_range,
-                &condition_rule.condition,
-                condition_rule.value,
-            );
-
-            let mapped_machine_part = mapped_range.map(|range| {
-                let destination = Some(condition_rule.destination.clone());
-
-                let (x, m, a, s) =
-                    set_variable_range(machine_part, &condition_rule.variable, range);
-
-                MachinePart {
-                    x,
-                    m,
-                    a,
-                    s,
-                    destination,
-                }
-            });
-
-            let remaining_machine_part = remaining_range.map(|range| {
-                let (x, m, a, s) =
-                    set_variable_range(machine_part, &condition_rule.variable, range);
-
-                MachinePart {
-                    x,
-                    m,
-                    a,
-                    s,
-                    ..machine_part.clone()
-                }
-            });
+            let index = condition_rule.variable as usize;
+            let ratings = &machine_part.ratings[index];
 
-            (mapped_machine_part, remaining_machine_part)
-        }
-        Rule::Destination(destination_rule) => {
-            let destination = Some(destination_rule.destination.clone());
+            let (mapped, remaining) = match condition_rule.condition {
+                Condition::LessThan => ratings.split_at(condition_rule.value),
+                Condition::GreaterThan => {
+                    let (below, above) = ratings.split_at(condition_rule.value + 1);
 
-            let mapped_machine_part = MachinePart {
-                destination,
-                ..machine_part.clone()
+                    (above, below)
+                }
             };
 
-            (Some(mapped_machine_part), None)
-        }
-    }
-}
+            let with_ratings = |ratings: IntervalSet<Rate>, destination: Option<Name>| {
+                let mut machine_part = machine_part.clone();
 
-fn map_range(
-    range: RangeInclusive<Rate>,
-    condition: &Condition,
-    value: Rate,
-) -> (Option<RangeInclusive<Rate>>, Option<RangeInclusive<Rate>>) {
-    match condition {
-        Condition::LessThan => {
-            let mapped = if *range.start() < value {
-                Some(*range.start()..=(value - 1).min(*range.end()))
-            } else {
-                None
-            };
+                machine_part.ratings[index] = ratings;
+                machine_part.destination = destination;
 
-            let remaining = if *range.end() >= value {
-                Some(value.max(*range.start())..=*range.end())
-            } else {
-                None
+                (!machine_part.ratings[index].is_empty()).then_some(machine_part)
             };
 
-            (mapped, remaining)
+            let destination = Some(condition_rule.destination.clone());
+
+            (
+                with_ratings(mapped, destination),
+                with_ratings(remaining, machine_part.destination.clone()),
+            )
         }
-        Condition::GreaterThan => {
-            let mapped = if *range.end() > value {
-                Some((value + 1).max(*range.start())..=*range.end())
-            } else {
-                None
-            };
+        Rule::Destination(destination_rule) => {
+            let destination = Some(destination_rule.destination.clone());
 
-            let remaining = if *range.start() <= value {
-                Some(*range.start()..=value.min(*range.end()))
-            } else {
-                None
+            let mapped_machine_part = MachinePart {
+                destination,
+                ..machine_part.clone()
             };
 
-            (mapped, remaining)
+            (Some(mapped_machine_part), None)
         }
     }
 }
 
-fn get_variable_range(
-    machine_part: &MachinePart,
-    condition_rule: &ConditionRule,
-) -> RangeInclusive<Rate> {
-    match condition_rule.variable {
-        Variable::X => machine_part.x.clone(),
-        Variable::M => machine_part.m.clone(),
-        Variable::A => machine_part.a.clone(),
-        Variable::S => machine_part.s.clone(),
-    }
-}
-
-fn set_variable_range(
-    machine_part: &MachinePart,
-    variable: &Variable,
-    range: RangeInclusive<Rate>,
-) -> (
-    RangeInclusive<Rate>,
-    RangeInclusive<Rate>,
-    RangeInclusive<Rate>,
-    RangeInclusive<Rate>,
-) {
-    match variable {
-        Variable::X => (
-            range,
-            machine_part.m.clone(),
-            machine_part.a.clone(),
-            machine_part.s.clone(),
-        ),
-        Variable::M => (
-            machine_part.x.clone(),
-            range,
-            machine_part.a.clone(),
-            machine_part.s.clone(),
-        ),
-        Variable::A => (
-            machine_part.x.clone(),
-            machine_part.m.clone(),
-            range,
-            machine_part.s.clone(),
-        ),
-        Variable::S => (
-            machine_part.x.clone(),
-            machine_part.m.clone(),
-            machine_part.a.clone(),
-            range,
-        ),
-    }
-}
-
 fn sum_machine_parts(machine_parts: &MachineParts) -> Rate {
     machine_parts
         .iter()
-        .map(|a| a.x.start() + a.m.start() + a.a.start() + a.s.start())
+        .flat_map(|machine_part| &machine_part.ratings)
+        .map(|ratings| ratings.min().expect("rating"))
         .sum()
 }
 
 fn combination_machine_parts(machine_parts: &MachineParts) -> Rate {
     machine_parts
         .iter()
-        .map(|a| {
-            (a.x.end() - a.x.start() + 1)
-                * (a.m.end() - a.m.start() + 1)
-                * (a.a.end() - a.a.start() + 1)
-                * (a.s.end() - a.s.start() + 1)
+        .map(|machine_part| {
+            machine_part
+                .ratings
+                .iter()
+                .map(IntervalSet::len)
+                .product::<Rate>()
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::examples! {
+        Day19,
+        example: "day19.txt" => part1 "19114", part2 "167409079868000";
+    }
+
+    fn parse_error(text: &str) -> String {
+        Day19::parse(&Input::literal(text))
+            .expect_err("parse error")
+            .to_string()
+    }
+
+    #[test]
+    fn rejects_unknown_workflows() {
+        assert!(parse_error("in{a<2006:qkq,R}\n\n{x=1,m=2,a=3,s=4}\n")
+            .ends_with("1:11: expected a known workflow, A or R"));
+
+        assert!(parse_error("px{a<2006:A,R}\n\n{x=1,m=2,a=3,s=4}\n")
+            .ends_with("expected a workflow named 'in'"));
+    }
+
+    #[test]
+    fn rejects_looping_workflows() {
+        assert!(
+            parse_error("in{a<2006:px,R}\npx{x>5:in,A}\n\n{x=1,m=2,a=3,s=4}\n")
+                .ends_with("1:1: expected workflows that do not loop")
+        );
+    }
+}
@@ -1,7 +1,7 @@
-use std::collections::{HashMap, VecDeque};
-
+use aoc_utils::{Input, ParseResult, Solution};
 use itertools::Itertools;
 use regex::Regex;
+use std::collections::{HashMap, VecDeque};
 
 type ModuleName = String;
 type ModuleDestination = Vec<ModuleName>;
@@ -17,40 +17,53 @@ type MemoryBlock = HashMap<MemoryAddress, PulseValue>;
 type MemoryBlocks = HashMap<ModuleName, MemoryBlock>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum PulseValue {
+pub enum PulseValue {
     Low,
     High,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum ModuleType {
+pub enum ModuleType {
     FlipFlop,
     Conjunction,
     Broadcast,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day20.txt", true).collect();
+pub struct Day20;
 
-    let configuration = parse_configuration(&lines);
+impl Solution for Day20 {
+    type Input = Configuration;
+    type Part1 = PulseCount;
+    type Part2 = ButtonPress;
 
-    let (count_low_pulses, count_high_pulses, _) = run_configuration(&configuration, 1000);
-    let (_, _, conjunction_pulses) = run_configuration(&configuration, 10000);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_configuration(&lines)
+    }
 
-    let button_presses = find_button_presses_low_pulse(&configuration, &conjunction_pulses);
+    fn part1(configuration: &Self::Input) -> Self::Part1 {
+        let (count_low_pulses, count_high_pulses, _) = run_configuration(configuration, 1000);
 
-    println!("{}", count_low_pulses * count_high_pulses);
-    println!("{}", button_presses);
+        count_low_pulses * count_high_pulses
+    }
+
+    fn part2(configuration: &Self::Input) -> Self::Part2 {
+        let (_, _, conjunction_pulses) = run_configuration(configuration, 10000);
+
+        find_button_presses_low_pulse(configuration, &conjunction_pulses)
+    }
 }
 
-fn parse_configuration(lines: &[String]) -> Configuration {
-    lines
-        .iter()
-        .map(|line| {
-            let re = r"(?P<type>[%&])?(?P<name>\w+) -> (?P<destination>.+)";
-            let re = Regex::new(re).expect("regex");
+fn parse_configuration(lines: &[String]) -> ParseResult<Configuration> {
+    let re = r"^(?P<type>[%&])?(?P<name>\w+) -> (?P<destination>.+)$";
+    let re = Regex::new(re).expect("regex");
 
-            let caps = re.captures(line).expect("captures");
+    aoc_utils::numbered(lines)
+        .map(|line| {
+            let caps = re
+                .captures(line.text)
+                .ok_or_else(|| line.error(line.text, "<module> -> <destinations>"))?;
 
             let module_name = caps["name"].to_string();
             let module_type = caps.name("type").map(|group| group.as_str());
@@ -59,7 +72,7 @@ fn parse_configuration(lines: &[String]) -> Configuration {
                 Some("%") => ModuleType::FlipFlop,
                 Some("&") => ModuleType::Conjunction,
                 _ if module_name == "broadcaster" => ModuleType::Broadcast,
-                _ => unreachable!(),
+                _ => return Err(line.error(&caps["name"], "'%', '&' or broadcaster")),
             };
 
             let destination = caps["destination"]
@@ -67,7 +80,7 @@ fn parse_configuration(lines: &[String]) -> Configuration {
                 .map(|name| name.trim().to_string())
                 .collect();
 
-            (module_name, (module_type, destination))
+            Ok((module_name, (module_type, destination)))
         })
         .collect()
 }
@@ -84,8 +97,7 @@ fn run_configuration(
 
     let conjunction_before_rx = get_module_origin(configuration, &String::from("rx"))
         .first()
-        .cloned()
-        .expect("conjunction");
+        .cloned();
 
     for button_press in 1..=button_presses {
         let button_pulse = (
@@ -105,7 +117,7 @@ fn run_configuration(
                 PulseValue::High => {
                     count_high_pulses += 1;
 
-                    if pulse_destination == conjunction_before_rx {
+                    if conjunction_before_rx.as_ref() == Some(&pulse_destination) {
                         conjunction_pulses.push((
                             button_press,
                             (pulse_value, pulse_origin.clone(), pulse_destination.clone()),
@@ -246,8 +258,16 @@ fn find_button_presses_low_pulse(
         })
         .collect();
 
-    button_presses
-        .into_iter()
-        .reduce(num::integer::lcm)
-        .expect("lcm")
+    aoc_utils::lcm_all(button_presses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day20;
+
+    aoc_utils::examples! {
+        Day20,
+        example: "day20.txt" => part1 "32000000";
+        example_cycle: "day20-2.txt" => part1 "11687500";
+    }
 }
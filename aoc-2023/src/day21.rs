@@ -1,167 +1,166 @@
+use aoc_utils::{Direction, Grid, Input, ParseError, ParseResult, Polynomial, Solution, Vec2};
 use std::collections::HashSet;
 
-use colored::*;
-
-type Number = i64;
-type Position = (i64, i64);
+type Position = Vec2<isize>;
 type Positions = HashSet<Position>;
 
-type Map = Vec<Vec<Tile>>;
+type Map = Grid<Tile>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Start,
     Plot,
     Rock,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day21.txt", true).collect();
-
-    let map = parse_map(&lines);
-
-    let plots_64 = find_reachable_plots(&map, 64);
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-    draw_map(&map, &plots_64, 1);
-    println!("{:?}", plots_64.len());
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '.' => Ok(Tile::Plot),
+            'S' => Ok(Tile::Start),
+            '#' => Ok(Tile::Rock),
+            _ => Err(char),
+        }
+    }
+}
 
-    let plots_26501365 = count_reachable_plots_lagrange_polynomial(&map, 26501365);
+pub struct Day21;
 
-    println!("{:?}", plots_26501365);
-}
+impl Solution for Day21 {
+    type Input = (Map, Position);
+    type Part1 = usize;
+    type Part2 = String;
 
-fn parse_map(lines: &[String]) -> Map {
-    lines
-        .iter()
-        .map(|line| {
-            line.chars()
-                .map(|char| match char {
-                    '.' => Tile::Plot,
-                    'S' => Tile::Start,
-                    '#' => Tile::Rock,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
-}
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-fn find_reachable_plots(map: &Map, steps: u64) -> Positions {
-    let mut current_positions = Positions::new();
+        let map = parse_map(&lines)?;
 
-    current_positions.insert(find_start_position(map));
+        let (x, y) = map
+            .find(&Tile::Start)
+            .ok_or_else(|| ParseError::new(1, 1, "a start tile 'S'"))?;
 
-    for _ in 0..steps {
-        let next_positions = current_positions
-            .iter()
-            .flat_map(|current_position| get_neighbors(map, current_position))
-            .collect();
+        Ok((map, Vec2::new(x as isize, y as isize)))
+    }
 
-        current_positions = next_positions;
+    fn part1((map, start): &Self::Input) -> Self::Part1 {
+        find_reachable_plots(map, *start, 64).len()
     }
 
-    current_positions
+    fn part2((map, start): &Self::Input) -> Self::Part2 {
+        match count_reachable_plots_polynomial(map, *start, 26501365) {
+            Some(count) => count.to_string(),
+            None => "no answer".to_string(),
+        }
+    }
 }
 
-fn count_reachable_plots_lagrange_polynomial(map: &Map, x: u64) -> u64 {
-    let size_x = map.first().map(|row| row.len()).unwrap_or_default() as f64;
-
-    let x = x as f64;
-    let x0 = 65.0;
-    let x1 = x0 + size_x;
-    let x2 = x0 + size_x * 2.0;
-
-    let y0 = find_reachable_plots(map, x0 as u64).len() as f64;
-    let y1 = find_reachable_plots(map, x1 as u64).len() as f64;
-    let y2 = find_reachable_plots(map, x2 as u64).len() as f64;
-
-    let l0 = ((x - x1) / (x0 - x1)) * ((x - x2) / (x0 - x2));
-    let l1 = ((x - x0) / (x1 - x0)) * ((x - x2) / (x1 - x2));
-    let l2 = ((x - x0) / (x2 - x0)) * ((x - x1) / (x2 - x1));
-
-    (y0 * l0 + y1 * l1 + y2 * l2) as u64
+fn parse_map(lines: &[String]) -> ParseResult<Map> {
+    Grid::parse(aoc_utils::numbered(lines), "'.', 'S' or '#'")
 }
 
-fn get_neighbors(map: &Map, &(position_x, position_y): &Position) -> Positions {
-    vec![(0, -1), (0, 1), (-1, 0), (1, 0)]
-        .into_iter()
-        .filter_map(|(direction_x, direction_y)| {
-            let next_position = (position_x + direction_x, position_y + direction_y);
+fn find_reachable_plots(map: &Map, start: Position, steps: u64) -> Positions {
+    let steps = steps as usize;
 
-            get_tile(map, &next_position)
-                .filter(|tile| tile != &Tile::Rock)
-                .map(move |_| next_position)
-        })
+    aoc_utils::distances([start], |position| get_neighbors(map, position), steps)
+        .into_iter()
+        .filter(|&(_, distance)| distance % 2 == steps % 2)
+        .map(|(position, _)| position)
         .collect()
 }
 
-fn find_start_position(map: &Map) -> Position {
-    map.iter()
-        .enumerate()
-        .find_map(|(row_index, row)| {
-            row.iter()
-                .enumerate()
-                .find(|(_, tile)| tile == &&Tile::Start)
-                .map(|(column_index, _)| (column_index as Number, row_index as Number))
-        })
-        .expect("start")
-}
+/// The reachable count grows quadratically in the number of whole map widths walked once the
+/// frontier has left the starting tile, so samples a map width apart fit it exactly. Walks no
+/// longer than the samples are counted directly. `None` when the map is not square or a fourth
+/// sample shows that the growth is not quadratic.
+fn count_reachable_plots_polynomial(map: &Map, start: Position, steps: u64) -> Option<u64> {
+    if map.width() != map.height() {
+        return None;
+    }
 
-fn get_tile(map: &Map, position: &Position) -> Option<Tile> {
-    let (x, y) = get_local_position(map, position);
+    let size = map.width() as u64;
+    let mut offset = steps % size;
 
-    map.get(y as usize)
-        .and_then(|row| row.get(x as usize))
-        .cloned()
-}
+    while offset < start.x as u64 {
+        offset += size;
+    }
 
-fn get_local_position(map: &Map, &(global_x, global_y): &Position) -> Position {
-    let size_x = map.first().map(|row| row.len()).unwrap_or_default() as Number;
-    let size_y = map.len() as Number;
+    if steps < offset + 3 * size {
+        return Some(find_reachable_plots(map, start, steps).len() as u64);
+    }
+
+    let samples = (0..4).map(|repeats| {
+        let reachable = find_reachable_plots(map, start, offset + repeats * size).len();
 
-    let local_x = if global_x >= 0 {
-        global_x % size_x
-    } else {
-        ((global_x % size_x) + size_x) % size_x
-    };
+        (repeats as i128, reachable as i128)
+    });
 
-    let local_y = if global_y >= 0 {
-        global_y % size_y
-    } else {
-        ((global_y % size_y) + size_y) % size_y
-    };
+    let polynomial = Polynomial::fit(samples);
+
+    if polynomial.degree() > 2 {
+        return None;
+    }
 
-    (local_x, local_y)
+    let repeats = ((steps - offset) / size) as i128;
+
+    polynomial
+        .eval_integer(repeats)
+        .and_then(|reachable| u64::try_from(reachable).ok())
 }
 
-fn draw_map(map: &Map, plots: &Positions, scale: Number) {
-    let size_x = map.first().map(|row| row.len()).unwrap_or_default() as Number;
-    let size_y = map.len() as Number;
+fn get_neighbors(map: &Map, &position: &Position) -> Positions {
+    Direction::ALL
+        .into_iter()
+        .map(|direction| position + Vec2::from(direction))
+        .filter(|&next_position| map.get_wrapped(next_position.into()) != &Tile::Rock)
+        .collect()
+}
 
-    let start_x = -((scale - 1) * (size_x));
-    let end_x = size_x + ((scale - 1) * size_x);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::Input;
 
-    let start_y = -((scale - 1) * (size_y));
-    let end_y = size_y + ((scale - 1) * size_y);
+    fn parse_example(text: &str) -> (Map, Position) {
+        Day21::parse(&Input::literal(text)).expect("map")
+    }
 
-    for row_index in start_y..end_y {
-        for column_index in start_x..end_x {
-            let position = (column_index as Number, row_index as Number);
+    #[test]
+    fn example_six_steps() {
+        let (map, start) = parse_example(include_str!("../examples/day21.txt"));
 
-            let tile = get_tile(map, &position).expect("tile");
-            let is_plot = plots.contains(&position);
+        assert_eq!(find_reachable_plots(&map, start, 6).len(), 16);
+    }
 
-            let tile = match tile {
-                Tile::Start if is_plot => "█".bright_red(),
-                Tile::Plot if is_plot => "█".red(),
-                Tile::Start => "█".bright_green(),
-                Tile::Plot => "█".green(),
-                Tile::Rock => "█".bright_black(),
-            };
+    #[test]
+    fn example_is_not_quadratic() {
+        let (map, start) = parse_example(include_str!("../examples/day21.txt"));
 
-            print!("{}", tile);
-        }
+        assert_eq!(count_reachable_plots_polynomial(&map, start, 10), Some(50));
+        assert_eq!(count_reachable_plots_polynomial(&map, start, 50), None);
+        assert_eq!(count_reachable_plots_polynomial(&map, start, 5000), None);
+    }
 
-        println!();
+    #[test]
+    fn open_map_extrapolates() {
+        let (map, start) = parse_example(".....\n.....\n..S..\n.....\n.....");
+
+        assert_eq!(
+            count_reachable_plots_polynomial(&map, start, 100),
+            Some(101 * 101)
+        );
+        assert_eq!(
+            count_reachable_plots_polynomial(&map, start, 26501365),
+            Some(26501366 * 26501366)
+        );
+
+        let (map, start) = parse_example("...\n.S.");
+
+        assert_eq!(
+            count_reachable_plots_polynomial(&map, start, 26501365),
+            None
+        );
     }
 }
@@ -1,12 +1,12 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::{Display, Formatter};
-
+use aoc_utils::{BoxN, Input, ParseResult, Solution, Vec3};
 use itertools::Itertools;
 use rayon::prelude::*;
-use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 
 type ID = usize;
 type Number = i64;
+type Position = Vec3<Number>;
 type Bricks = Vec<Brick>;
 type BricksByZ = HashMap<Number, Vec<Brick>>;
 type SupportTree = HashMap<ID, HashSet<ID>>;
@@ -14,20 +14,7 @@ type SupportTree = HashMap<ID, HashSet<ID>>;
 const GROUND_LEVEL: Number = 0;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct Position {
-    x: Number,
-    y: Number,
-    z: Number,
-}
-
-impl Display for Position {
-    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(formatter, "{},{},{}", self.x, self.y, self.z)
-    }
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-struct Brick {
+pub struct Brick {
     id: ID,
     start: Position,
     end: Position,
@@ -43,6 +30,20 @@ impl Brick {
         self.start.z += 1;
         self.end.z += 1;
     }
+
+    fn bounds(&self) -> BoxN<3> {
+        let Vec3 { x, y, z } = self.start;
+        let Vec3 {
+            x: end_x,
+            y: end_y,
+            z: end_z,
+        } = self.end;
+
+        BoxN::from_inclusive(
+            [x.min(end_x), y.min(end_y), z.min(end_z)],
+            [x.max(end_x), y.max(end_y), z.max(end_z)],
+        )
+    }
 }
 
 impl Display for Brick {
@@ -51,46 +52,52 @@ impl Display for Brick {
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day22.txt", true).collect();
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Bricks;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_bricks(&lines)
+    }
+
+    fn part1(bricks: &Self::Input) -> Self::Part1 {
+        let (stable_bricks, stable_bricks_by_z) = process_falling_bricks(bricks);
 
-    let bricks = parse_bricks(&lines);
+        let support_tree = build_support_tree(&stable_bricks, &stable_bricks_by_z);
 
-    let (stable_bricks, stable_bricks_by_z) = process_falling_bricks(&bricks);
+        get_optional_bricks(&support_tree).len()
+    }
 
-    let support_tree = build_support_tree(&stable_bricks, &stable_bricks_by_z);
+    fn part2(bricks: &Self::Input) -> Self::Part2 {
+        let (stable_bricks, stable_bricks_by_z) = process_falling_bricks(bricks);
 
-    let optional_bricks = get_optional_bricks(&support_tree);
+        let support_tree = build_support_tree(&stable_bricks, &stable_bricks_by_z);
 
-    let count_chain_reaction = count_chain_reaction_bricks(&optional_bricks, &stable_bricks);
+        let optional_bricks = get_optional_bricks(&support_tree);
 
-    println!("{}", optional_bricks.len());
-    println!("{}", count_chain_reaction);
+        count_chain_reaction_bricks(&optional_bricks, &stable_bricks)
+    }
 }
 
-fn parse_bricks(lines: &[String]) -> Bricks {
-    let re = r"(?P<x0>\d+),(?P<y0>\d+),(?P<z0>\d+)~(?P<x1>\d+),(?P<y1>\d+),(?P<z1>\d+)";
-    let re = Regex::new(re).expect("regex");
+fn parse_bricks(lines: &[String]) -> ParseResult<Bricks> {
+    let expected = "<x>,<y>,<z>~<x>,<y>,<z>";
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .enumerate()
         .map(|(id, line)| {
-            let caps = re.captures(line).expect("captures");
-
-            let start = Position {
-                x: caps["x0"].parse().expect("x0"),
-                y: caps["y0"].parse().expect("y0"),
-                z: caps["z0"].parse().expect("z0"),
-            };
+            let (start, end) = line
+                .split_once('~')
+                .ok_or_else(|| line.error(line.text, expected))?;
 
-            let end = Position {
-                x: caps["x1"].parse().expect("x1"),
-                y: caps["y1"].parse().expect("y1"),
-                z: caps["z1"].parse().expect("z1"),
-            };
+            let start = line.parse(start, expected)?;
+            let end = line.parse(end, expected)?;
 
-            Brick { id, start, end }
+            Ok(Brick { id, start, end })
         })
         .collect()
 }
@@ -113,8 +120,8 @@ fn process_falling_bricks(falling_bricks: &Bricks) -> (Bricks, BricksByZ) {
     }
 
     let stable_bricks = stable_bricks_by_z
-        .iter()
-        .flat_map(|(_, stable_bricks)| stable_bricks)
+        .values()
+        .flatten()
         .unique()
         .copied()
         .collect();
@@ -237,34 +244,19 @@ fn get_bricks_nearby(brick: &Brick, stable_bricks_by_z: &BricksByZ) -> Bricks {
 }
 
 fn is_colliding_brick(a: &Brick, b: &Brick) -> bool {
-    let a_x = (a.start.x, a.end.x);
-    let a_y = (a.start.y, a.end.y);
-    let a_z = (a.start.z, a.end.z);
-
-    let b_x = (b.start.x, b.end.x);
-    let b_y = (b.start.y, b.end.y);
-    let b_z = (b.start.z, b.end.z);
-
-    let is_colliding_x = is_colliding_interval(&a_x, &b_x);
-    let is_colliding_y = is_colliding_interval(&a_y, &b_y);
-    let is_colliding_z = is_colliding_interval(&a_z, &b_z);
-
-    is_colliding_x && is_colliding_y && is_colliding_z
-}
-
-fn is_colliding_interval(
-    (start_a, end_a): &(Number, Number),
-    (start_b, end_b): &(Number, Number),
-) -> bool {
-    let min_a = start_a.min(end_a);
-    let max_a = start_a.max(end_a);
-
-    let min_b = start_b.min(end_b);
-    let max_b = start_b.max(end_b);
-
-    !(max_a < min_b || min_a > max_b)
+    a.bounds().intersects(&b.bounds())
 }
 
 fn is_colliding_ground(a: &Brick) -> bool {
     a.start.z <= GROUND_LEVEL || a.end.z <= GROUND_LEVEL
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day22;
+
+    aoc_utils::examples! {
+        Day22,
+        example: "day22.txt" => part1 "5", part2 "7";
+    }
+}
@@ -1,18 +1,15 @@
+use aoc_utils::{Direction, Grid, Input, ParseResult, Position, Solution};
 use std::collections::HashMap;
 
-use colored::*;
-
-type Number = i64;
-type Position = (Number, Number);
 type Positions = Vec<Position>;
 
 type Distance = usize;
 type Graph = HashMap<Position, HashMap<Position, Distance>>;
 
-type Map = Vec<Vec<Tile>>;
+type Map = Grid<Tile>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Path,
     Forest,
     SlopeLeft,
@@ -21,39 +18,50 @@ enum Tile {
     SlopeDown,
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day23.txt", true).collect();
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        match char {
+            '.' => Ok(Tile::Path),
+            '#' => Ok(Tile::Forest),
+            '<' => Ok(Tile::SlopeLeft),
+            '>' => Ok(Tile::SlopeRight),
+            '^' => Ok(Tile::SlopeUp),
+            'v' => Ok(Tile::SlopeDown),
+            _ => Err(char),
+        }
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Map;
+    type Part1 = Distance;
+    type Part2 = Distance;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
+
+        parse_map(&lines)
+    }
 
-    let map = parse_map(&lines);
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        let graph_slopes = build_map_graph(map, (1, 0), true);
 
-    let graph_slopes = build_map_graph(&map, (1, 0), true);
-    let graph = build_map_graph(&map, (1, 0), false);
+        find_longest_hike(map, &graph_slopes)
+    }
 
-    let longest_slopes = find_longest_hike(&map, &graph_slopes);
-    let longest = find_longest_hike(&map, &graph);
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        let graph = build_map_graph(map, (1, 0), false);
 
-    draw_map(&map);
-    println!("{}", longest_slopes);
-    println!("{}", longest);
+        find_longest_hike(map, &graph)
+    }
 }
 
-fn parse_map(lines: &[String]) -> Map {
-    lines
-        .iter()
-        .map(|line| {
-            line.chars()
-                .map(|char| match char {
-                    '.' => Tile::Path,
-                    '#' => Tile::Forest,
-                    '<' => Tile::SlopeLeft,
-                    '>' => Tile::SlopeRight,
-                    '^' => Tile::SlopeUp,
-                    'v' => Tile::SlopeDown,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
+fn parse_map(lines: &[String]) -> ParseResult<Map> {
+    Grid::parse(aoc_utils::numbered(lines), "'.', '#' or slope")
 }
 
 fn build_map_graph(map: &Map, start: Position, slopes: bool) -> Graph {
@@ -102,20 +110,16 @@ fn build_map_graph(map: &Map, start: Position, slopes: bool) -> Graph {
     graph
 }
 
-fn get_map_neighbors(map: &Map, (x, y): Position, previous: Position, slopes: bool) -> Positions {
-    let tile = get_map_tile(map, (x, y));
+fn get_map_neighbors(map: &Map, position: Position, previous: Position, slopes: bool) -> Positions {
+    let tile = map.get(position).copied();
 
-    let left = (-1, 0);
-    let right = (1, 0);
-    let up = (0, -1);
-    let down = (0, 1);
-    let all = vec![up, down, left, right];
+    let all = Direction::ALL.to_vec();
 
     let directions = match tile {
-        Some(Tile::SlopeLeft) if slopes => vec![left],
-        Some(Tile::SlopeRight) if slopes => vec![right],
-        Some(Tile::SlopeUp) if slopes => vec![up],
-        Some(Tile::SlopeDown) if slopes => vec![down],
+        Some(Tile::SlopeLeft) if slopes => vec![Direction::Left],
+        Some(Tile::SlopeRight) if slopes => vec![Direction::Right],
+        Some(Tile::SlopeUp) if slopes => vec![Direction::Up],
+        Some(Tile::SlopeDown) if slopes => vec![Direction::Down],
         Some(Tile::SlopeLeft | Tile::SlopeRight | Tile::SlopeUp | Tile::SlopeDown) => all,
         Some(Tile::Path) => all,
         _ => vec![],
@@ -123,32 +127,15 @@ fn get_map_neighbors(map: &Map, (x, y): Position, previous: Position, slopes: bo
 
     directions
         .into_iter()
-        .map(|(direction_x, direction_y)| (x + direction_x, y + direction_y))
+        .filter_map(|direction| map.offset(position, direction.offset()))
         .filter(|&next_position| next_position != previous)
-        .filter_map(|next_position| {
-            get_map_tile(map, next_position)
-                .filter(|tile| tile != &Tile::Forest)
-                .map(move |_| next_position)
-        })
+        .filter(|&next_position| map[next_position] != Tile::Forest)
         .collect()
 }
 
-fn get_map_tile(map: &Map, (x, y): Position) -> Option<Tile> {
-    if x < 0 || y < 0 {
-        None
-    } else {
-        map.get(y as usize)
-            .and_then(|row| row.get(x as usize))
-            .cloned()
-    }
-}
-
 fn find_longest_hike(map: &Map, graph: &Graph) -> Distance {
-    let rows = map.len() as Number;
-    let columns = map.first().map(|row| row.len()).unwrap_or_default() as Number;
-
     let start = (1, 0);
-    let goal = (columns - 2, rows - 1);
+    let goal = (map.width() - 2, map.height() - 1);
     let visited = Positions::new();
     let distance = 0;
 
@@ -187,21 +174,12 @@ fn dfs_longest_path(
     }
 }
 
-fn draw_map(map: &Map) {
-    for (_, row) in map.iter().enumerate() {
-        for (_, tile) in row.iter().enumerate() {
-            let tile = match tile {
-                Tile::Path => "█".bright_green(),
-                Tile::Forest => "█".green(),
-                Tile::SlopeLeft => "⮜".black().on_bright_green(),
-                Tile::SlopeRight => "⮞".black().on_bright_green(),
-                Tile::SlopeUp => "⮝".black().on_bright_green(),
-                Tile::SlopeDown => "⮟".black().on_bright_green(),
-            };
-
-            print!("{}", tile);
-        }
+#[cfg(test)]
+mod tests {
+    use super::Day23;
 
-        println!();
+    aoc_utils::examples! {
+        Day23,
+        example: "day23.txt" => part1 "94", part2 "154";
     }
 }
@@ -1,89 +1,43 @@
+use aoc_utils::{Input, ParseResult, Solution, Vec2, Vec3};
 use itertools::Itertools;
 use num::rational::Ratio;
 use num::Zero;
 use rayon::prelude::*;
-use regex::Regex;
 
 type Number = Ratio<i128>;
+type Point = Vec2<Number>;
+type Vector = Vec3<Number>;
 type Objects = Vec<Object>;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Point {
-    x: Number,
-    y: Number,
-}
-
-impl Point {
-    fn new(x: i128, y: i128) -> Self {
-        Self {
-            x: Number::from_integer(x),
-            y: Number::from_integer(y),
-        }
-    }
-
-    fn is_inside_area(&self, min: &Point, max: &Point) -> bool {
-        let is_inside_x = self.x >= min.x && self.x <= max.x;
-        let is_inside_y = self.y >= min.y && self.y <= max.y;
-
-        is_inside_x && is_inside_y
-    }
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Line {
+pub struct Line {
     a: Point,
     b: Point,
 }
 
 impl Line {
     fn intersect(&self, other: &Self) -> Option<Point> {
-        let x1 = self.a.x;
-        let y1 = self.a.y;
-        let x2 = self.b.x;
-        let y2 = self.b.y;
-
-        let x3 = other.a.x;
-        let y3 = other.a.y;
-        let x4 = other.b.x;
-        let y4 = other.b.y;
+        let direction = self.b - self.a;
+        let direction_other = other.b - other.a;
 
-        let denominator = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
+        let denominator = direction.cross(direction_other);
 
         if denominator.is_zero() {
             return None;
         }
 
-        let x_numerator = (x1 * y2 - y1 * x2) * (x3 - x4) - (x1 - x2) * (x3 * y4 - y3 * x4);
-        let y_numerator = (x1 * y2 - y1 * x2) * (y3 - y4) - (y1 - y2) * (x3 * y4 - y3 * x4);
-
-        let x = (x_numerator) / (denominator);
-        let y = (y_numerator) / (denominator);
+        let t = (other.a - self.a).cross(direction_other) / denominator;
 
-        Some(Point { x, y })
+        Some(self.a + direction * t)
     }
 
     fn is_before_a(&self, c: &Point) -> bool {
-        let ac_x = c.x - self.a.x;
-        let ac_y = c.y - self.a.y;
-
-        let ab_x = self.b.x - self.a.x;
-        let ab_y = self.b.y - self.a.y;
-
-        let dot_product = (ac_x * ab_x) + (ac_y * ab_y);
-
-        dot_product <= Number::zero()
+        (*c - self.a).dot(self.b - self.a) <= Number::zero()
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Vector {
-    x: Number,
-    y: Number,
-    z: Number,
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct Object {
+pub struct Object {
     position: Vector,
     velocity: Vector,
 }
@@ -112,92 +66,94 @@ impl Object {
     }
 
     fn line_xy(&self) -> Line {
+        let next = self.position + self.velocity;
+
         Line {
-            a: Point {
-                x: self.position.x,
-                y: self.position.y,
-            },
-            b: Point {
-                x: self.position.x + self.velocity.x,
-                y: self.position.y + self.velocity.y,
-            },
+            a: Point::new(self.position.x, self.position.y),
+            b: Point::new(next.x, next.y),
         }
     }
 
     fn line_xz(&self) -> Line {
+        let next = self.position + self.velocity;
+
         Line {
-            a: Point {
-                x: self.position.x,
-                y: self.position.z,
-            },
-            b: Point {
-                x: self.position.x + self.velocity.x,
-                y: self.position.z + self.velocity.z,
-            },
+            a: Point::new(self.position.x, self.position.z),
+            b: Point::new(next.x, next.z),
         }
     }
 }
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day24.txt", true).collect();
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Objects;
+    type Part1 = usize;
+    type Part2 = Number;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    let hailstones = parse_hailstones(&lines);
-    let min = Point::new(200000000000000, 200000000000000);
-    let max = Point::new(400000000000000, 400000000000000);
+        parse_hailstones(&lines)
+    }
 
-    let count = count_hailstone_intersections(&hailstones, min, max);
+    fn part1(hailstones: &Self::Input) -> Self::Part1 {
+        count_hailstone_intersections(hailstones, 200000000000000, 400000000000000)
+    }
 
-    let rock = estimate_rock_intersection(&hailstones, 300);
-    let sum = rock.position.x + rock.position.y + rock.position.z;
+    fn part2(hailstones: &Self::Input) -> Self::Part2 {
+        let rock = estimate_rock_intersection(hailstones, 300);
 
-    println!("{:?}", count);
-    println!("{}", sum);
+        rock.position.x + rock.position.y + rock.position.z
+    }
 }
 
-fn parse_hailstones(lines: &[String]) -> Objects {
-    let re = r"(?P<px>-?\d+),\s+(?P<py>-?\d+),\s+(?P<pz>-?\d+)\s+@\s+(?P<vx>-?\d+),\s+(?P<vy>-?\d+),\s+(?P<vz>-?\d+)";
-    let re = Regex::new(re).expect("regex");
+fn parse_hailstones(lines: &[String]) -> ParseResult<Objects> {
+    let expected = "<px>, <py>, <pz> @ <vx>, <vy>, <vz>";
 
-    lines
-        .iter()
+    aoc_utils::numbered(lines)
         .map(|line| {
-            let caps = re.captures(line).expect("captures");
-
-            let position = Vector {
-                x: caps["px"].parse().expect("px"),
-                y: caps["py"].parse().expect("py"),
-                z: caps["pz"].parse().expect("pz"),
-            };
+            let (position, velocity) = line
+                .split_once('@')
+                .ok_or_else(|| line.error(line.text, expected))?;
 
-            let velocity = Vector {
-                x: caps["vx"].parse().expect("vx"),
-                y: caps["vy"].parse().expect("vy"),
-                z: caps["vz"].parse().expect("vz"),
-            };
+            let position = line.parse(position, expected)?;
+            let velocity = line.parse(velocity, expected)?;
 
-            Object { position, velocity }
+            Ok(Object { position, velocity })
         })
         .collect()
 }
 
-fn count_hailstone_intersections(hailstones: &Objects, min: Point, max: Point) -> usize {
+/// Crossings of future paths inside the square test area `min..=max` on both axes.
+fn count_hailstone_intersections(hailstones: &Objects, min: i128, max: i128) -> usize {
+    let min = Point::new(Number::from_integer(min), Number::from_integer(min));
+    let max = Point::new(Number::from_integer(max), Number::from_integer(max));
+
     hailstones
         .iter()
         .combinations(2)
         .filter_map(|hailstones| {
             hailstones[0]
                 .intersect_xy(hailstones[1])
-                .filter(|intersection| intersection.is_inside_area(&min, &max))
+                .filter(|intersection| is_inside_area(intersection, &min, &max))
         })
         .count()
 }
 
+fn is_inside_area(point: &Point, min: &Point, max: &Point) -> bool {
+    let is_inside_x = point.x >= min.x && point.x <= max.x;
+    let is_inside_y = point.y >= min.y && point.y <= max.y;
+
+    is_inside_x && is_inside_y
+}
+
 fn estimate_rock_intersection(hailstones: &Objects, range: i128) -> Object {
     let (rock_velocity_xy, rock_position_xy) = (-range..=range)
         .into_par_iter()
         .find_map_any(|x| {
             (-range..=range).find_map(|y| {
-                let rock_velocity_xy = (Number::from_integer(x), Number::from_integer(y));
+                let rock_velocity_xy = Point::new(Number::from_integer(x), Number::from_integer(y));
 
                 let rock_position_xy = estimate_rock_position_xy(hailstones, rock_velocity_xy);
 
@@ -209,7 +165,7 @@ fn estimate_rock_intersection(hailstones: &Objects, range: i128) -> Object {
     let (rock_velocity_xz, rock_position_xz) = (-range..=range)
         .into_par_iter()
         .find_map_any(|z| {
-            let rock_velocity_xz = (rock_velocity_xy.0, Number::from_integer(z));
+            let rock_velocity_xz = Point::new(rock_velocity_xy.x, Number::from_integer(z));
 
             let rock_position_xz = estimate_rock_position_xz(hailstones, rock_velocity_xz);
 
@@ -217,17 +173,8 @@ fn estimate_rock_intersection(hailstones: &Objects, range: i128) -> Object {
         })
         .expect("rock xz");
 
-    let rock_position = Vector {
-        x: rock_position_xy.x,
-        y: rock_position_xy.y,
-        z: rock_position_xz.y,
-    };
-
-    let rock_velocity = Vector {
-        x: rock_velocity_xy.0,
-        y: rock_velocity_xy.1,
-        z: rock_velocity_xz.1,
-    };
+    let rock_position = Vector::new(rock_position_xy.x, rock_position_xy.y, rock_position_xz.y);
+    let rock_velocity = Vector::new(rock_velocity_xy.x, rock_velocity_xy.y, rock_velocity_xz.y);
 
     Object {
         position: rock_position,
@@ -235,19 +182,13 @@ fn estimate_rock_intersection(hailstones: &Objects, range: i128) -> Object {
     }
 }
 
-fn estimate_rock_position_xy(
-    hailstones: &Objects,
-    (rock_velocity_x, rock_velocity_y): (Number, Number),
-) -> Option<Point> {
+fn estimate_rock_position_xy(hailstones: &Objects, rock_velocity: Point) -> Option<Point> {
     let hailstones: Objects = hailstones
         .iter()
         .map(|hailstone| Object {
             position: hailstone.position,
-            velocity: Vector {
-                x: hailstone.velocity.x - rock_velocity_x,
-                y: hailstone.velocity.y - rock_velocity_y,
-                z: hailstone.velocity.z,
-            },
+            velocity: hailstone.velocity
+                - Vector::new(rock_velocity.x, rock_velocity.y, Number::zero()),
         })
         .collect();
 
@@ -279,19 +220,13 @@ fn estimate_rock_position_xy(
     intersection
 }
 
-fn estimate_rock_position_xz(
-    hailstones: &Objects,
-    (rock_velocity_x, rock_velocity_z): (Number, Number),
-) -> Option<Point> {
+fn estimate_rock_position_xz(hailstones: &Objects, rock_velocity: Point) -> Option<Point> {
     let hailstones: Objects = hailstones
         .iter()
         .map(|hailstone| Object {
             position: hailstone.position,
-            velocity: Vector {
-                x: hailstone.velocity.x - rock_velocity_x,
-                y: hailstone.velocity.y,
-                z: hailstone.velocity.z - rock_velocity_z,
-            },
+            velocity: hailstone.velocity
+                - Vector::new(rock_velocity.x, Number::zero(), rock_velocity.y),
         })
         .collect();
 
@@ -322,3 +257,21 @@ fn estimate_rock_position_xz(
 
     intersection
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_utils::examples! {
+        Day24,
+        example: "day24.txt" => part2 "47";
+    }
+
+    #[test]
+    fn example_test_area() {
+        let hailstones = Day24::parse(&Input::literal(include_str!("../examples/day24.txt")))
+            .expect("hailstones");
+
+        assert_eq!(count_hailstone_intersections(&hailstones, 7, 27), 2);
+    }
+}
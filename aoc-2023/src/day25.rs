@@ -1,3 +1,4 @@
+use aoc_utils::{Input, ParseResult, Solution};
 use rand::prelude::*;
 use rayon::prelude::*;
 use std::collections::HashMap;
@@ -9,27 +10,41 @@ type Diagram = HashMap<Component, Components>;
 
 const COUNT_WIRES: usize = 3;
 
-pub fn run() {
-    let lines: Vec<String> = aoc_utils::read_lines("aoc-2023/inputs/day25.txt", true).collect();
+pub struct Day25;
 
-    let diagram = parse_diagram(&lines);
+impl Solution for Day25 {
+    type Input = Diagram;
+    type Part1 = usize;
+    type Part2 = &'static str;
 
-    let (size_a, size_b) = find_diagram_groups(diagram, COUNT_WIRES);
+    fn parse(input: &Input) -> ParseResult<Self::Input> {
+        let lines: Vec<String> = input.lines(true).collect();
 
-    println!("{}", size_a * size_b);
+        parse_diagram(&lines)
+    }
+
+    fn part1(diagram: &Self::Input) -> Self::Part1 {
+        let (size_a, size_b) = find_diagram_groups(diagram.clone(), COUNT_WIRES);
+
+        size_a * size_b
+    }
+
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        "-"
+    }
 }
 
-fn parse_diagram(lines: &[String]) -> Diagram {
+fn parse_diagram(lines: &[String]) -> ParseResult<Diagram> {
     let mut diagram = Diagram::new();
 
-    for line in lines {
-        let mut parts = line.split(':');
+    for line in aoc_utils::numbered(lines) {
+        let (component, connected_components) = line
+            .split_once(':')
+            .ok_or_else(|| line.error_end("':' after component"))?;
 
-        let component = parts.next().expect("component").trim().to_string();
+        let component = component.trim().to_string();
 
-        let connected_components: Vec<_> = parts
-            .next()
-            .expect("connected components")
+        let connected_components: Vec<_> = connected_components
             .trim()
             .split_ascii_whitespace()
             .map(|component| component.trim().to_string())
@@ -48,7 +63,7 @@ fn parse_diagram(lines: &[String]) -> Diagram {
         }
     }
 
-    diagram
+    Ok(diagram)
 }
 
 fn find_diagram_groups(diagram: Diagram, expected_minimum_cut: usize) -> (usize, usize) {
@@ -141,3 +156,13 @@ fn contract_connection(diagram: &mut Diagram, (component_a, component_b): Connec
     diagram.remove(&component_a);
     diagram.remove(&component_b);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Day25;
+
+    aoc_utils::examples! {
+        Day25,
+        example: "day25.txt" => part1 "54";
+    }
+}
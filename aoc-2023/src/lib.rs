@@ -0,0 +1,63 @@
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+use aoc_utils::{Registry, Year};
+
+pub const YEAR: Year = 2023;
+
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+pub const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+pub fn register(registry: &mut Registry) {
+    registry.register_input_dir(YEAR, INPUT_DIR);
+    registry.register_answers_file(YEAR, ANSWERS_FILE);
+
+    registry.register::<day01::Day01>(YEAR, 1);
+    registry.register::<day02::Day02>(YEAR, 2);
+    registry.register::<day03::Day03>(YEAR, 3);
+    registry.register::<day04::Day04>(YEAR, 4);
+    registry.register::<day05::Day05>(YEAR, 5);
+    registry.register::<day06::Day06>(YEAR, 6);
+    registry.register::<day07::Day07>(YEAR, 7);
+    registry.register::<day08::Day08>(YEAR, 8);
+    registry.register::<day09::Day09>(YEAR, 9);
+    registry.register::<day10::Day10>(YEAR, 10);
+    registry.register::<day11::Day11>(YEAR, 11);
+    registry.register::<day12::Day12>(YEAR, 12);
+    registry.register::<day13::Day13>(YEAR, 13);
+    registry.register::<day14::Day14>(YEAR, 14);
+    registry.register::<day15::Day15>(YEAR, 15);
+    registry.register::<day16::Day16>(YEAR, 16);
+    registry.register::<day17::Day17>(YEAR, 17);
+    registry.register::<day18::Day18>(YEAR, 18);
+    registry.register::<day19::Day19>(YEAR, 19);
+    registry.register::<day20::Day20>(YEAR, 20);
+    registry.register::<day21::Day21>(YEAR, 21);
+    registry.register::<day22::Day22>(YEAR, 22);
+    registry.register::<day23::Day23>(YEAR, 23);
+    registry.register::<day24::Day24>(YEAR, 24);
+    registry.register::<day25::Day25>(YEAR, 25);
+}
@@ -0,0 +1,116 @@
+use crate::registry::Day;
+use crate::solution::Part;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use toml::{Table, Value};
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Read(String, String),
+    Malformed(String, String),
+    Write(String, String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Read(filename, error) => {
+                write!(formatter, "cannot read answers {}: {}", filename, error)
+            }
+            AnswersError::Malformed(filename, error) => {
+                write!(formatter, "malformed answers {}: {}", filename, error)
+            }
+            AnswersError::Write(filename, error) => {
+                write!(formatter, "cannot write answers {}: {}", filename, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// The known answers of a year, stored as `[dayNN]` tables with `part1` and `part2` strings.
+#[derive(Debug)]
+pub struct Answers {
+    filename: String,
+    answers: BTreeMap<(Day, Part), String>,
+}
+
+impl Answers {
+    /// Loads the answers file, treating a missing file as one without answers.
+    pub fn load(filename: &str) -> Result<Self, AnswersError> {
+        let mut answers = Answers {
+            filename: filename.to_string(),
+            answers: BTreeMap::new(),
+        };
+
+        let content = match fs::read_to_string(filename) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(answers),
+            Err(error) => return Err(AnswersError::Read(filename.to_string(), error.to_string())),
+        };
+
+        let malformed =
+            |message: &str| AnswersError::Malformed(filename.to_string(), message.to_string());
+
+        let table: Table = content
+            .parse()
+            .map_err(|error: toml::de::Error| malformed(error.message()))?;
+
+        for (key, parts) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| malformed(&format!("invalid day {}", key)))?;
+
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| malformed(&format!("{} is not a table", key)))?;
+
+            for (key, answer) in parts {
+                let part = match key.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => return Err(malformed(&format!("invalid part {}", key))),
+                };
+
+                let answer = answer
+                    .as_str()
+                    .ok_or_else(|| malformed(&format!("answer {} is not a string", key)))?;
+
+                answers.answers.insert((day, part), answer.to_string());
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|answer| answer.as_str())
+    }
+
+    pub fn insert(&mut self, day: Day, part: Part, answer: &str) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    pub fn save(&self) -> Result<(), AnswersError> {
+        let mut table = Table::new();
+
+        for (&(day, part), answer) in &self.answers {
+            let parts = table
+                .entry(format!("day{:02}", day))
+                .or_insert_with(|| Value::Table(Table::new()));
+
+            if let Value::Table(parts) = parts {
+                parts.insert(format!("part{}", part), Value::String(answer.clone()));
+            }
+        }
+
+        let content = toml::to_string(&table).expect("toml");
+
+        fs::write(&self.filename, content)
+            .map_err(|error| AnswersError::Write(self.filename.clone(), error.to_string()))
+    }
+}
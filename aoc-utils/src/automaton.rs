@@ -0,0 +1,393 @@
+use crate::cycle::fixpoint;
+use crate::grid::{Grid, Offset, NEIGHBORS_4, NEIGHBORS_8};
+use crate::vector::Vec2;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Which cells neighbour which, always listed in the same order.
+pub trait Topology {
+    type Cell: Clone + Eq + Hash;
+
+    /// How many neighbours every cell has.
+    fn degree(&self) -> usize;
+
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell>;
+}
+
+/// Square cells touching by an edge, in the order of [`NEIGHBORS_4`].
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Square4;
+
+/// Square cells touching by an edge or a corner, in the order of [`NEIGHBORS_8`].
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Square8;
+
+/// Hexagonal cells in axial coordinates: east, southeast, southwest, west, northwest and
+/// northeast.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Hex;
+
+/// Hypercubes in `D` dimensions touching by any face, edge or corner.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Cubic<const D: usize>;
+
+impl Hex {
+    pub const OFFSETS: [Vec2<i64>; 6] = [
+        Vec2::new(1, 0),
+        Vec2::new(0, 1),
+        Vec2::new(-1, 1),
+        Vec2::new(-1, 0),
+        Vec2::new(0, -1),
+        Vec2::new(1, -1),
+    ];
+}
+
+fn offset_neighbors(cell: &Vec2<i64>, offsets: &[Offset]) -> Vec<Vec2<i64>> {
+    offsets
+        .iter()
+        .map(|&(x, y)| *cell + Vec2::new(x as i64, y as i64))
+        .collect()
+}
+
+impl Topology for Square4 {
+    type Cell = Vec2<i64>;
+
+    fn degree(&self) -> usize {
+        4
+    }
+
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        offset_neighbors(cell, &NEIGHBORS_4)
+    }
+}
+
+impl Topology for Square8 {
+    type Cell = Vec2<i64>;
+
+    fn degree(&self) -> usize {
+        8
+    }
+
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        offset_neighbors(cell, &NEIGHBORS_8)
+    }
+}
+
+impl Topology for Hex {
+    type Cell = Vec2<i64>;
+
+    fn degree(&self) -> usize {
+        6
+    }
+
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        Hex::OFFSETS.iter().map(|&offset| *cell + offset).collect()
+    }
+}
+
+impl<const D: usize> Topology for Cubic<D> {
+    type Cell = [i64; D];
+
+    fn degree(&self) -> usize {
+        3usize.pow(D as u32) - 1
+    }
+
+    fn neighbors(&self, cell: &Self::Cell) -> Vec<Self::Cell> {
+        (0..3usize.pow(D as u32))
+            .map(|mut index| {
+                let mut neighbor = *cell;
+
+                for value in neighbor.iter_mut() {
+                    *value += (index % 3) as i64 - 1;
+                    index /= 3;
+                }
+
+                neighbor
+            })
+            .filter(|neighbor| neighbor != cell)
+            .collect()
+    }
+}
+
+/// How a cell changes, from its own state and its neighbours' in topology order. Sparse
+/// automata have two states, alive or dead.
+pub trait Rule<S: Copy = bool> {
+    fn next(&self, cell: S, neighbors: &[S]) -> S;
+}
+
+impl<S: Copy, F: Fn(S, &[S]) -> S> Rule<S> for F {
+    fn next(&self, cell: S, neighbors: &[S]) -> S {
+        self(cell, neighbors)
+    }
+}
+
+/// A Life-like rule: dead cells come alive with a `birth` count of live neighbours and live
+/// cells stay alive with a `survival` count.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LifeRule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl LifeRule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        LifeRule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    /// Conway's Game of Life, B3/S23.
+    pub fn conway() -> Self {
+        LifeRule::new(&[3], &[2, 3])
+    }
+}
+
+impl Rule for LifeRule {
+    fn next(&self, alive: bool, neighbors: &[bool]) -> bool {
+        let count = neighbors.iter().filter(|&&alive| alive).count();
+
+        match alive {
+            true => self.survival.contains(&count),
+            false => self.birth.contains(&count),
+        }
+    }
+}
+
+/// A two-state automaton on an unbounded topology. Only the cells that differ from the
+/// background are stored, so the background may itself switch on and off between steps.
+#[derive(Debug, Clone)]
+pub struct Sparse<T: Topology> {
+    topology: T,
+    cells: HashSet<T::Cell>,
+    background: bool,
+}
+
+impl<T: Topology> Sparse<T> {
+    /// Starts with the `alive` cells on a dead background.
+    pub fn new(topology: T, alive: impl IntoIterator<Item = T::Cell>) -> Self {
+        Sparse {
+            topology,
+            cells: alive.into_iter().collect(),
+            background: false,
+        }
+    }
+
+    pub fn is_alive(&self, cell: &T::Cell) -> bool {
+        self.cells.contains(cell) != self.background
+    }
+
+    /// The state of every cell not listed by [`Sparse::exceptions`].
+    pub fn background(&self) -> bool {
+        self.background
+    }
+
+    /// The cells whose state differs from the background.
+    pub fn exceptions(&self) -> impl Iterator<Item = &T::Cell> {
+        self.cells.iter()
+    }
+
+    /// Number of live cells, `None` while the background is alive and there are infinitely many.
+    pub fn population(&self) -> Option<usize> {
+        match self.background {
+            true => None,
+            false => Some(self.cells.len()),
+        }
+    }
+
+    pub fn step(&mut self, rule: &impl Rule) {
+        let background = rule.next(
+            self.background,
+            &vec![self.background; self.topology.degree()],
+        );
+
+        let candidates: HashSet<T::Cell> = self
+            .cells
+            .iter()
+            .flat_map(|cell| self.topology.neighbors(cell))
+            .chain(self.cells.iter().cloned())
+            .collect();
+
+        let cells = candidates
+            .into_iter()
+            .filter(|cell| {
+                let neighbors: Vec<bool> = self
+                    .topology
+                    .neighbors(cell)
+                    .iter()
+                    .map(|neighbor| self.is_alive(neighbor))
+                    .collect();
+
+                rule.next(self.is_alive(cell), &neighbors) != background
+            })
+            .collect();
+
+        self.cells = cells;
+        self.background = background;
+    }
+
+    pub fn run(&mut self, rule: &impl Rule, steps: usize) {
+        for _ in 0..steps {
+            self.step(rule);
+        }
+    }
+}
+
+/// A many-state automaton on a bounded grid. Neighbours past an edge are either left out, so
+/// the rule sees fewer of them, or wrapped around to the opposite edge.
+#[derive(Debug, Clone)]
+pub struct Dense<T: Topology<Cell = Vec2<i64>>, S> {
+    topology: T,
+    grid: Grid<S>,
+    wrapping: bool,
+}
+
+impl<T: Topology<Cell = Vec2<i64>>, S: Copy + Eq> Dense<T, S> {
+    /// Leaves out neighbours past the edges.
+    pub fn new(topology: T, grid: Grid<S>) -> Self {
+        Dense {
+            topology,
+            grid,
+            wrapping: false,
+        }
+    }
+
+    /// Wraps neighbours past an edge around to the opposite one.
+    pub fn wrapping(topology: T, grid: Grid<S>) -> Self {
+        Dense {
+            topology,
+            grid,
+            wrapping: true,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<S> {
+        &self.grid
+    }
+
+    /// Steps every cell at once, returning whether any of them changed.
+    pub fn step(&mut self, rule: &impl Rule<S>) -> bool {
+        let grid = self.next(&self.grid, rule);
+        let changed = grid != self.grid;
+
+        self.grid = grid;
+
+        changed
+    }
+
+    pub fn run(&mut self, rule: &impl Rule<S>, steps: usize) {
+        for _ in 0..steps {
+            self.step(rule);
+        }
+    }
+
+    /// Steps until nothing changes, returning how many steps changed something.
+    pub fn settle(&mut self, rule: &impl Rule<S>) -> usize {
+        let (grid, steps) = fixpoint(self.grid.clone(), |grid| self.next(grid, rule));
+
+        self.grid = grid;
+
+        steps
+    }
+
+    fn next(&self, grid: &Grid<S>, rule: &impl Rule<S>) -> Grid<S> {
+        grid.map(|(x, y), &cell| {
+            let neighbors: Vec<S> = self
+                .topology
+                .neighbors(&Vec2::new(x as i64, y as i64))
+                .into_iter()
+                .filter_map(|neighbor| self.get(grid, neighbor))
+                .collect();
+
+            rule.next(cell, &neighbors)
+        })
+    }
+
+    fn get(&self, grid: &Grid<S>, cell: Vec2<i64>) -> Option<S> {
+        let offset = (cell.x as isize, cell.y as isize);
+
+        match self.wrapping {
+            true => Some(*grid.get_wrapped(offset)),
+            false => grid.offset((0, 0), offset).map(|position| grid[position]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinker_oscillates() {
+        let horizontal = [(-1, 0), (0, 0), (1, 0)].map(Vec2::from);
+        let mut life = Sparse::new(Square8, horizontal);
+
+        life.step(&LifeRule::conway());
+
+        let mut cells: Vec<_> = life.exceptions().copied().collect();
+
+        cells.sort();
+
+        assert_eq!(cells, [(0, -1), (0, 0), (0, 1)].map(Vec2::from));
+        assert_eq!(
+            Square8.neighbors(&Vec2::new(0, 0)),
+            NEIGHBORS_8.map(|(x, y)| Vec2::new(x as i64, y as i64))
+        );
+
+        life.step(&LifeRule::conway());
+
+        assert!(horizontal.iter().all(|cell| life.is_alive(cell)));
+        assert_eq!(life.population(), Some(3));
+    }
+
+    #[test]
+    fn degrees_match_neighbors() {
+        assert_eq!(Cubic::<4>.neighbors(&[0; 4]).len(), Cubic::<4>.degree());
+        assert_eq!(Hex.neighbors(&Vec2::new(0, 0)).len(), 6);
+        assert_eq!(Square4.neighbors(&Vec2::new(0, 0)).len(), 4);
+    }
+
+    #[test]
+    fn background_flips() {
+        let invert = |alive: bool, _: &[bool]| !alive;
+        let mut sparse = Sparse::new(Square4, [Vec2::new(0, 0)]);
+
+        sparse.step(&invert);
+
+        assert!(sparse.background());
+        assert!(!sparse.is_alive(&Vec2::new(0, 0)));
+        assert!(sparse.is_alive(&Vec2::new(5, 5)));
+        assert_eq!(sparse.population(), None);
+
+        sparse.step(&invert);
+
+        assert_eq!(sparse.population(), Some(1));
+    }
+
+    #[test]
+    fn dense_edges_drop_or_wrap() {
+        let sum = |cell: i32, neighbors: &[i32]| cell + neighbors.iter().sum::<i32>();
+        let row = Grid::from_rows(vec![vec![1, 2, 3, 4]]);
+
+        let mut bounded = Dense::new(Square4, row.clone());
+
+        assert!(bounded.step(&sum));
+        assert_eq!(bounded.grid().row(0), [3, 6, 9, 7]);
+
+        let mut wrapped = Dense::wrapping(Square4, row);
+
+        wrapped.step(&sum);
+
+        assert_eq!(wrapped.grid().row(0), [9, 10, 15, 16]);
+    }
+
+    #[test]
+    fn dense_settles() {
+        let spread = |cell: bool, neighbors: &[bool]| cell || neighbors.iter().any(|&on| on);
+        let grid = Grid::from_rows(vec![vec![true, false, false, false]]);
+        let mut dense = Dense::new(Square8, grid);
+
+        assert_eq!(dense.settle(&spread), 3);
+        assert!(dense.grid().values().all(|&on| on));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::input::Input;
+use crate::parse::ParseResult;
+use crate::solution::Solution;
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// The durations of every timed run of a day, per phase.
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Times parsing and both parts separately, after one untimed warm-up run.
+pub fn bench<S: Solution>(input: &Input, runs: usize) -> ParseResult<Timings> {
+    let mut timings = Timings::default();
+
+    for run in 0..=runs {
+        let start = Instant::now();
+        let parsed = S::parse(input).map_err(|error| error.in_file(input.name()))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        hint::black_box(S::part1(&parsed));
+        let part1 = start.elapsed();
+
+        let start = Instant::now();
+        hint::black_box(S::part2(&parsed));
+        let part2 = start.elapsed();
+
+        if run > 0 {
+            timings.parse.push(parse);
+            timings.part1.push(part1);
+            timings.part2.push(part2);
+        }
+    }
+
+    Ok(timings)
+}
@@ -0,0 +1,219 @@
+/// An axis-aligned box of integer cells, spanning `min[axis]..max[axis]` on every axis.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BoxN<const D: usize> {
+    pub min: [i64; D],
+    pub max: [i64; D],
+}
+
+impl<const D: usize> BoxN<D> {
+    pub fn new(min: [i64; D], max: [i64; D]) -> Self {
+        BoxN { min, max }
+    }
+
+    /// The box whose corner cells are `min` and `max`, both included.
+    pub fn from_inclusive(min: [i64; D], max: [i64; D]) -> Self {
+        BoxN::new(min, max.map(|value| value + 1))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..D).any(|axis| self.min[axis] >= self.max[axis])
+    }
+
+    /// Number of cells inside, zero when empty.
+    pub fn volume(&self) -> i64 {
+        (0..D)
+            .map(|axis| (self.max[axis] - self.min[axis]).max(0))
+            .product()
+    }
+
+    pub fn contains(&self, point: [i64; D]) -> bool {
+        (0..D).all(|axis| self.min[axis] <= point[axis] && point[axis] < self.max[axis])
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = BoxN {
+            min: std::array::from_fn(|axis| self.min[axis].max(other.min[axis])),
+            max: std::array::from_fn(|axis| self.max[axis].min(other.max[axis])),
+        };
+
+        match intersection.is_empty() {
+            true => None,
+            false => Some(intersection),
+        }
+    }
+
+    /// The cells of this box outside `other`, as at most `2 * D` disjoint boxes.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return match self.is_empty() {
+                true => vec![],
+                false => vec![*self],
+            };
+        };
+
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+
+        for axis in 0..D {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut below = rest;
+
+                below.max[axis] = overlap.min[axis];
+                rest.min[axis] = overlap.min[axis];
+                pieces.push(below);
+            }
+
+            if overlap.max[axis] < rest.max[axis] {
+                let mut above = rest;
+
+                above.min[axis] = overlap.max[axis];
+                rest.max[axis] = overlap.max[axis];
+                pieces.push(above);
+            }
+        }
+
+        pieces
+    }
+
+    pub fn translate(&self, offset: [i64; D]) -> Self {
+        BoxN {
+            min: std::array::from_fn(|axis| self.min[axis] + offset[axis]),
+            max: std::array::from_fn(|axis| self.max[axis] + offset[axis]),
+        }
+    }
+}
+
+/// A union of boxes kept as disjoint pieces, so its volume is a plain sum.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct BoxSet<const D: usize> {
+    boxes: Vec<BoxN<D>>,
+}
+
+impl<const D: usize> BoxSet<D> {
+    pub fn new() -> Self {
+        BoxSet { boxes: Vec::new() }
+    }
+
+    pub fn boxes(&self) -> &[BoxN<D>] {
+        &self.boxes
+    }
+
+    pub fn insert(&mut self, added: BoxN<D>) {
+        self.remove(&added);
+
+        if !added.is_empty() {
+            self.boxes.push(added);
+        }
+    }
+
+    pub fn remove(&mut self, removed: &BoxN<D>) {
+        self.boxes = self
+            .boxes
+            .iter()
+            .flat_map(|piece| match piece.intersects(removed) {
+                true => piece.subtract(removed),
+                false => vec![*piece],
+            })
+            .collect();
+    }
+
+    pub fn contains(&self, point: [i64; D]) -> bool {
+        self.boxes.iter().any(|piece| piece.contains(point))
+    }
+
+    pub fn volume(&self) -> i64 {
+        self.boxes.iter().map(BoxN::volume).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn intersection_and_volume() {
+        let a = BoxN::new([0, 0, 0], [4, 4, 4]);
+        let b = BoxN::new([2, 3, -1], [6, 5, 1]);
+
+        assert_eq!(a.intersection(&b), Some(BoxN::new([2, 3, 0], [4, 4, 1])));
+        assert_eq!(a.volume(), 64);
+        assert!(!a.intersects(&BoxN::new([4, 0, 0], [5, 1, 1])));
+    }
+
+    #[test]
+    fn subtract_leaves_disjoint_pieces() {
+        let a = BoxN::new([0, 0], [5, 5]);
+        let hole = BoxN::new([1, 1], [3, 3]);
+        let pieces = a.subtract(&hole);
+
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces.iter().map(BoxN::volume).sum::<i64>(), 21);
+
+        for (i, first) in pieces.iter().enumerate() {
+            assert!(pieces[i + 1..]
+                .iter()
+                .all(|second| !first.intersects(second)));
+        }
+    }
+
+    fn cells(cuboid: &BoxN<3>) -> Vec<[i64; 3]> {
+        (cuboid.min[0]..cuboid.max[0])
+            .flat_map(|x| (cuboid.min[1]..cuboid.max[1]).map(move |y| (x, y)))
+            .flat_map(|(x, y)| (cuboid.min[2]..cuboid.max[2]).map(move |z| [x, y, z]))
+            .collect()
+    }
+
+    fn steps() -> impl Strategy<Value = Vec<(bool, BoxN<3>)>> {
+        let corner = || prop::array::uniform3(-4i64..4);
+        let size = || prop::array::uniform3(0i64..5);
+
+        prop::collection::vec(
+            (any::<bool>(), corner(), size()).prop_map(|(on, min, size)| {
+                let max = [min[0] + size[0], min[1] + size[1], min[2] + size[2]];
+
+                (on, BoxN::new(min, max))
+            }),
+            0..8,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn set_counts_cells_against_brute_force(steps in steps()) {
+            let mut set = BoxSet::new();
+            let mut lit = HashSet::new();
+
+            for (on, cuboid) in steps {
+                match on {
+                    true => {
+                        set.insert(cuboid);
+                        lit.extend(cells(&cuboid));
+                    }
+                    false => {
+                        set.remove(&cuboid);
+
+                        for cell in cells(&cuboid) {
+                            lit.remove(&cell);
+                        }
+                    }
+                }
+            }
+
+            prop_assert_eq!(set.volume(), lit.len() as i64);
+
+            for (i, first) in set.boxes().iter().enumerate() {
+                prop_assert!(set.boxes()[i + 1..].iter().all(|second| !first.intersects(second)));
+            }
+
+            for &cell in &lit {
+                prop_assert!(set.contains(cell));
+            }
+        }
+    }
+}
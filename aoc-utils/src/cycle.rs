@@ -0,0 +1,124 @@
+/// Where the sequence `initial, step(initial), ...` starts repeating: the states at
+/// `offset..offset + period` loop forever.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first index whose state equals the state after `steps` steps.
+    pub fn reduce(&self, steps: usize) -> usize {
+        if steps < self.offset {
+            steps
+        } else {
+            self.offset + (steps - self.offset) % self.period
+        }
+    }
+
+    /// Whether the sequence settles on a single state.
+    pub fn is_fixpoint(&self) -> bool {
+        self.period == 1
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only two states in memory. The sequence
+/// must eventually repeat.
+pub fn find_cycle<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+
+    let mut offset = 0;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    Cycle { offset, period }
+}
+
+/// The state after `steps` steps, skipping whole periods once the sequence repeats.
+pub fn state_after<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S, steps: usize) -> S {
+    let cycle = find_cycle(initial, &mut step);
+    let mut state = initial.clone();
+
+    for _ in 0..cycle.reduce(steps) {
+        state = step(&state);
+    }
+
+    state
+}
+
+/// Steps until the state stops changing, returning it with the number of steps that changed it.
+pub fn fixpoint<S: Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> (S, usize) {
+    let mut state = initial;
+    let mut steps = 0;
+
+    loop {
+        let next = step(&state);
+
+        if next == state {
+            return (state, steps);
+        }
+
+        state = next;
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rho(value: &u32) -> u32 {
+        match value {
+            0..=4 => value + 1,
+            _ => 2,
+        }
+    }
+
+    #[test]
+    fn finds_offset_and_period() {
+        assert_eq!(
+            find_cycle(&0, rho),
+            Cycle {
+                offset: 2,
+                period: 4
+            }
+        );
+    }
+
+    #[test]
+    fn skips_to_far_steps() {
+        assert_eq!(state_after(&0, rho, 1_000_000_001), 5);
+        assert_eq!(state_after(&0, rho, 1), 1);
+    }
+
+    #[test]
+    fn fixpoint_counts_changing_steps() {
+        assert_eq!(fixpoint(20, |value| value / 2), (0, 5));
+        assert!(find_cycle(&20, |value| value / 2).is_fixpoint());
+    }
+}
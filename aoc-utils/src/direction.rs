@@ -0,0 +1,92 @@
+use crate::grid::Offset;
+use crate::vector::Vec2;
+use num::Signed;
+
+/// A cardinal direction on a grid with `y` growing downwards.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up, matching [`NEIGHBORS_4`](crate::NEIGHBORS_4).
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// The step to the neighbouring grid cell.
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+impl<T: Signed> From<Direction> for Vec2<T> {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Vec2::new(T::zero(), -T::one()),
+            Direction::Right => Vec2::new(T::one(), T::zero()),
+            Direction::Down => Vec2::new(T::zero(), T::one()),
+            Direction::Left => Vec2::new(-T::one(), T::zero()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::NEIGHBORS_4;
+
+    #[test]
+    fn turns_compose() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_ne!(direction.turn_left(), direction.turn_right());
+        }
+    }
+
+    #[test]
+    fn offsets_match_neighbors() {
+        let offsets: Vec<Offset> = Direction::ALL.map(Direction::offset).to_vec();
+
+        assert_eq!(offsets, NEIGHBORS_4);
+        assert_eq!(Vec2::<i32>::from(Direction::Left), Vec2::new(-1, 0));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::input::Input;
+use crate::solution::{self, Part, Solution};
+
+/// Solves `part` of a worked example and asserts that it gives the expected answer.
+pub fn check_example<S: Solution>(input: &str, part: Part, expected: &str) {
+    match solution::solve::<S>(&Input::literal(input), &[part]) {
+        Ok(answers) => assert_eq!(answers[0], expected, "part {}", part),
+        Err(error) => panic!("example does not parse: {}", error),
+    }
+}
+
+/// Generates a `#[test]` per worked example of a day. Examples are read from the
+/// `examples` directory of the calling crate and checked against the listed answers.
+///
+/// ```ignore
+/// aoc_utils::examples! {
+///     Day08,
+///     example: "day08.txt" => part1 "6";
+///     example_ghosts: "day08-2.txt" => part2 "6";
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    (@part part1) => {
+        $crate::Part::One
+    };
+    (@part part2) => {
+        $crate::Part::Two
+    };
+    ($solution:ty, $($name:ident: $file:literal => $($part:ident $answer:literal),+;)+) => {
+        $(
+            #[test]
+            fn $name() {
+                let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/", $file));
+
+                $(
+                    $crate::check_example::<$solution>(input, $crate::examples!(@part $part), $answer);
+                )+
+            }
+        )+
+    };
+}
@@ -0,0 +1,376 @@
+use std::fmt::{Display, Formatter};
+
+pub type Value = i128;
+
+/// Where an expression stopped making sense, as a byte offset into its text.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExpressionError {
+    pub offset: usize,
+    pub expected: &'static str,
+}
+
+impl ExpressionError {
+    fn new(offset: usize, expected: &'static str) -> Self {
+        ExpressionError { offset, expected }
+    }
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{}: expected {}", self.offset, self.expected)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOperator {
+    fn symbol(self) -> char {
+        match self {
+            BinaryOperator::Add => '+',
+            BinaryOperator::Sub => '-',
+            BinaryOperator::Mul => '*',
+            BinaryOperator::Div => '/',
+            BinaryOperator::Rem => '%',
+        }
+    }
+
+    fn apply(self, a: Value, b: Value) -> Option<Value> {
+        match self {
+            BinaryOperator::Add => a.checked_add(b),
+            BinaryOperator::Sub => a.checked_sub(b),
+            BinaryOperator::Mul => a.checked_mul(b),
+            BinaryOperator::Div => a.checked_div(b),
+            BinaryOperator::Rem => a.checked_rem(b),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Token {
+    Number(Value),
+    Operator(BinaryOperator),
+    Open,
+    Close,
+}
+
+/// Splits text into tokens with their byte offsets, skipping whitespace.
+pub fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((offset, char)) = chars.next() {
+        let token = match char {
+            ' ' | '\t' => continue,
+            '+' => Token::Operator(BinaryOperator::Add),
+            '-' => Token::Operator(BinaryOperator::Sub),
+            '*' => Token::Operator(BinaryOperator::Mul),
+            '/' => Token::Operator(BinaryOperator::Div),
+            '%' => Token::Operator(BinaryOperator::Rem),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '0'..='9' => {
+                let mut end = offset + 1;
+
+                while let Some(&(next, '0'..='9')) = chars.peek() {
+                    end = next + 1;
+                    chars.next();
+                }
+
+                let value = text[offset..end]
+                    .parse()
+                    .map_err(|_| ExpressionError::new(offset, "smaller number"))?;
+
+                Token::Number(value)
+            }
+            _ => return Err(ExpressionError::new(offset, "number, operator or bracket")),
+        };
+
+        tokens.push((offset, token));
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// Which operators an expression may use and how tightly each binds. Higher levels bind
+/// tighter; operators missing from the table are rejected.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperatorTable {
+    binary: Vec<(BinaryOperator, u8, Associativity)>,
+    negation: Option<u8>,
+}
+
+impl OperatorTable {
+    pub fn new(binary: &[(BinaryOperator, u8, Associativity)], negation: Option<u8>) -> Self {
+        OperatorTable {
+            binary: binary.to_vec(),
+            negation,
+        }
+    }
+
+    /// The usual rules: negation, then `* / %`, then `+ -`, all left-associative.
+    pub fn arithmetic() -> Self {
+        use Associativity::Left;
+        use BinaryOperator::*;
+
+        OperatorTable::new(
+            &[
+                (Mul, 2, Left),
+                (Div, 2, Left),
+                (Rem, 2, Left),
+                (Add, 1, Left),
+                (Sub, 1, Left),
+            ],
+            Some(3),
+        )
+    }
+
+    fn binary(&self, operator: BinaryOperator) -> Option<(u8, Associativity)> {
+        self.binary
+            .iter()
+            .find(|&&(known, _, _)| known == operator)
+            .map(|&(_, level, associativity)| (level, associativity))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expr {
+    Number(Value),
+    Negate(Box<Expr>),
+    Binary(BinaryOperator, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn parse(text: &str, table: &OperatorTable) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(text)?;
+
+        let mut parser = Parser {
+            tokens: &tokens,
+            index: 0,
+            end: text.len(),
+            table,
+        };
+
+        let expr = parser.expression(0)?;
+
+        match parser.peek() {
+            Some((offset, _)) => Err(ExpressionError::new(offset, "operator")),
+            None => Ok(expr),
+        }
+    }
+
+    /// The value, `None` on overflow or division by zero.
+    pub fn evaluate(&self) -> Option<Value> {
+        match self {
+            &Expr::Number(value) => Some(value),
+            Expr::Negate(expr) => expr.evaluate()?.checked_neg(),
+            Expr::Binary(operator, a, b) => operator.apply(a.evaluate()?, b.evaluate()?),
+        }
+    }
+}
+
+/// Prints the expression fully parenthesised, so the grouping chosen by the table shows.
+impl Display for Expr {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Number(value) => write!(formatter, "{}", value),
+            Expr::Negate(expr) => write!(formatter, "(-{})", expr),
+            Expr::Binary(operator, a, b) => {
+                write!(formatter, "({} {} {})", a, operator.symbol(), b)
+            }
+        }
+    }
+}
+
+/// A Pratt parser over a token list.
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    index: usize,
+    end: usize,
+    table: &'a OperatorTable,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<(usize, Token)> {
+        self.tokens.get(self.index).copied()
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<(usize, Token), ExpressionError> {
+        let token = self
+            .peek()
+            .ok_or(ExpressionError::new(self.end, expected))?;
+
+        self.index += 1;
+
+        Ok(token)
+    }
+
+    /// Parses operands joined by operators of at least level `min`.
+    fn expression(&mut self, min: u8) -> Result<Expr, ExpressionError> {
+        let mut left = self.operand()?;
+
+        while let Some((offset, token)) = self.peek() {
+            let operator = match token {
+                Token::Operator(operator) => operator,
+                Token::Close => break,
+                Token::Number(_) | Token::Open => {
+                    return Err(ExpressionError::new(offset, "operator"))
+                }
+            };
+
+            let (level, associativity) = self
+                .table
+                .binary(operator)
+                .ok_or(ExpressionError::new(offset, "operator in the table"))?;
+
+            if level < min {
+                break;
+            }
+
+            self.index += 1;
+
+            let right = match associativity {
+                Associativity::Left => match level.checked_add(1) {
+                    Some(level) => self.expression(level)?,
+                    None => self.operand()?,
+                },
+                Associativity::Right => self.expression(level)?,
+            };
+
+            left = Expr::Binary(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Expr, ExpressionError> {
+        let expected = "number, '(' or '-'";
+
+        match self.next(expected)? {
+            (_, Token::Number(value)) => Ok(Expr::Number(value)),
+            (_, Token::Open) => {
+                let expr = self.expression(0)?;
+
+                match self.next("')'")? {
+                    (_, Token::Close) => Ok(expr),
+                    (offset, _) => Err(ExpressionError::new(offset, "')'")),
+                }
+            }
+            (offset, Token::Operator(BinaryOperator::Sub)) => {
+                let level = self
+                    .table
+                    .negation
+                    .ok_or(ExpressionError::new(offset, expected))?;
+
+                Ok(Expr::Negate(Box::new(self.expression(level)?)))
+            }
+            (offset, _) => Err(ExpressionError::new(offset, expected)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn show(text: &str, table: &OperatorTable) -> String {
+        Expr::parse(text, table).expect("expression").to_string()
+    }
+
+    #[test]
+    fn tokenizes_multi_digit_numbers() {
+        assert_eq!(
+            tokenize("12*(305)").expect("tokens"),
+            [
+                (0, Token::Number(12)),
+                (2, Token::Operator(BinaryOperator::Mul)),
+                (3, Token::Open),
+                (4, Token::Number(305)),
+                (7, Token::Close),
+            ]
+        );
+        assert_eq!(
+            tokenize("1 ^ 2"),
+            Err(ExpressionError::new(2, "number, operator or bracket"))
+        );
+    }
+
+    #[test]
+    fn follows_the_table() {
+        let arithmetic = OperatorTable::arithmetic();
+
+        assert_eq!(
+            show("2 * 3 + 4 * -5", &arithmetic),
+            "((2 * 3) + (4 * (-5)))"
+        );
+        assert_eq!(show("-2 - -3 % 2", &arithmetic), "((-2) - ((-3) % 2))");
+        assert_eq!(
+            Expr::parse("17 / 5 - 10", &arithmetic)
+                .expect("expr")
+                .evaluate(),
+            Some(-7)
+        );
+
+        let right = OperatorTable::new(&[(BinaryOperator::Sub, 1, Associativity::Right)], None);
+
+        assert_eq!(show("8 - 3 - 2", &right), "(8 - (3 - 2))");
+
+        let highest =
+            OperatorTable::new(&[(BinaryOperator::Sub, u8::MAX, Associativity::Left)], None);
+
+        assert_eq!(show("8 - 3 - 2", &highest), "((8 - 3) - 2)");
+        assert_eq!(
+            Expr::parse("8 - 3 - 2", &right).expect("expr").evaluate(),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let arithmetic = OperatorTable::arithmetic();
+        let additive = OperatorTable::new(&[(BinaryOperator::Add, 1, Associativity::Left)], None);
+
+        assert_eq!(
+            Expr::parse("2 +", &arithmetic),
+            Err(ExpressionError::new(3, "number, '(' or '-'"))
+        );
+        assert_eq!(
+            Expr::parse("2 3", &arithmetic),
+            Err(ExpressionError::new(2, "operator"))
+        );
+        assert_eq!(
+            Expr::parse("(1 + 2", &arithmetic),
+            Err(ExpressionError::new(6, "')'"))
+        );
+        assert_eq!(
+            Expr::parse("1 + 2)", &arithmetic),
+            Err(ExpressionError::new(5, "operator"))
+        );
+        assert_eq!(
+            Expr::parse("1 * 2", &additive),
+            Err(ExpressionError::new(2, "operator in the table"))
+        );
+        assert_eq!(
+            Expr::parse("-1", &additive),
+            Err(ExpressionError::new(0, "number, '(' or '-'"))
+        );
+        assert_eq!(
+            Expr::parse("1 % 0", &arithmetic).expect("expr").evaluate(),
+            None
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use num::Integer;
+
+/// A lattice point as `(x, y)`.
+pub type Point = (i64, i64);
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Containment {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Consecutive vertices of a closed polygon, the last joined back to the first.
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the area enclosed by `vertices`, by the shoelace formula. Doubled so it stays exact
+/// for lattice polygons.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|((x, y), (next_x, next_y))| x * next_y - next_x * y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Lattice points on the edges of the polygon, vertices included.
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|((x, y), (next_x, next_y))| (next_x - x).gcd(&(next_y - y)))
+        .sum()
+}
+
+/// Lattice points strictly inside the polygon, by Pick's theorem `A = i + b / 2 - 1`.
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices)) / 2 + 1
+}
+
+/// Whether `point` is inside, on the edge of or outside the polygon, by ray casting.
+pub fn locate(vertices: &[Point], (x, y): Point) -> Containment {
+    let mut inside = false;
+
+    for ((x1, y1), (x2, y2)) in edges(vertices) {
+        let cross = (x2 - x1) * (y - y1) - (x - x1) * (y2 - y1);
+        let within = x1.min(x2) <= x && x <= x1.max(x2) && y1.min(y2) <= y && y <= y1.max(y2);
+
+        if cross == 0 && within {
+            return Containment::Boundary;
+        }
+
+        if (y1 > y) != (y2 > y) && (cross > 0) == (y2 > y1) {
+            inside = !inside;
+        }
+    }
+
+    match inside {
+        true => Containment::Inside,
+        false => Containment::Outside,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Point; 4] = [(0, 0), (4, 0), (4, 4), (0, 4)];
+    const TRIANGLE: [Point; 3] = [(0, 0), (4, 0), (0, 3)];
+
+    #[test]
+    fn area_and_lattice_points() {
+        assert_eq!(double_area(&SQUARE), 32);
+        assert_eq!(boundary_points(&SQUARE), 16);
+        assert_eq!(interior_points(&SQUARE), 9);
+
+        assert_eq!(double_area(&TRIANGLE), 12);
+        assert_eq!(boundary_points(&TRIANGLE), 8);
+        assert_eq!(interior_points(&TRIANGLE), 3);
+    }
+
+    #[test]
+    fn orientation_does_not_matter() {
+        let reversed: Vec<Point> = SQUARE.iter().rev().copied().collect();
+
+        assert_eq!(double_area(&reversed), 32);
+    }
+
+    #[test]
+    fn locates_points() {
+        assert_eq!(locate(&TRIANGLE, (1, 1)), Containment::Inside);
+        assert_eq!(locate(&TRIANGLE, (2, 0)), Containment::Boundary);
+        assert_eq!(locate(&TRIANGLE, (0, 3)), Containment::Boundary);
+        assert_eq!(locate(&TRIANGLE, (3, 2)), Containment::Outside);
+        assert_eq!(locate(&SQUARE, (2, 5)), Containment::Outside);
+        assert_eq!(locate(&SQUARE, (-1, 4)), Containment::Outside);
+    }
+}
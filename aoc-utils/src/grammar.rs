@@ -0,0 +1,415 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Symbol {
+    Terminal(String),
+    Rule(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum Compiled {
+    Terminal(String),
+    Rule(usize),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Rule {
+    name: String,
+    alternatives: Vec<Vec<Compiled>>,
+}
+
+/// A context-free grammar of named rules, each a list of alternatives. Any rule may refer to
+/// any other, itself included, and terminals are arbitrary strings.
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    rules: Vec<Rule>,
+    index: HashMap<String, usize>,
+}
+
+/// How a rule matched a piece of text.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Derivation {
+    Terminal(String),
+    Rule(String, Vec<Derivation>),
+}
+
+/// Prints the tree as `rule(child child ...)` with terminals quoted.
+impl Display for Derivation {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Derivation::Terminal(terminal) => write!(formatter, "{:?}", terminal),
+            Derivation::Rule(name, children) => {
+                write!(formatter, "{}(", name)?;
+
+                for (index, child) in children.iter().enumerate() {
+                    if index > 0 {
+                        write!(formatter, " ")?;
+                    }
+
+                    write!(formatter, "{}", child)?;
+                }
+
+                write!(formatter, ")")
+            }
+        }
+    }
+}
+
+/// An Earley item: alternative `alternative` of `rule`, matched up to `dot` from `origin`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Item {
+    rule: usize,
+    alternative: usize,
+    dot: usize,
+    origin: usize,
+}
+
+impl Grammar {
+    pub fn new() -> Self {
+        Grammar::default()
+    }
+
+    /// Defines `name`, replacing any earlier definition. Rules it refers to may come later.
+    pub fn insert(&mut self, name: &str, alternatives: Vec<Vec<Symbol>>) {
+        let alternatives = alternatives
+            .into_iter()
+            .map(|symbols| {
+                symbols
+                    .into_iter()
+                    .map(|symbol| match symbol {
+                        Symbol::Terminal(terminal) => Compiled::Terminal(terminal),
+                        Symbol::Rule(name) => Compiled::Rule(self.intern(&name)),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let rule = self.intern(name);
+
+        self.rules[rule].alternatives = alternatives;
+    }
+
+    /// Rules that are referred to but were never defined.
+    pub fn undefined(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.alternatives.is_empty())
+            .map(|rule| rule.name.as_str())
+            .collect()
+    }
+
+    /// Whether the whole of `text` derives from the rule `start`.
+    pub fn matches(&self, start: &str, text: &str) -> bool {
+        self.index
+            .get(start)
+            .is_some_and(|&start| Chart::new(self, start, text).accepts(start))
+    }
+
+    /// A derivation of the whole of `text` from the rule `start`, if there is one.
+    pub fn derive(&self, start: &str, text: &str) -> Option<Derivation> {
+        let &start = self.index.get(start)?;
+        let chart = Chart::new(self, start, text);
+
+        chart.build(start, 0, text.len(), &mut HashSet::new())
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.index.get(name) {
+            return index;
+        }
+
+        self.rules.push(Rule {
+            name: name.to_string(),
+            alternatives: Vec::new(),
+        });
+        self.index.insert(name.to_string(), self.rules.len() - 1);
+
+        self.rules.len() - 1
+    }
+
+    /// Rules that can match the empty string.
+    fn nullable(&self) -> Vec<bool> {
+        let mut nullable = vec![false; self.rules.len()];
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for (index, rule) in self.rules.iter().enumerate() {
+                let empty = rule.alternatives.iter().any(|symbols| {
+                    symbols.iter().all(|symbol| match symbol {
+                        Compiled::Terminal(terminal) => terminal.is_empty(),
+                        &Compiled::Rule(rule) => nullable[rule],
+                    })
+                });
+
+                if empty && !nullable[index] {
+                    nullable[index] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        nullable
+    }
+}
+
+/// The Earley chart of a text parsed from one start rule: the items alive at every byte
+/// offset, and every completed `(rule, alternative, start, end)` match.
+struct Chart<'a> {
+    grammar: &'a Grammar,
+    text: &'a str,
+    sets: Vec<Vec<Item>>,
+    completed: HashSet<(usize, usize, usize, usize)>,
+    spans: HashSet<(usize, usize, usize)>,
+}
+
+impl<'a> Chart<'a> {
+    fn new(grammar: &'a Grammar, start: usize, text: &'a str) -> Self {
+        let nullable = grammar.nullable();
+        let mut chart = Chart {
+            grammar,
+            text,
+            sets: vec![Vec::new(); text.len() + 1],
+            completed: HashSet::new(),
+            spans: HashSet::new(),
+        };
+
+        let mut seen: Vec<HashSet<Item>> = vec![HashSet::new(); text.len() + 1];
+
+        for alternative in 0..grammar.rules[start].alternatives.len() {
+            let item = Item {
+                rule: start,
+                alternative,
+                dot: 0,
+                origin: 0,
+            };
+
+            seen[0].insert(item);
+            chart.sets[0].push(item);
+        }
+
+        for position in 0..=text.len() {
+            let mut next = 0;
+
+            while next < chart.sets[position].len() {
+                let item = chart.sets[position][next];
+                let symbols = &grammar.rules[item.rule].alternatives[item.alternative];
+
+                next += 1;
+
+                let mut add = |chart: &mut Chart, at: usize, item: Item| {
+                    if seen[at].insert(item) {
+                        chart.sets[at].push(item);
+                    }
+                };
+
+                let advanced = Item {
+                    dot: item.dot + 1,
+                    ..item
+                };
+
+                match symbols.get(item.dot) {
+                    Some(Compiled::Terminal(terminal)) => {
+                        if text[position..].starts_with(terminal.as_str()) {
+                            add(&mut chart, position + terminal.len(), advanced);
+                        }
+                    }
+                    Some(&Compiled::Rule(rule)) => {
+                        for alternative in 0..grammar.rules[rule].alternatives.len() {
+                            let predicted = Item {
+                                rule,
+                                alternative,
+                                dot: 0,
+                                origin: position,
+                            };
+
+                            add(&mut chart, position, predicted);
+                        }
+
+                        if nullable[rule] {
+                            add(&mut chart, position, advanced);
+                        }
+                    }
+                    None => {
+                        chart.completed.insert((
+                            item.rule,
+                            item.alternative,
+                            item.origin,
+                            position,
+                        ));
+                        chart.spans.insert((item.rule, item.origin, position));
+
+                        let waiting: Vec<Item> = chart.sets[item.origin]
+                            .iter()
+                            .filter(|waiting| {
+                                grammar.rules[waiting.rule].alternatives[waiting.alternative]
+                                    .get(waiting.dot)
+                                    == Some(&Compiled::Rule(item.rule))
+                            })
+                            .copied()
+                            .collect();
+
+                        for waiting in waiting {
+                            let advanced = Item {
+                                dot: waiting.dot + 1,
+                                ..waiting
+                            };
+
+                            add(&mut chart, position, advanced);
+                        }
+                    }
+                }
+            }
+        }
+
+        chart
+    }
+
+    fn accepts(&self, start: usize) -> bool {
+        self.spans.contains(&(start, 0, self.text.len()))
+    }
+
+    /// Rebuilds how `rule` matched `start..end`, skipping matches already being built higher up
+    /// so cyclic rules cannot recurse forever.
+    fn build(
+        &self,
+        rule: usize,
+        start: usize,
+        end: usize,
+        path: &mut HashSet<(usize, usize, usize)>,
+    ) -> Option<Derivation> {
+        if !self.spans.contains(&(rule, start, end)) || !path.insert((rule, start, end)) {
+            return None;
+        }
+
+        let alternatives = &self.grammar.rules[rule].alternatives;
+
+        let children = (0..alternatives.len())
+            .filter(|&alternative| self.completed.contains(&(rule, alternative, start, end)))
+            .find_map(|alternative| self.split(&alternatives[alternative], start, end, path));
+
+        path.remove(&(rule, start, end));
+
+        let name = self.grammar.rules[rule].name.clone();
+
+        children.map(|children| Derivation::Rule(name, children))
+    }
+
+    /// Splits `start..end` between `symbols`, deriving each piece.
+    fn split(
+        &self,
+        symbols: &[Compiled],
+        start: usize,
+        end: usize,
+        path: &mut HashSet<(usize, usize, usize)>,
+    ) -> Option<Vec<Derivation>> {
+        let Some((first, rest)) = symbols.split_first() else {
+            return (start == end).then(Vec::new);
+        };
+
+        match first {
+            Compiled::Terminal(terminal) => {
+                let middle = start + terminal.len();
+
+                if middle > end || !self.text[start..].starts_with(terminal.as_str()) {
+                    return None;
+                }
+
+                let mut children = self.split(rest, middle, end, path)?;
+
+                children.insert(0, Derivation::Terminal(terminal.clone()));
+
+                Some(children)
+            }
+            &Compiled::Rule(rule) => (start..=end).find_map(|middle| {
+                let child = self.build(rule, start, middle, path)?;
+                let mut children = self.split(rest, middle, end, path)?;
+
+                children.insert(0, child);
+
+                Some(children)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str) -> Symbol {
+        Symbol::Rule(name.to_string())
+    }
+
+    fn terminal(text: &str) -> Symbol {
+        Symbol::Terminal(text.to_string())
+    }
+
+    #[test]
+    fn matches_recursive_rules() {
+        let mut grammar = Grammar::new();
+
+        grammar.insert(
+            "pairs",
+            vec![
+                vec![terminal("ab")],
+                vec![terminal("a"), rule("pairs"), terminal("b")],
+            ],
+        );
+        grammar.insert(
+            "list",
+            vec![
+                vec![rule("pairs")],
+                vec![rule("list"), terminal(","), rule("pairs")],
+            ],
+        );
+
+        assert!(grammar.matches("pairs", "aaabbb"));
+        assert!(!grammar.matches("pairs", "aaabb"));
+        assert!(grammar.matches("list", "ab,aabb,ab"));
+        assert!(!grammar.matches("list", "ab,"));
+        assert!(!grammar.matches("missing", "ab"));
+    }
+
+    #[test]
+    fn handles_empty_and_undefined_rules() {
+        let mut grammar = Grammar::new();
+
+        grammar.insert("maybe", vec![vec![], vec![terminal("x")]]);
+        grammar.insert(
+            "start",
+            vec![vec![rule("maybe"), rule("maybe"), terminal("!")]],
+        );
+        grammar.insert("broken", vec![vec![rule("nowhere")]]);
+
+        assert!(grammar.matches("start", "!"));
+        assert!(grammar.matches("start", "xx!"));
+        assert!(!grammar.matches("start", "xxx!"));
+        assert!(!grammar.matches("broken", ""));
+        assert_eq!(grammar.undefined(), ["nowhere"]);
+    }
+
+    #[test]
+    fn derives_matched_text() {
+        let mut grammar = Grammar::new();
+
+        grammar.insert(
+            "sum",
+            vec![
+                vec![rule("digit")],
+                vec![rule("sum"), terminal("+"), rule("digit")],
+            ],
+        );
+        grammar.insert("digit", vec![vec![terminal("1")], vec![terminal("2")]]);
+
+        let derivation = grammar.derive("sum", "1+2").expect("derivation");
+
+        assert_eq!(
+            derivation.to_string(),
+            r#"sum(sum(digit("1")) "+" digit("2"))"#
+        );
+        assert_eq!(grammar.derive("sum", "1+"), None);
+    }
+}
@@ -0,0 +1,402 @@
+use crate::parse::{Line, ParseResult};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A cell of a grid as `(x, y)`, with `y` growing downwards.
+pub type Position = (usize, usize);
+pub type Offset = (isize, isize);
+
+/// Up, right, down and left.
+pub const NEIGHBORS_4: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The four orthogonal neighbours followed by the four diagonal ones.
+pub const NEIGHBORS_8: [Offset; 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// A rectangular grid of tiles stored row by row.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut tile: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut tile)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+
+        assert!(rows.iter().all(|row| row.len() == width), "ragged rows");
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses one tile per character with `tile`, reporting `expected` for any other character.
+    pub fn parse_with<'a, F>(
+        lines: impl IntoIterator<Item = Line<'a>>,
+        tile: F,
+        expected: &str,
+    ) -> ParseResult<Self>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in lines {
+            let mut length = 0;
+
+            for (offset, char) in line.char_indices() {
+                cells.push(tile(char).ok_or_else(|| line.error_at(offset, expected))?);
+                length += 1;
+            }
+
+            match width {
+                None => width = Some(length),
+                Some(width) if width == length => (),
+                Some(width) if width < length => return Err(line.error_at(width, "end of row")),
+                Some(_) => return Err(line.error_end(expected)),
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one tile per character through the tile type's `TryFrom<char>`.
+    pub fn parse<'a>(lines: impl IntoIterator<Item = Line<'a>>, expected: &str) -> ParseResult<Self>
+    where
+        T: TryFrom<char>,
+    {
+        Self::parse_with(lines, |char| T::try_from(char).ok(), expected)
+    }
+
+    /// Parses one decimal digit per character.
+    pub fn parse_digits<'a>(lines: impl IntoIterator<Item = Line<'a>>) -> ParseResult<Self>
+    where
+        T: From<u8>,
+    {
+        Self::parse_with(
+            lines,
+            |char| char.to_digit(10).map(|digit| T::from(digit as u8)),
+            "digit",
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        match self.contains(position) {
+            true => Some(&self.cells[self.index(position)]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => {
+                let index = self.index(position);
+
+                Some(&mut self.cells[index])
+            }
+            false => None,
+        }
+    }
+
+    /// The position `offset` away from `position`, if it is inside the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): Offset) -> Option<Position> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+
+        match self.contains((x, y)) {
+            true => Some((x, y)),
+            false => None,
+        }
+    }
+
+    /// Maps a position on the infinite tiling of this grid back onto the grid.
+    pub fn wrap(&self, (x, y): Offset) -> Position {
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// The tile at a position on the infinite tiling of this grid.
+    pub fn get_wrapped(&self, position: Offset) -> &T {
+        &self[self.wrap(position)]
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &NEIGHBORS_4)
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &NEIGHBORS_8)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        position: Position,
+        offsets: &'a [Offset],
+    ) -> impl Iterator<Item = Position> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.offset(position, offset))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, tile)| *tile == value)
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, mut tile: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(position, value)| tile(position, value))
+                .collect(),
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+
+    fn index(&self, (x, y): Position) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        assert!(
+            self.contains(position),
+            "position {:?} outside grid",
+            position
+        );
+
+        &self.cells[self.index(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        assert!(
+            self.contains(position),
+            "position {:?} outside grid",
+            position
+        );
+
+        let index = self.index(position);
+
+        &mut self.cells[index]
+    }
+}
+
+/// Draws one character per tile, a row per line.
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let row: String = row.iter().map(|&tile| tile.into()).collect();
+
+            writeln!(formatter, "{}", row)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::blocks;
+
+    fn grid(text: &str) -> Grid<char> {
+        let block = blocks(text).next().expect("block");
+
+        Grid::parse(block.lines(), "tile").expect("grid")
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let block = blocks("ab\nabc\n").next().expect("block");
+        let error = Grid::<char>::parse(block.lines(), "tile").expect_err("ragged");
+
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn neighbors_stay_inside() {
+        let grid = grid("abc\ndef\n");
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((2, 1)).count(), 3);
+    }
+
+    #[test]
+    fn rotations_and_flips() {
+        let grid = grid("abc\ndef\n");
+
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+    }
+
+    #[test]
+    fn wraps_around() {
+        let grid = grid("abc\ndef\n");
+
+        assert_eq!(grid.get_wrapped((-1, -1)), &'f');
+        assert_eq!(grid.get_wrapped((3, 4)), &'a');
+    }
+}
@@ -0,0 +1,111 @@
+use crate::parse::{self, Block, ParseResult};
+use crate::registry::{Day, Year};
+use num::Num;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(String),
+    Unreadable(String, String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(filename) => write!(formatter, "input not found: {}", filename),
+            InputError::Unreadable(filename, error) => {
+                write!(formatter, "cannot read input {}: {}", filename, error)
+            }
+        }
+    }
+}
+
+/// The text of a puzzle input, together with the name errors report it under.
+#[derive(Debug, Clone)]
+pub struct Input {
+    name: String,
+    text: String,
+}
+
+impl Input {
+    /// Reads a file, or stdin when `filename` is [`STDIN`].
+    pub fn read(filename: &str) -> Result<Self, InputError> {
+        let text = if filename == STDIN {
+            let mut text = String::new();
+
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        } else {
+            fs::read_to_string(filename)
+        };
+
+        text.map(|text| Input {
+            name: filename.to_string(),
+            text,
+        })
+        .map_err(|error| InputError::Unreadable(filename.to_string(), error.to_string()))
+    }
+
+    pub fn literal(text: &str) -> Self {
+        Input {
+            name: String::from("<literal>"),
+            text: text.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self, ignore_empty: bool) -> impl Iterator<Item = String> + '_ {
+        crate::lines(&self.text, ignore_empty)
+    }
+
+    pub fn numbers<T>(&self, split: &str) -> ParseResult<Vec<T>>
+    where
+        T: Num + FromStr,
+    {
+        crate::numbers(&self.text, split)
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = Block<'_>> {
+        parse::blocks(&self.text)
+    }
+
+    pub fn parse_blocks<T, F>(&self, parse: F) -> ParseResult<Vec<T>>
+    where
+        F: FnMut(Block) -> ParseResult<T>,
+    {
+        parse::parse_blocks(&self.text, parse)
+    }
+}
+
+pub fn resolve_input(
+    year: Year,
+    day: Day,
+    default_dir: &str,
+    input: Option<&str>,
+) -> Result<String, InputError> {
+    let filename = match (input, env::var(INPUT_DIR_VAR)) {
+        (Some(input), _) => input.to_string(),
+        (None, Ok(input_dir)) => format!("{}/{}/day{:02}.txt", input_dir, year, day),
+        (None, Err(_)) => format!("{}/day{:02}.txt", default_dir, day),
+    };
+
+    if filename == STDIN || Path::new(&filename).is_file() {
+        Ok(filename)
+    } else {
+        Err(InputError::NotFound(filename))
+    }
+}
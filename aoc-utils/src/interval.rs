@@ -0,0 +1,257 @@
+use num::PrimInt;
+use std::ops::{Range, RangeInclusive};
+
+/// A set of integers kept as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+
+    /// Sorts and merges arbitrary ranges, dropping empty ones.
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many integers the set holds.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |total, range| total + (range.end - range.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::one())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        IntervalSet::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            match a.end < b.end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            while let Some(next) = others.peek() {
+                if next.end <= start {
+                    others.next();
+                    continue;
+                }
+
+                if next.start >= range.end {
+                    break;
+                }
+
+                if next.start > start {
+                    ranges.push(start..next.start);
+                }
+
+                start = next.end;
+
+                if next.end > range.end {
+                    break;
+                }
+
+                others.next();
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// The values below `at` and those at or above it.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let below = IntervalSet::from(T::min_value()..at);
+
+        (self.intersection(&below), self.difference(&below))
+    }
+
+    /// Moves the values inside `source` so that `source.start` lands on `destination`,
+    /// returning the moved values and the ones left outside `source`.
+    pub fn shift(&self, source: Range<T>, destination: T) -> (Self, Self) {
+        let start = source.start;
+        let source = IntervalSet::from(source);
+
+        let offset = |value: T| match destination >= start {
+            true => value + (destination - start),
+            false => value - (start - destination),
+        };
+
+        let moved = self
+            .intersection(&source)
+            .ranges
+            .iter()
+            .map(|range| offset(range.start)..offset(range.end))
+            .collect();
+
+        (moved, self.difference(&source))
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        IntervalSet::normalized(vec![range])
+    }
+}
+
+/// Panics if the range ends at `T::max_value()`, which a half-open range cannot hold.
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let end = range.end().checked_add(&T::one()).expect("inclusive end");
+
+        IntervalSet::from(*range.start()..end)
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        IntervalSet::normalized(ranges.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn set(ranges: &[Range<u32>]) -> IntervalSet<u32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn values(set: &IntervalSet<u32>) -> BTreeSet<u32> {
+        set.ranges().iter().cloned().flatten().collect()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<u32>>> {
+        prop::collection::vec(
+            (0u32..60, 0u32..12).prop_map(|(start, length)| start..start + length),
+            0..6,
+        )
+    }
+
+    #[test]
+    fn merges_touching_ranges() {
+        let set = set(&[5..8, 1..3, 3..4, 7..10]);
+
+        assert_eq!(set.ranges(), [1..4, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert!(set.contains(9) && !set.contains(4));
+    }
+
+    #[test]
+    fn shifts_sub_range() {
+        let (moved, rest) = IntervalSet::from(45..=100).shift(50..98, 52);
+
+        assert_eq!(moved, IntervalSet::from(52..=99));
+        assert_eq!(rest, set(&[45..50, 98..101]));
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_brute_force(a in ranges(), b in ranges()) {
+            let (a, b) = (set(&a), set(&b));
+            let (left, right) = (values(&a), values(&b));
+
+            prop_assert_eq!(values(&a.union(&b)), &left | &right);
+            prop_assert_eq!(values(&a.intersection(&b)), &left & &right);
+            prop_assert_eq!(values(&a.difference(&b)), &left - &right);
+            prop_assert_eq!(a.len() as usize, left.len());
+
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start));
+            }
+        }
+
+        #[test]
+        fn split_and_shift_match_brute_force(a in ranges(), at in 0u32..80, to in 0u32..80) {
+            let a = set(&a);
+            let all = values(&a);
+
+            let (below, above) = a.split_at(at);
+
+            prop_assert_eq!(values(&below), all.iter().copied().filter(|&v| v < at).collect());
+            prop_assert_eq!(values(&above), all.iter().copied().filter(|&v| v >= at).collect());
+
+            let source = at..at + 10;
+            let (moved, rest) = a.shift(source.clone(), to);
+            let expected: BTreeSet<u32> = all
+                .iter()
+                .filter(|value| source.contains(value))
+                .map(|value| value - at + to)
+                .collect();
+
+            prop_assert_eq!(values(&moved), expected);
+            prop_assert_eq!(values(&rest), all.iter().copied().filter(|v| !source.contains(v)).collect());
+        }
+    }
+}
@@ -1,29 +1,70 @@
+mod answers;
+mod automaton;
+mod bench;
+mod boxes;
+mod cycle;
+mod direction;
+mod example;
+mod expression;
+mod geometry;
+mod grammar;
+mod grid;
+mod input;
+mod interval;
+mod number;
+mod parse;
+mod polynomial;
+mod registry;
+mod search;
+mod solution;
+mod vector;
+
+pub use answers::{Answers, AnswersError};
+pub use automaton::{Cubic, Dense, Hex, LifeRule, Rule, Sparse, Square4, Square8, Topology};
+pub use bench::{bench, Timings};
+pub use boxes::{BoxN, BoxSet};
+pub use cycle::{find_cycle, fixpoint, state_after, Cycle};
+pub use direction::Direction;
+pub use example::check_example;
+pub use expression::{
+    tokenize, Associativity, BinaryOperator, Expr, ExpressionError, OperatorTable, Token,
+};
+pub use geometry::{boundary_points, double_area, interior_points, locate, Containment, Point};
+pub use grammar::{Derivation, Grammar, Symbol};
+pub use grid::{Grid, Offset, Position, NEIGHBORS_4, NEIGHBORS_8};
+pub use input::{resolve_input, Input, InputError, INPUT_DIR_VAR, STDIN};
+pub use interval::IntervalSet;
+pub use number::{crt, discrete_log, extended_gcd, gcd_all, lcm_all, mod_inverse, modpow, mul_mod};
+pub use parse::{blocks, numbered, parse_blocks, Block, Line, ParseError, ParseResult};
+pub use polynomial::Polynomial;
+pub use registry::{Bencher, Day, Registry, Solver, Year};
+pub use search::{astar, bfs, dijkstra, distances, SearchResult};
+pub use solution::{solve, Part, Solution, PARTS};
+pub use vector::{Rotation, Vec2, Vec3, VectorError};
+
 use num::Num;
-use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::iter::Iterator;
 use std::str::FromStr;
 
-pub fn read_lines(filename: &str, ignore_empty: bool) -> impl Iterator<Item = String> {
-    let file = File::open(filename).expect("file");
-    let buffer = BufReader::new(file);
-
-    buffer
-        .lines()
-        .filter_map(Result::ok)
+pub fn lines(text: &str, ignore_empty: bool) -> impl Iterator<Item = String> + '_ {
+    text.lines()
         .filter(move |line| !ignore_empty || !line.is_empty())
+        .map(str::to_string)
 }
 
-pub fn read_numbers<'a, T: 'a>(filename: &str, split: &'a str) -> impl Iterator<Item = T> + 'a
+pub fn numbers<T>(text: &str, split: &str) -> ParseResult<Vec<T>>
 where
     T: Num + FromStr,
-    <T as FromStr>::Err: Debug,
 {
-    read_lines(filename, true).flat_map(move |line| {
-        line.split(split)
-            .filter(|number| !number.is_empty())
-            .map(|number| number.parse().expect(&format!("invalid number {}", number)))
-            .collect::<Vec<T>>()
-    })
+    let lines: Vec<String> = lines(text, true).collect();
+
+    let mut numbers = Vec::new();
+
+    for line in numbered(&lines) {
+        for number in line.split(split).filter(|number| !number.is_empty()) {
+            numbers.push(line.parse(number, "number")?);
+        }
+    }
+
+    Ok(numbers)
 }
@@ -0,0 +1,211 @@
+use num::integer::Roots;
+use num::{Integer, Signed};
+use std::collections::HashMap;
+
+/// `(g, x, y)` such that `a * x + b * y = g`, the greatest common divisor of `a` and `b`.
+pub fn extended_gcd<T: Integer + Signed + Copy>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::one(), T::zero());
+    let (mut old_t, mut t) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r / r;
+
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    match old_r.is_negative() {
+        true => (-old_r, -old_s, -old_t),
+        false => (old_r, old_s, old_t),
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1`, if `a` and `modulus` are coprime.
+pub fn mod_inverse<T: Integer + Signed + Copy>(a: T, modulus: T) -> Option<T> {
+    let (gcd, x, _) = extended_gcd(a.mod_floor(&modulus), modulus);
+
+    match gcd.is_one() {
+        true => Some(x.mod_floor(&modulus)),
+        false => None,
+    }
+}
+
+/// The smallest non-negative `x` with `x ≡ remainder (mod modulus)` for every congruence,
+/// together with the combined modulus. Moduli need not be coprime; `None` if the
+/// congruences contradict each other.
+pub fn crt<T: Integer + Signed + Copy>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), |(x, modulus), (remainder, other)| {
+            let (gcd, p, _) = extended_gcd(modulus, other);
+            let difference = remainder - x;
+
+            if !(difference % gcd).is_zero() {
+                return None;
+            }
+
+            let step = other / gcd;
+            let k = (difference / gcd).mod_floor(&step) * p.mod_floor(&step) % step;
+            let lcm = modulus * step;
+
+            Some(((x + modulus * k).mod_floor(&lcm), lcm))
+        })
+}
+
+/// Greatest common divisor of all `values`, zero when there are none.
+pub fn gcd_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |gcd, value| gcd.gcd(&value))
+}
+
+/// Least common multiple of all `values`, one when there are none.
+pub fn lcm_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::one(), |lcm, value| lcm.lcm(&value))
+}
+
+/// `a * b % modulus`, falling back to shift-and-add when the product overflows `u128`.
+pub fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    let (mut a, mut b) = (a % modulus, b % modulus);
+
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let mut result = 0;
+
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+
+    result
+}
+
+fn add_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    match a.overflowing_add(b) {
+        (sum, false) if sum < modulus => sum,
+        (sum, _) => sum.wrapping_sub(modulus),
+    }
+}
+
+/// `base.pow(exponent) % modulus` by repeated squaring.
+pub fn modpow(base: u128, mut exponent: u128, modulus: u128) -> u128 {
+    let mut base = base % modulus;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// The smallest `x` with `base.pow(x) ≡ target (mod modulus)`, by baby-step giant-step in
+/// O(sqrt(modulus)) time and memory. `base` must be coprime to `modulus`.
+pub fn discrete_log(base: u128, target: u128, modulus: u128) -> Option<u128> {
+    let target = target % modulus;
+
+    if target == 1 % modulus {
+        return Some(0);
+    }
+
+    let steps = modulus.sqrt() + 1;
+    let mut baby_steps = HashMap::new();
+    let mut value = target;
+
+    for j in 0..steps {
+        baby_steps.insert(value, j);
+        value = mul_mod(value, base, modulus);
+    }
+
+    let giant_step = modpow(base, steps, modulus);
+    let mut value = 1;
+
+    for i in 1..=steps {
+        value = mul_mod(value, giant_step, modulus);
+
+        if let Some(j) = baby_steps.get(&value) {
+            return Some(i * steps - j);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        let (gcd, x, y) = extended_gcd(240, 46);
+
+        assert_eq!(gcd, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn inverse_only_when_coprime() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(0, 4), (1, 6)]), None);
+    }
+
+    #[test]
+    fn mul_mod_beyond_u128_products() {
+        let modulus = u128::MAX - 158;
+        let a = u128::MAX - 200;
+
+        assert_eq!(mul_mod(a, a, modulus), 42 * 42);
+        assert_eq!(modpow(a, 2, modulus), 42 * 42);
+    }
+
+    #[test]
+    fn modpow_small() {
+        assert_eq!(modpow(4, 13, 497), 445);
+        assert_eq!(modpow(7, 0, 1), 0);
+    }
+
+    #[test]
+    fn discrete_log_finds_smallest_exponent() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(2, 1, 11), Some(0));
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+        assert_eq!(discrete_log(4, 2, 7), Some(2));
+        assert_eq!(discrete_log(2, 3, 7), None);
+    }
+
+    #[test]
+    fn folds() {
+        assert_eq!(lcm_all([4, 6, 10]), 60);
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+}
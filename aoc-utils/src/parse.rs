@@ -0,0 +1,202 @@
+use std::fmt::{Display, Formatter};
+use std::iter;
+use std::ops::Deref;
+use std::str::FromStr;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: &str) -> Self {
+        ParseError {
+            file: String::new(),
+            line,
+            column,
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn in_file(self, file: &str) -> Self {
+        ParseError {
+            file: file.to_string(),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{}:{}:{}: expected {}",
+            self.file, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of input together with its 1-based line number, so errors can point back at it.
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Line { number, text }
+    }
+
+    /// Error at the start of `token`, which must be a slice of this line.
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        self.error_at(self.offset(token), expected)
+    }
+
+    /// Error at the 0-based byte `offset` within this line.
+    pub fn error_at(&self, offset: usize, expected: &str) -> ParseError {
+        ParseError::new(self.number, offset + 1, expected)
+    }
+
+    /// Error just past the end of this line, for truncated input.
+    pub fn error_end(&self, expected: &str) -> ParseError {
+        self.error_at(self.text.len(), expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> ParseResult<T> {
+        token
+            .trim()
+            .parse()
+            .map_err(|_| self.error(token, expected))
+    }
+
+    pub fn digits<T: From<u8>>(&self) -> ParseResult<Vec<T>> {
+        self.char_indices()
+            .map(|(offset, char)| match char.to_digit(10) {
+                Some(digit) => Ok(T::from(digit as u8)),
+                None => Err(self.error_at(offset, "digit")),
+            })
+            .collect()
+    }
+
+    fn offset(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let position = token.as_ptr() as usize;
+
+        if position >= start && position <= start + self.text.len() {
+            position - start
+        } else {
+            0
+        }
+    }
+}
+
+impl Deref for Line<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.text
+    }
+}
+
+pub fn numbered(lines: &[String]) -> impl Iterator<Item = Line<'_>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| Line::new(index + 1, line))
+}
+
+/// A paragraph of input: a run of non-blank lines, numbered as in the whole input.
+#[derive(Debug, Clone)]
+pub struct Block<'a> {
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> Block<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + '_ {
+        self.lines.iter().copied()
+    }
+
+    /// Error just past the last line, for blocks that end too early.
+    pub fn error_end(&self, expected: &str) -> ParseError {
+        match self.lines.last() {
+            Some(line) => line.error_end(expected),
+            None => ParseError::new(0, 1, expected),
+        }
+    }
+}
+
+impl<'a> Deref for Block<'a> {
+    type Target = [Line<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.lines
+    }
+}
+
+/// Splits `text` on blank lines, skipping leading, trailing and repeated ones.
+pub fn blocks(text: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| Line::new(index + 1, line))
+        .peekable();
+
+    iter::from_fn(move || {
+        while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+
+        let block: Vec<Line> =
+            iter::from_fn(|| lines.next_if(|line| !line.trim().is_empty())).collect();
+
+        match block.is_empty() {
+            true => None,
+            false => Some(Block { lines: block }),
+        }
+    })
+}
+
+pub fn parse_blocks<T, F>(text: &str, parse: F) -> ParseResult<Vec<T>>
+where
+    F: FnMut(Block) -> ParseResult<T>,
+{
+    blocks(text).map(parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<Vec<(usize, &str)>> {
+        blocks(text)
+            .map(|block| block.lines().map(|line| (line.number, line.text)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn blocks_keep_line_numbers() {
+        let expected = vec![vec![(1, "a"), (2, "b")], vec![(4, "c")]];
+
+        assert_eq!(texts("a\nb\n\nc\n"), expected);
+    }
+
+    #[test]
+    fn blocks_skip_extra_blank_lines() {
+        let expected = vec![vec![(2, "a")], vec![(5, "b")]];
+
+        assert_eq!(texts("\na\n\n  \nb\n\n\n"), expected);
+    }
+
+    #[test]
+    fn blocks_strip_crlf() {
+        let expected = vec![vec![(1, "a"), (2, "b")], vec![(4, "c")]];
+
+        assert_eq!(texts("a\r\nb\r\n\r\nc\r\n"), expected);
+    }
+}
@@ -0,0 +1,124 @@
+use num::{BigInt, BigRational, One, ToPrimitive, Zero};
+
+/// A polynomial with exact rational coefficients, lowest degree first.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    pub fn new(mut coefficients: Vec<BigRational>) -> Self {
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+
+        Polynomial { coefficients }
+    }
+
+    /// The polynomial of lowest degree through every `(x, y)` sample, by Newton's divided
+    /// differences. Panics if two samples share an `x`.
+    pub fn fit(points: impl IntoIterator<Item = (i128, i128)>) -> Self {
+        let (xs, ys): (Vec<BigRational>, Vec<BigRational>) = points
+            .into_iter()
+            .map(|(x, y)| (rational(x), rational(y)))
+            .unzip();
+
+        let mut differences = ys;
+
+        for level in 1..xs.len() {
+            for i in (level..xs.len()).rev() {
+                let run = &xs[i] - &xs[i - level];
+
+                assert!(!run.is_zero(), "duplicate sample x {}", xs[i]);
+
+                differences[i] = (&differences[i] - &differences[i - 1]) / run;
+            }
+        }
+
+        let mut coefficients = Vec::new();
+
+        for (x, difference) in xs.iter().zip(differences).rev() {
+            coefficients.insert(0, BigRational::zero());
+
+            for i in 0..coefficients.len() - 1 {
+                let shifted = &coefficients[i + 1] * x;
+
+                coefficients[i] -= shifted;
+            }
+
+            coefficients[0] += difference;
+        }
+
+        Polynomial::new(coefficients)
+    }
+
+    /// The polynomial through `values` sampled at `x = 0, 1, 2, ...`.
+    pub fn fit_sequence(values: &[i128]) -> Self {
+        Polynomial::fit((0..).zip(values.iter().copied()))
+    }
+
+    pub fn coefficients(&self) -> &[BigRational] {
+        &self.coefficients
+    }
+
+    /// The highest power with a non-zero coefficient, zero for the zero polynomial.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn eval(&self, x: i128) -> BigRational {
+        let x = rational(x);
+
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |value, coefficient| {
+                value * &x + coefficient
+            })
+    }
+
+    /// The value at `x` if it is an integer that fits in an `i128`.
+    pub fn eval_integer(&self, x: i128) -> Option<i128> {
+        let value = self.eval(x);
+
+        match value.denom().is_one() {
+            true => value.numer().to_i128(),
+            false => None,
+        }
+    }
+}
+
+fn rational(value: i128) -> BigRational {
+    BigRational::from_integer(BigInt::from(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_arbitrary_points() {
+        let polynomial = Polynomial::fit([(65, 3911), (196, 34786), (327, 96435)]);
+
+        assert_eq!(polynomial.degree(), 2);
+        assert_eq!(polynomial.eval_integer(65), Some(3911));
+        assert_eq!(polynomial.eval_integer(327), Some(96435));
+    }
+
+    #[test]
+    fn extrapolates_both_ways() {
+        let polynomial = Polynomial::fit_sequence(&[10, 13, 16, 21, 30, 45]);
+
+        assert_eq!(polynomial.eval_integer(6), Some(68));
+        assert_eq!(polynomial.eval_integer(-1), Some(5));
+    }
+
+    #[test]
+    fn evaluates_huge_x_exactly() {
+        let polynomial = Polynomial::fit_sequence(&[1, 2, 5]);
+
+        assert_eq!(polynomial.eval_integer(1 << 40), Some((1 << 80) + 1));
+        assert_eq!(Polynomial::fit_sequence(&[0, 1]).eval_integer(1), Some(1));
+        assert_eq!(Polynomial::fit([(0, 0), (2, 1)]).eval_integer(1), None);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::answers::{Answers, AnswersError};
+use crate::bench::{self, Timings};
+use crate::input::{self, Input, InputError};
+use crate::parse::ParseResult;
+use crate::solution::{self, Part, Solution};
+use std::collections::BTreeMap;
+
+pub type Year = u16;
+pub type Day = u8;
+pub type Solver = fn(&Input, &[Part]) -> ParseResult<Vec<String>>;
+pub type Bencher = fn(&Input, usize) -> ParseResult<Timings>;
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<(Year, Day), Solver>,
+    benchers: BTreeMap<(Year, Day), Bencher>,
+    input_dirs: BTreeMap<Year, String>,
+    answers_files: BTreeMap<Year, String>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S: Solution>(&mut self, year: Year, day: Day) {
+        self.solvers.insert((year, day), solution::solve::<S>);
+        self.benchers.insert((year, day), bench::bench::<S>);
+    }
+
+    pub fn register_input_dir(&mut self, year: Year, input_dir: &str) {
+        self.input_dirs.insert(year, input_dir.to_string());
+    }
+
+    pub fn register_answers_file(&mut self, year: Year, answers_file: &str) {
+        self.answers_files.insert(year, answers_file.to_string());
+    }
+
+    pub fn solver(&self, year: Year, day: Day) -> Option<Solver> {
+        self.solvers.get(&(year, day)).copied()
+    }
+
+    pub fn bencher(&self, year: Year, day: Day) -> Option<Bencher> {
+        self.benchers.get(&(year, day)).copied()
+    }
+
+    pub fn input(&self, year: Year, day: Day, input: Option<&str>) -> Result<String, InputError> {
+        let input_dir = self
+            .input_dirs
+            .get(&year)
+            .map_or("inputs", |dir| dir.as_str());
+
+        input::resolve_input(year, day, input_dir, input)
+    }
+
+    pub fn answers(&self, year: Year) -> Result<Answers, AnswersError> {
+        let answers_file = self
+            .answers_files
+            .get(&year)
+            .map_or("answers.toml", |file| file.as_str());
+
+        Answers::load(answers_file)
+    }
+
+    pub fn years(&self) -> Vec<Year> {
+        let mut years: Vec<_> = self.solvers.keys().map(|&(year, _)| year).collect();
+
+        years.dedup();
+
+        years
+    }
+
+    pub fn days(&self, year: Year) -> Vec<Day> {
+        self.solvers
+            .range((year, Day::MIN)..=(year, Day::MAX))
+            .map(|(&(_, day), _)| day)
+            .collect()
+    }
+}
@@ -0,0 +1,256 @@
+use num::Zero;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A cheapest way to a goal, along with how many states were expanded to find it.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    pub path: Vec<S>,
+    pub expanded: usize,
+}
+
+/// The states seen so far, each with its best known cost and the state it was reached from.
+struct Visited<S, C> {
+    nodes: Vec<(S, C, Option<usize>)>,
+    indices: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Visited<S, C> {
+    fn new() -> Self {
+        Visited {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Records `cost` for `state` if it is the cheapest so far, returning the state's index.
+    fn improve(&mut self, state: S, cost: C, parent: Option<usize>) -> Option<usize> {
+        match self.indices.entry(state) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+
+                match cost < self.nodes[index].1 {
+                    true => {
+                        self.nodes[index].1 = cost;
+                        self.nodes[index].2 = parent;
+
+                        Some(index)
+                    }
+                    false => None,
+                }
+            }
+            Entry::Vacant(entry) => {
+                let index = self.nodes.len();
+
+                self.nodes.push((entry.key().clone(), cost, parent));
+                entry.insert(index);
+
+                Some(index)
+            }
+        }
+    }
+
+    fn result(&self, index: usize, expanded: usize) -> SearchResult<S, C> {
+        let mut path = Vec::new();
+        let mut current = Some(index);
+
+        while let Some(index) = current {
+            path.push(self.nodes[index].0.clone());
+            current = self.nodes[index].2;
+        }
+
+        path.reverse();
+
+        SearchResult {
+            cost: self.nodes[index].1,
+            path,
+            expanded,
+        }
+    }
+}
+
+/// Cheapest path from any of `starts` to a state satisfying `is_goal`.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` that must never overestimate the remaining cost.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut visited = Visited::new();
+    let mut heap = BinaryHeap::new();
+    let mut expanded = 0;
+
+    for start in starts {
+        let estimate = heuristic(&start);
+
+        if let Some(index) = visited.improve(start, C::zero(), None) {
+            heap.push((Reverse(estimate), Reverse(C::zero()), index));
+        }
+    }
+
+    while let Some((_, Reverse(cost), index)) = heap.pop() {
+        if cost > visited.nodes[index].1 {
+            continue;
+        }
+
+        if is_goal(&visited.nodes[index].0) {
+            return Some(visited.result(index, expanded));
+        }
+
+        expanded += 1;
+
+        let nexts: Vec<(S, C)> = successors(&visited.nodes[index].0).into_iter().collect();
+
+        for (next, step) in nexts {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+
+            if let Some(next_index) = visited.improve(next, next_cost, Some(index)) {
+                heap.push((Reverse(estimate), Reverse(next_cost), next_index));
+            }
+        }
+    }
+
+    None
+}
+
+/// Shortest path in steps from any of `starts` to a state satisfying `is_goal`.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    let mut expanded = 0;
+
+    for start in starts {
+        queue.extend(visited.improve(start, 0, None));
+    }
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&visited.nodes[index].0) {
+            return Some(visited.result(index, expanded));
+        }
+
+        expanded += 1;
+
+        let steps = visited.nodes[index].1 + 1;
+        let nexts: Vec<S> = successors(&visited.nodes[index].0).into_iter().collect();
+
+        for next in nexts {
+            if !visited.indices.contains_key(&next) {
+                queue.extend(visited.improve(next, steps, Some(index)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Steps from the nearest of `starts` to every state reachable in at most `max_steps`.
+pub fn distances<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    max_steps: usize,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if steps == max_steps {
+            continue;
+        }
+
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(position: &i32) -> Vec<(i32, u32)> {
+        vec![(position - 1, 1), (position + 1, 1), (position + 5, 7)]
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_steps() {
+        let result = dijkstra([0], line, |&position| position == 5).expect("path");
+
+        assert_eq!(result.cost, 5);
+        assert_eq!(result.path, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn astar_expands_fewer_states() {
+        let goal = 20;
+        let heuristic = |position: &i32| (goal - position).unsigned_abs();
+
+        let plain = dijkstra([0], line, |&position| position == goal).expect("path");
+        let guided = astar([0], line, heuristic, |&position| position == goal).expect("path");
+
+        assert_eq!(plain.cost, guided.cost);
+        assert!(guided.expanded < plain.expanded);
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        let successors = |position: &i32| [position - 1, position + 1];
+        let result = bfs([10, -3], successors, |&position| position == 0).expect("path");
+
+        assert_eq!(result.cost, 3);
+        assert_eq!(result.path, [-3, -2, -1, 0]);
+    }
+
+    #[test]
+    fn distances_stop_at_max_steps() {
+        let successors = |position: &i32| [position - 1, position + 1];
+
+        assert_eq!(distances([0], successors, 3).len(), 7);
+    }
+}
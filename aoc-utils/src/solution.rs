@@ -0,0 +1,46 @@
+use crate::input::Input;
+use crate::parse::ParseResult;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+pub const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+impl Display for Part {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(formatter, "1"),
+            Part::Two => write!(formatter, "2"),
+        }
+    }
+}
+
+pub trait Solution {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &Input) -> ParseResult<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+pub fn solve<S: Solution>(input: &Input, parts: &[Part]) -> ParseResult<Vec<String>> {
+    let input = S::parse(input).map_err(|error| error.in_file(input.name()))?;
+
+    let answers = parts
+        .iter()
+        .map(|part| match part {
+            Part::One => S::part1(&input).to_string(),
+            Part::Two => S::part2(&input).to_string(),
+        })
+        .collect();
+
+    Ok(answers)
+}
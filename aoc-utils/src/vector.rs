@@ -0,0 +1,333 @@
+use num::{Num, Signed};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Why text could not be read as a vector.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VectorError {
+    Components { expected: usize, found: usize },
+    Component(String),
+}
+
+impl Display for VectorError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VectorError::Components { expected, found } => write!(
+                formatter,
+                "expected {} comma-separated components, found {}",
+                expected, found
+            ),
+            VectorError::Component(component) => {
+                write!(formatter, "invalid component {:?}", component)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VectorError {}
+
+fn parse_components<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], VectorError> {
+    let parts: Vec<&str> = text.split(',').map(str::trim).collect();
+
+    if parts.len() != N {
+        return Err(VectorError::Components {
+            expected: N,
+            found: parts.len(),
+        });
+    }
+
+    let components: Vec<T> = parts
+        .iter()
+        .map(|part| {
+            part.parse()
+                .map_err(|_| VectorError::Component(part.to_string()))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(components
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("length checked")))
+}
+
+/// A vector or point in the plane.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A vector or point in space.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Num + Copy> Vec2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, zero when the vectors are parallel.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Signed + Copy> Vec2<T> {
+    pub fn abs(self) -> Self {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Each component replaced by its sign, a unit step towards the vector's direction.
+    pub fn signum(self) -> Self {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Taxicab distance: the sum of the absolute component differences.
+    pub fn manhattan(self, other: Self) -> T {
+        let Vec2 { x, y } = (self - other).abs();
+
+        x + y
+    }
+
+    /// King-move distance: the largest absolute component difference.
+    pub fn chebyshev(self, other: Self) -> T
+    where
+        T: Ord,
+    {
+        let Vec2 { x, y } = (self - other).abs();
+
+        x.max(y)
+    }
+}
+
+impl<T: Signed + Copy> Vec3<T> {
+    pub fn abs(self) -> Self {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        let Vec3 { x, y, z } = (self - other).abs();
+
+        x + y + z
+    }
+
+    pub fn chebyshev(self, other: Self) -> T
+    where
+        T: Ord,
+    {
+        let Vec3 { x, y, z } = (self - other).abs();
+
+        x.max(y).max(z)
+    }
+}
+
+macro_rules! impl_ops {
+    ($vector:ident { $($field:ident),+ }, $count:literal) => {
+        impl<T: Num + Copy> Add for $vector<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $vector { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Num + Copy> Sub for $vector<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $vector { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Num + Copy> Mul<T> for $vector<T> {
+            type Output = Self;
+
+            fn mul(self, scalar: T) -> Self {
+                $vector { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl<T: Signed + Copy> Neg for $vector<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $vector { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Num + Copy> AddAssign for $vector<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Num + Copy> SubAssign for $vector<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        /// Reads comma-separated components, allowing whitespace around each.
+        impl<T: FromStr> FromStr for $vector<T> {
+            type Err = VectorError;
+
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                let [$($field),+] = parse_components::<T, $count>(text)?;
+
+                Ok($vector { $($field),+ })
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2 { x, y }, 2);
+impl_ops!(Vec3 { x, y, z }, 3);
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(Vec2 { x, y }: Vec2<T>) -> Self {
+        (x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+/// One of the 24 orientations of a cube: a permutation of the axes with some of them negated,
+/// keeping handedness.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rotation {
+    axes: [usize; 3],
+    negated: [bool; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        axes: [0, 1, 2],
+        negated: [false; 3],
+    };
+
+    /// All 24 rotations, the identity first.
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        const PERMUTATIONS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], false),
+            ([1, 2, 0], false),
+            ([2, 0, 1], false),
+            ([0, 2, 1], true),
+            ([2, 1, 0], true),
+            ([1, 0, 2], true),
+        ];
+
+        PERMUTATIONS.into_iter().flat_map(|(axes, odd)| {
+            (0..8).filter_map(move |signs: u8| {
+                let negated = [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0];
+                let flips = negated.iter().filter(|&&negated| negated).count();
+
+                match (flips % 2 == 1) == odd {
+                    true => Some(Rotation { axes, negated }),
+                    false => None,
+                }
+            })
+        })
+    }
+
+    pub fn apply<T: Signed + Copy>(&self, vector: Vec3<T>) -> Vec3<T> {
+        let components = [vector.x, vector.y, vector.z];
+        let component = |index: usize| match self.negated[index] {
+            true => -components[self.axes[index]],
+            false => components[self.axes[index]],
+        };
+
+        Vec3::new(component(0), component(1), component(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn arithmetic_and_distances() {
+        let a = Vec2::new(1, -2);
+        let b = Vec2::new(4, 2);
+
+        assert_eq!(a + b, Vec2::new(5, 0));
+        assert_eq!(b - a, Vec2::new(3, 4));
+        assert_eq!(a * 3, Vec2::new(3, -6));
+        assert_eq!(a.dot(b), 0);
+        assert_eq!(a.cross(b), 10);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Vec3::new(1, 2, 3).manhattan(Vec3::new(-1, 2, 0)), 5);
+    }
+
+    #[test]
+    fn parses_comma_separated_text() {
+        assert_eq!("1, -2,3".parse(), Ok(Vec3::new(1, -2, 3)));
+        assert_eq!(
+            "1,2".parse::<Vec3<i32>>(),
+            Err(VectorError::Components {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "1,x".parse::<Vec2<i32>>(),
+            Err(VectorError::Component("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn twenty_four_distinct_rotations() {
+        let vector = Vec3::new(1, 2, 3);
+        let rotated: HashSet<_> = Rotation::all()
+            .map(|rotation| rotation.apply(vector))
+            .collect();
+
+        assert_eq!(rotated.len(), 24);
+        assert!(rotated.contains(&Vec3::new(-2, 1, 3)));
+        assert!(!rotated.contains(&Vec3::new(-1, 2, 3)));
+        assert_eq!(Rotation::all().next(), Some(Rotation::IDENTITY));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::error::Error;
+use aoc_utils::{Day, Part, Registry, Year, PARTS};
+use std::str::FromStr;
+
+pub type Puzzle = (Year, Day);
+
+#[derive(Debug)]
+pub enum Command {
+    Run(Vec<Puzzle>, Vec<Part>, Option<String>, Mode),
+    Bench(Vec<Puzzle>, BenchOptions),
+    Alu(AluCommand, Option<String>),
+    Help,
+}
+
+#[derive(Debug)]
+pub enum AluCommand {
+    Trace(Vec<i64>),
+    Disassemble,
+}
+
+#[derive(Debug)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub output: Option<String>,
+    pub baseline: Option<String>,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Mode {
+    Print,
+    Check,
+    Record,
+}
+
+#[derive(Debug)]
+enum Selection<T> {
+    All,
+    Range(T, T),
+}
+
+pub fn parse_command(args: &[String], registry: &Registry) -> Result<Command, Error> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Ok(Command::Help),
+    };
+
+    match command {
+        "run" => parse_run(args, registry),
+        "bench" => parse_bench(args, registry),
+        "alu" => parse_alu(args),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        _ => Err(Error::Usage(format!("unknown command {}", command))),
+    }
+}
+
+fn parse_run(args: &[String], registry: &Registry) -> Result<Command, Error> {
+    let mut positional = Vec::new();
+    let mut parts = PARTS.to_vec();
+    let mut input = None;
+    let mut mode = Mode::Print;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage("missing value for --part"))?;
+
+                parts = vec![parse_part(value)?];
+            }
+            "--input" | "-i" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage("missing value for --input"))?;
+
+                input = Some(value.to_string());
+            }
+            "--check" => mode = Mode::Check,
+            "--record" => mode = Mode::Record,
+            flag if flag.starts_with('-') => return Err(usage(&format!("unknown flag {}", flag))),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let puzzles = parse_puzzles(&positional, registry)?;
+
+    if input.is_some() && puzzles.len() > 1 {
+        return Err(usage("--input requires a single puzzle"));
+    }
+
+    Ok(Command::Run(puzzles, parts, input, mode))
+}
+
+fn parse_bench(args: &[String], registry: &Registry) -> Result<Command, Error> {
+    let mut positional = Vec::new();
+    let mut options = BenchOptions {
+        runs: 10,
+        output: None,
+        baseline: None,
+        threshold: 10.0,
+    };
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| usage(&format!("missing value for {}", flag)))
+        };
+
+        match arg.as_str() {
+            "--runs" | "-n" => {
+                options.runs = value("--runs")?
+                    .parse()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or_else(|| usage("--runs must be a positive number"))?;
+            }
+            "--output" | "-o" => options.output = Some(value("--output")?.to_string()),
+            "--baseline" | "-b" => options.baseline = Some(value("--baseline")?.to_string()),
+            "--threshold" | "-t" => {
+                options.threshold = value("--threshold")?
+                    .parse()
+                    .ok()
+                    .filter(|&threshold: &f64| threshold >= 0.0)
+                    .ok_or_else(|| usage("--threshold must be a non-negative percentage"))?;
+            }
+            flag if flag.starts_with('-') => return Err(usage(&format!("unknown flag {}", flag))),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let puzzles = parse_puzzles(&positional, registry)?;
+
+    Ok(Command::Bench(puzzles, options))
+}
+
+fn parse_alu(args: &[String]) -> Result<Command, Error> {
+    let mut positional = Vec::new();
+    let mut input = None;
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage("missing value for --input"))?;
+
+                input = Some(value.to_string());
+            }
+            flag if flag.starts_with('-') => return Err(usage(&format!("unknown flag {}", flag))),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let command = match *positional {
+        ["trace", digits] => AluCommand::Trace(parse_digits(digits)?),
+        ["disassemble"] => AluCommand::Disassemble,
+        ["trace"] => return Err(usage("missing digits")),
+        [] => return Err(usage("missing alu command")),
+        [command, ..] if command != "trace" && command != "disassemble" => {
+            return Err(usage(&format!("unknown alu command {}", command)))
+        }
+        _ => return Err(usage("too many arguments")),
+    };
+
+    Ok(Command::Alu(command, input))
+}
+
+fn parse_puzzles(positional: &[&str], registry: &Registry) -> Result<Vec<Puzzle>, Error> {
+    let (years, days) = match *positional {
+        [years] => (parse_selection(years)?, Selection::All),
+        [years, days] => (parse_selection(years)?, parse_selection(days)?),
+        [] => return Err(usage("missing year")),
+        _ => return Err(usage("too many arguments")),
+    };
+
+    select_puzzles(registry, &years, &days)
+}
+
+fn parse_part(value: &str) -> Result<Part, Error> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(usage(&format!("invalid part {}", value))),
+    }
+}
+
+fn parse_digits(value: &str) -> Result<Vec<i64>, Error> {
+    value
+        .chars()
+        .map(|char| char.to_digit(10).map(i64::from))
+        .collect::<Option<_>>()
+        .ok_or_else(|| usage(&format!("invalid digits {}", value)))
+}
+
+fn parse_selection<T: FromStr + Copy>(value: &str) -> Result<Selection<T>, Error> {
+    let number = |value: &str| {
+        value
+            .parse()
+            .map_err(|_| usage(&format!("invalid number {}", value)))
+    };
+
+    if value == "all" {
+        Ok(Selection::All)
+    } else if let Some((start, end)) = value.split_once("..=") {
+        Ok(Selection::Range(number(start)?, number(end)?))
+    } else {
+        let value = number(value)?;
+
+        Ok(Selection::Range(value, value))
+    }
+}
+
+fn select_puzzles(
+    registry: &Registry,
+    years: &Selection<Year>,
+    days: &Selection<Day>,
+) -> Result<Vec<Puzzle>, Error> {
+    let years = match *years {
+        Selection::All => registry.years(),
+        Selection::Range(start, end) => {
+            let known_years = registry.years();
+
+            (start..=end)
+                .map(|year| match known_years.contains(&year) {
+                    true => Ok(year),
+                    false => Err(Error::UnknownYear(year)),
+                })
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let mut puzzles = Vec::new();
+
+    for year in years {
+        let known_days = registry.days(year);
+
+        match *days {
+            Selection::All => puzzles.extend(known_days.iter().map(|&day| (year, day))),
+            Selection::Range(start, end) => {
+                for day in start..=end {
+                    if !known_days.contains(&day) {
+                        return Err(Error::UnknownDay(year, day));
+                    }
+
+                    puzzles.push((year, day));
+                }
+            }
+        }
+    }
+
+    if puzzles.is_empty() {
+        return Err(usage("empty selection"));
+    }
+
+    Ok(puzzles)
+}
+
+fn usage(message: &str) -> Error {
+    Error::Usage(message.to_string())
+}
@@ -0,0 +1,113 @@
+use crate::args::Puzzle;
+use aoc_utils::{Day, Timings, Year};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::time::Duration;
+
+/// Phases whose medians differ by less than this are never reported as regressions.
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut samples = samples.to_vec();
+
+        samples.sort();
+
+        let nanos = |duration: Duration| duration.as_nanos() as u64;
+
+        Stats {
+            min_ns: samples.first().copied().map_or(0, nanos),
+            median_ns: samples.get(samples.len() / 2).copied().map_or(0, nanos),
+            max_ns: samples.last().copied().map_or(0, nanos),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+            Duration::from_nanos(self.min_ns),
+            Duration::from_nanos(self.median_ns),
+            Duration::from_nanos(self.max_ns)
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: Year,
+    pub day: Day,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl Measurement {
+    pub fn new((year, day): Puzzle, timings: &Timings) -> Self {
+        Measurement {
+            year,
+            day,
+            parse: Stats::new(&timings.parse),
+            part1: Stats::new(&timings.part1),
+            part2: Stats::new(&timings.part2),
+        }
+    }
+
+    pub fn phases(&self) -> [(&'static str, Stats); 3] {
+        [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ]
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub runs: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn load(filename: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(filename).map_err(|error| error.to_string())?;
+
+        serde_json::from_str(&content).map_err(|error| error.to_string())
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).expect("json");
+
+        fs::write(filename, content + "\n").map_err(|error| error.to_string())
+    }
+
+    pub fn find(&self, (year, day): Puzzle) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|measurement| measurement.year == year && measurement.day == day)
+    }
+}
+
+/// How much slower a median got relative to the baseline, if beyond `threshold` percent.
+pub fn regression(current: &Stats, baseline: &Stats, threshold: f64) -> Option<f64> {
+    let current = current.median_ns as f64;
+    let baseline = baseline.median_ns as f64;
+
+    let slower = current - baseline > NOISE_FLOOR.as_nanos() as f64;
+    let change = (current - baseline) / baseline.max(1.0) * 100.0;
+
+    match slower && change > threshold {
+        true => Some(change),
+        false => None,
+    }
+}
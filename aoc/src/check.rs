@@ -0,0 +1,70 @@
+use aoc_utils::{Answers, Day, Part};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Missing,
+    Recorded,
+}
+
+impl Display for Status {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(formatter, "pass"),
+            Status::Fail(expected) => write!(formatter, "FAIL (expected {})", expected),
+            Status::Missing => write!(formatter, "missing"),
+            Status::Recorded => write!(formatter, "recorded"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+    pub recorded: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, status: &Status) {
+        match status {
+            Status::Pass => self.passed += 1,
+            Status::Fail(_) => self.failed += 1,
+            Status::Missing => self.missing += 1,
+            Status::Recorded => self.recorded += 1,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} passed, {} failed, {} missing, {} recorded",
+            self.passed, self.failed, self.missing, self.recorded
+        )
+    }
+}
+
+/// Compares an answer against the known one, filling it in when missing and `record` is set.
+pub fn check_answer(
+    answers: &mut Answers,
+    day: Day,
+    part: Part,
+    answer: &str,
+    record: bool,
+) -> Status {
+    match answers.get(day, part) {
+        Some(expected) if expected == answer => Status::Pass,
+        Some(expected) => Status::Fail(expected.to_string()),
+        None if record => {
+            answers.insert(day, part, answer);
+
+            Status::Recorded
+        }
+        None => Status::Missing,
+    }
+}
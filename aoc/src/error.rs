@@ -0,0 +1,75 @@
+use aoc_2021::alu::AluError;
+use aoc_utils::{AnswersError, Day, InputError, ParseError, Year};
+use std::fmt::{Display, Formatter};
+use std::process::ExitCode;
+
+#[derive(Debug)]
+pub enum Error {
+    Usage(String),
+    UnknownYear(Year),
+    UnknownDay(Year, Day),
+    Input(InputError),
+    Parse(ParseError),
+    Alu(AluError),
+    Answers(AnswersError),
+    Regression(usize),
+    Report(String),
+    Slower(usize),
+}
+
+impl Error {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::Usage(_) => ExitCode::from(2),
+            Error::UnknownYear(_)
+            | Error::UnknownDay(_, _)
+            | Error::Input(_)
+            | Error::Answers(_)
+            | Error::Report(_) => ExitCode::FAILURE,
+            Error::Parse(_) | Error::Alu(_) => ExitCode::from(3),
+            Error::Regression(_) => ExitCode::from(4),
+            Error::Slower(_) => ExitCode::from(5),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) => write!(formatter, "{}\n\n{}", message, crate::USAGE),
+            Error::UnknownYear(year) => write!(formatter, "unknown year {}", year),
+            Error::UnknownDay(year, day) => write!(formatter, "unknown day {} of {}", day, year),
+            Error::Input(error) => write!(formatter, "{}", error),
+            Error::Parse(error) => write!(formatter, "{}", error),
+            Error::Alu(error) => write!(formatter, "{}", error),
+            Error::Answers(error) => write!(formatter, "{}", error),
+            Error::Regression(count) => write!(formatter, "{} answers differ", count),
+            Error::Report(message) => write!(formatter, "{}", message),
+            Error::Slower(count) => write!(formatter, "{} timings regressed", count),
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(error: InputError) -> Self {
+        Error::Input(error)
+    }
+}
+
+impl From<AnswersError> for Error {
+    fn from(error: AnswersError) -> Self {
+        Error::Answers(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<AluError> for Error {
+    fn from(error: AluError) -> Self {
+        Error::Alu(error)
+    }
+}
@@ -0,0 +1,217 @@
+use aoc_2021::alu::{self, Alu, Disassembly};
+use aoc_utils::{Answers, Input, Registry, Year};
+use args::{AluCommand, BenchOptions, Command, Mode, Puzzle};
+use bench::{Measurement, Report};
+use check::{Status, Summary};
+use error::Error;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::env;
+use std::process::ExitCode;
+
+mod args;
+mod bench;
+mod check;
+mod error;
+
+const USAGE: &str =
+    "usage: aoc run <year|all> [day|start..=end|all] [--part 1|2] [--input <path|->] [--check|--record]
+       aoc bench <year|all> [day|start..=end|all] [--runs <n>] [--output <json>]
+                 [--baseline <json>] [--threshold <percent>]
+       aoc alu <trace <digits>|disassemble> [--input <path|->]
+
+--check compares answers against aoc-<year>/answers.toml, --record also fills in missing ones
+
+bench times parsing, part 1 and part 2 over --runs warm runs (default 10), optionally
+writes them to --output and flags medians over --threshold percent (default 10)
+slower than --baseline
+
+alu loads a 2021 day 24 program; trace runs it on the digits and prints the registers after
+every instruction, disassemble lists it block by block with each block's constants
+
+inputs are read from --input (- for stdin), then $AOC_INPUT_DIR/<year>/dayNN.txt,
+then aoc-<year>/inputs/dayNN.txt
+
+exit codes:
+  0  success
+  1  unknown year or day, or missing input
+  2  invalid arguments
+  3  malformed input, or an ALU program that faults
+  4  answers differ from the answers file
+  5  timings regressed against the baseline";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let registry = build_registry();
+
+    match args::parse_command(&args, &registry).and_then(|command| execute(command, &registry)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+
+            error.exit_code()
+        }
+    }
+}
+
+fn execute(command: Command, registry: &Registry) -> Result<(), Error> {
+    match command {
+        Command::Run(puzzles, parts, input, Mode::Print) => {
+            for (year, day) in puzzles {
+                let solver = registry.solver(year, day).expect("solver");
+                let filename = registry.input(year, day, input.as_deref())?;
+
+                let answers = solver(&Input::read(&filename)?, &parts)?;
+
+                for (part, answer) in parts.iter().zip(answers) {
+                    println!("{} day {:02} part {}: {}", year, day, part, answer);
+                }
+            }
+        }
+        Command::Run(puzzles, parts, input, mode) => {
+            let mut known_answers: BTreeMap<Year, Answers> = BTreeMap::new();
+            let mut summary = Summary::default();
+
+            for (year, day) in puzzles {
+                let solver = registry.solver(year, day).expect("solver");
+                let filename = registry.input(year, day, input.as_deref())?;
+
+                let answers = solver(&Input::read(&filename)?, &parts)?;
+
+                let known = match known_answers.entry(year) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(registry.answers(year)?),
+                };
+
+                let mut recorded = false;
+
+                for (&part, answer) in parts.iter().zip(answers) {
+                    let status =
+                        check::check_answer(known, day, part, &answer, mode == Mode::Record);
+
+                    recorded |= matches!(status, Status::Recorded);
+                    summary.add(&status);
+
+                    println!(
+                        "{} day {:02} part {}: {} {}",
+                        year, day, part, answer, status
+                    );
+                }
+
+                if recorded {
+                    known.save()?;
+                }
+            }
+
+            println!("{}", summary);
+
+            if summary.failed > 0 {
+                return Err(Error::Regression(summary.failed));
+            }
+        }
+        Command::Bench(puzzles, options) => bench(&puzzles, &options, registry)?,
+        Command::Alu(command, input) => run_alu(&command, input.as_deref(), registry)?,
+        Command::Help => println!("{}", USAGE),
+    }
+
+    Ok(())
+}
+
+fn bench(puzzles: &[Puzzle], options: &BenchOptions, registry: &Registry) -> Result<(), Error> {
+    let baseline = match &options.baseline {
+        Some(filename) => Some(
+            Report::load(filename)
+                .map_err(|error| Error::Report(format!("cannot read {}: {}", filename, error)))?,
+        ),
+        None => None,
+    };
+
+    let mut report = Report {
+        runs: options.runs,
+        ..Report::default()
+    };
+    let mut regressions = 0;
+
+    for &(year, day) in puzzles {
+        let bencher = registry.bencher(year, day).expect("bencher");
+        let filename = registry.input(year, day, None)?;
+
+        let input = Input::read(&filename)?;
+
+        let measurement = Measurement::new((year, day), &bencher(&input, options.runs)?);
+        let previous = baseline
+            .as_ref()
+            .and_then(|baseline| baseline.find((year, day)));
+
+        for (index, (phase, stats)) in measurement.phases().into_iter().enumerate() {
+            print!("{} day {:02} {:<6}  {}", year, day, phase, stats);
+
+            if let Some(previous) = previous {
+                let (_, previous) = previous.phases()[index];
+
+                if let Some(change) = bench::regression(&stats, &previous, options.threshold) {
+                    regressions += 1;
+
+                    print!("  REGRESSION (+{:.0}%)", change);
+                }
+            }
+
+            println!();
+        }
+
+        report.measurements.push(measurement);
+    }
+
+    if let Some(filename) = &options.output {
+        report
+            .save(filename)
+            .map_err(|error| Error::Report(format!("cannot write {}: {}", filename, error)))?;
+    }
+
+    if regressions > 0 {
+        return Err(Error::Slower(regressions));
+    }
+
+    Ok(())
+}
+
+fn run_alu(command: &AluCommand, input: Option<&str>, registry: &Registry) -> Result<(), Error> {
+    let filename = registry.input(aoc_2021::YEAR, 24, input)?;
+    let input = Input::read(&filename)?;
+
+    let program = alu::parse_program(&input).map_err(|error| error.in_file(input.name()))?;
+
+    match command {
+        AluCommand::Trace(digits) => {
+            let mut alu = Alu::new(&program);
+            let mut digits = digits.iter().copied();
+
+            while !alu.is_halted() {
+                let index = alu.next();
+
+                alu.step(&mut digits)?;
+
+                println!(
+                    "{:>4}  {:<12}{}",
+                    index,
+                    program[index].to_string(),
+                    alu.registers()
+                );
+            }
+        }
+        AluCommand::Disassemble => print!("{}", Disassembly::new(&program)),
+    }
+
+    Ok(())
+}
+
+fn build_registry() -> Registry {
+    let mut registry = Registry::new();
+
+    aoc_2020::register(&mut registry);
+    aoc_2021::register(&mut registry);
+    aoc_2023::register(&mut registry);
+
+    registry
+}